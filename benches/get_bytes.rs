@@ -0,0 +1,99 @@
+//! Measure the scaling of `Container::get_bytes` with the number of reading threads.
+//!
+//! Run with `cargo bench --bench get_bytes`.
+
+use jubako::creator;
+use std::io::Read;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const CONTENT_COUNT: usize = 10_000;
+const CONTENT_SIZE: usize = 4 * 1024;
+const READ_COUNT: usize = 200_000;
+
+// Contents are only accessed through their content address, no entry store is needed.
+struct NoEntryStore;
+
+impl creator::EntryStoreCreatorTrait for NoEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        _directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        Ok(())
+    }
+}
+
+fn create_container(path: &camino::Utf8Path) -> Vec<jubako::ContentAddress> {
+    let mut creator = creator::BasicCreator::new(
+        path,
+        creator::ConcatMode::OneFile,
+        jubako::VendorId::from([1, 0, 0, 0]),
+        creator::Compression::None,
+        Arc::new(()),
+    )
+    .unwrap();
+    let addresses = (0..CONTENT_COUNT)
+        .map(|i| {
+            let content = vec![(i % 256) as u8; CONTENT_SIZE];
+            creator
+                .add_content(Box::new(std::io::Cursor::new(content)), Default::default())
+                .unwrap()
+        })
+        .collect();
+    creator.finalize(Box::new(NoEntryStore), vec![]).unwrap();
+    addresses
+}
+
+/// Read `READ_COUNT` contents, shared between `thread_count` threads.
+fn read_contents(
+    container: &jubako::reader::Container,
+    addresses: &[jubako::ContentAddress],
+    thread_count: usize,
+) -> Duration {
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        for t in 0..thread_count {
+            scope.spawn(move || {
+                let mut buf = Vec::with_capacity(CONTENT_SIZE);
+                for i in (t..READ_COUNT).step_by(thread_count) {
+                    // Spread the reads over the whole container.
+                    let address = addresses[(i * 7919) % addresses.len()];
+                    let bytes = container
+                        .get_bytes(address)
+                        .unwrap()
+                        .and_then(|m| m.transpose())
+                        .expect("content address should be valid")
+                        .unwrap();
+                    buf.clear();
+                    bytes.stream().read_to_end(&mut buf).unwrap();
+                    assert_eq!(buf.len(), CONTENT_SIZE);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn main() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = camino::Utf8PathBuf::try_from(temp_dir.path().join("bench.jbk")).unwrap();
+    let addresses = create_container(&path);
+    let container = jubako::reader::Container::new(&path).unwrap();
+    // Warm up the caches (os page cache and clusters).
+    read_contents(&container, &addresses, 1);
+
+    println!(
+        "Available parallelism: {}",
+        std::thread::available_parallelism().map_or(0, |n| n.get())
+    );
+    let mut reference = None;
+    for thread_count in [1, 2, 4, 8, 16] {
+        let duration = read_contents(&container, &addresses, thread_count);
+        let reference = *reference.get_or_insert(duration);
+        println!(
+            "{thread_count:>3} threads: {:>8.0} reads/s (speedup x{:.2})",
+            READ_COUNT as f64 / duration.as_secs_f64(),
+            reference.as_secs_f64() / duration.as_secs_f64()
+        );
+    }
+}
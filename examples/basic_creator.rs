@@ -77,26 +77,29 @@ impl CustomEntryStore {
 }
 
 impl EntryStoreCreatorTrait for CustomEntryStore {
-    fn finalize(self: Box<Self>, directory_pack: &mut jbk::creator::DirectoryPackCreator) {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut jbk::creator::DirectoryPackCreator,
+    ) -> jbk::creator::Result<()> {
         // We have to populate the DirectoryPack with our data.
 
         // First, we can add our (unique here) value store.
         directory_pack.add_value_store(self.value_store);
 
         // Then, add our (unique here) entry store.
-        let entry_store = jbk::creator::EntryStore::new(self.schema, self.entry_store.into_iter());
+        let entry_store = jbk::creator::EntryStore::new(self.schema, self.entry_store.into_iter())?;
         let entry_store_id = directory_pack.add_entry_store(entry_store);
 
         // We have to reference (a entry range in) our entry store to lets readers find it.
         // This is done with a "Index"
-        directory_pack.create_index(
+        directory_pack.create_range_index(
             "My own index", // This is the name of our index. Reader will seach for it.
             Default::default(),
-            0.into(), // The index is not sorted
             entry_store_id,
             3.into(),                  // Our index is 3 entries length
             jubako::EntryIdx::from(0), // starting at offset 0
         );
+        Ok(())
     }
 }
 
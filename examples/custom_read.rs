@@ -141,7 +141,7 @@ impl jbk::reader::builder::BuilderTrait for Builder {
 
     fn create_entry(&self, idx: jbk::EntryIdx) -> jbk::Result<Option<Self::Entry>> {
         // With this, we can read the bytes corresponding to our entry in the container.
-        let reader = self.store.get_entry_reader(idx);
+        let reader = self.store.get_entry_reader(idx)?;
         if reader.is_none() {
             return Ok(None);
         }
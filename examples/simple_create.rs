@@ -103,15 +103,14 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // Add the value store and the entry store the directory.
     directory_pack.add_value_store(value_store);
-    let entry_store = jbk::creator::EntryStore::new(entry_def, entry_store.into_iter());
+    let entry_store = jbk::creator::EntryStore::new(entry_def, entry_store.into_iter())?;
     let entry_store_id = directory_pack.add_entry_store(entry_store);
 
     // We have to reference (a entry range in) our entry store to lets readers find it.
     // This is done with a "Index"
-    directory_pack.create_index(
+    directory_pack.create_range_index(
         "My own index", // This is the name of our index. Reader will seach for it.
         Default::default(),
-        0.into(), // The index is not sorted
         entry_store_id,
         3.into(),                  // Our index is 3 entries length
         jubako::EntryIdx::from(0), // starting at offset 0
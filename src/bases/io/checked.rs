@@ -0,0 +1,61 @@
+use crate::bases::*;
+use fxhash::FxHashSet;
+use std::sync::Mutex;
+
+pub(super) const CHECK_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The regions of a source for which the crc has already been checked.
+#[derive(Debug, Default)]
+pub(super) struct CheckedRegions(Mutex<FxHashSet<(u64, u64)>>);
+
+impl CheckedRegions {
+    pub fn contains(&self, region: Region) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .contains(&(region.begin().into_u64(), region.end().into_u64()))
+    }
+
+    pub fn insert(&self, region: Region) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert((region.begin().into_u64(), region.end().into_u64()));
+    }
+
+    /// Check the crc of `region` (the crc is stored just after the region)
+    /// without loading the whole region in memory.
+    ///
+    /// Data is read with `read_exact`. The check is done only once per region.
+    pub fn check(
+        &self,
+        region: Region,
+        read_exact: impl Fn(Offset, &mut [u8]) -> std::io::Result<()>,
+    ) -> Result<()> {
+        if self.contains(region) {
+            return Ok(());
+        }
+        let mut digest = CRC.digest();
+        let mut buf = vec![0; CHECK_CHUNK_SIZE];
+        let mut offset = region.begin();
+        while offset < region.end() {
+            let chunk_size =
+                std::cmp::min((region.end() - offset).into_u64(), CHECK_CHUNK_SIZE as u64) as usize;
+            read_exact(offset, &mut buf[..chunk_size])?;
+            digest.update(&buf[..chunk_size]);
+            offset += chunk_size;
+        }
+        let mut expected_checksum = [0; 4];
+        read_exact(region.end(), &mut expected_checksum)?;
+        let checksum = digest.finalize();
+        if checksum != u32::from_be_bytes(expected_checksum) {
+            return Err(CorruptedFile {
+                buf: expected_checksum.to_vec(),
+                found_checksum: checksum.to_be_bytes(),
+            }
+            .into());
+        }
+        self.insert(region);
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use super::checked::CheckedRegions;
+use crate::bases::*;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// A storage from which a [`Reader`] can read Jubako packs.
+///
+/// Implement this trait to read containers stored anywhere (a blob store, a encrypted volume,
+/// a cache, ...) and create a reader on it with [`Reader::from_source`].
+/// A custom [`PackLocatorTrait`](crate::reader::PackLocatorTrait) can return readers
+/// on custom sources to locate the packs of a container.
+///
+/// Sources are shared between threads and may be read concurrently.
+pub trait SourceTrait: Send + Sync + std::fmt::Debug {
+    /// The size of the source.
+    fn size(&self) -> Size;
+
+    /// Read bytes at `offset` in `buf`, returning the number of bytes read.
+    ///
+    /// As [`std::io::Read::read`], less than `buf.len()` bytes may be read.
+    fn read_at(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<usize>;
+
+    /// Read exactly `buf.len()` bytes at `offset` in `buf`.
+    fn read_exact_at(&self, mut offset: Offset, mut buf: &mut [u8]) -> std::io::Result<()> {
+        while !buf.is_empty() {
+            match self.read_at(offset, buf) {
+                Ok(0) => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => {
+                    buf = &mut buf[n..];
+                    offset += n;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the `size` bytes at `offset` without copy, if the source has them in memory.
+    ///
+    /// `offset..offset+size` is always in the source.
+    /// Default implementation returns `None`: bytes are copied with [`Self::read_exact_at`].
+    fn slice(&self, _offset: Offset, _size: usize) -> Option<&[u8]> {
+        None
+    }
+}
+
+impl<S: SourceTrait + ?Sized> SourceTrait for Arc<S> {
+    fn size(&self) -> Size {
+        (**self).size()
+    }
+    fn read_at(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<usize> {
+        (**self).read_at(offset, buf)
+    }
+    fn read_exact_at(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<()> {
+        (**self).read_exact_at(offset, buf)
+    }
+    fn slice(&self, offset: Offset, size: usize) -> Option<&[u8]> {
+        (**self).slice(offset, size)
+    }
+}
+
+/// A [`Source`] reading a [`SourceTrait`].
+#[derive(Debug)]
+pub(crate) struct CustomSource<S> {
+    source: S,
+    checked_regions: CheckedRegions,
+}
+
+impl<S: SourceTrait> CustomSource<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            checked_regions: Default::default(),
+        }
+    }
+}
+
+impl<S: SourceTrait + 'static> Source for CustomSource<S> {
+    fn size(&self) -> Size {
+        self.source.size()
+    }
+
+    fn read(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.source.read_at(offset, buf)
+    }
+
+    fn read_exact(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<()> {
+        self.source.read_exact_at(offset, buf)
+    }
+
+    fn get_slice(&self, region: ARegion, block_check: BlockCheck) -> Result<Cow<'_, [u8]>> {
+        let size = region.size().into_usize();
+        let full_size = size + block_check.size();
+        let mut data = match self.source.slice(region.begin(), full_size) {
+            Some(slice) => Cow::Borrowed(slice),
+            None => {
+                let mut buf = vec![0; full_size];
+                self.source.read_exact_at(region.begin(), &mut buf)?;
+                Cow::Owned(buf)
+            }
+        };
+        if let BlockCheck::Crc32 = block_check {
+            assert_slice_crc(&data)?;
+        }
+        match &mut data {
+            Cow::Borrowed(slice) => *slice = &slice[..size],
+            Cow::Owned(buf) => buf.truncate(size),
+        }
+        Ok(data)
+    }
+
+    fn cut(
+        self: Arc<Self>,
+        region: Region,
+        block_check: BlockCheck,
+        in_memory: bool,
+    ) -> Result<(Arc<dyn Source>, Region)> {
+        let full_size = region.size().into_u64() as usize + block_check.size();
+        match self.source.slice(region.begin(), full_size) {
+            Some(slice) => {
+                if let BlockCheck::Crc32 = block_check {
+                    assert_slice_crc(slice)?;
+                }
+            }
+            None if in_memory => {
+                let mut buf = vec![0; full_size];
+                self.source.read_exact_at(region.begin(), &mut buf)?;
+                if let BlockCheck::Crc32 = block_check {
+                    assert_slice_crc(&buf)?;
+                }
+                return Ok((
+                    Arc::new(buf),
+                    Region::new_from_size(Offset::zero(), region.size()),
+                ));
+            }
+            None => {
+                if let BlockCheck::Crc32 = block_check {
+                    self.checked_regions
+                        .check(region, |offset, buf| self.source.read_exact_at(offset, buf))?;
+                }
+            }
+        }
+        Ok((self, region))
+    }
+
+    fn display(&self) -> String {
+        format!("{:?}", self.source)
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// A source reading at most 3 bytes at a time.
+    #[derive(Debug)]
+    pub struct ReadSource(pub Vec<u8>);
+
+    impl SourceTrait for ReadSource {
+        fn size(&self) -> Size {
+            self.0.len().into()
+        }
+        fn read_at(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<usize> {
+            let data = self.0.get(offset.force_into_usize()..).unwrap_or_default();
+            let size = std::cmp::min(std::cmp::min(buf.len(), data.len()), 3);
+            buf[..size].copy_from_slice(&data[..size]);
+            Ok(size)
+        }
+    }
+
+    /// A source giving access to its bytes without copy.
+    #[derive(Debug)]
+    struct SliceSource(Vec<u8>);
+
+    impl SourceTrait for SliceSource {
+        fn size(&self) -> Size {
+            self.0.len().into()
+        }
+        fn read_at(&self, _offset: Offset, _buf: &mut [u8]) -> std::io::Result<usize> {
+            unreachable!("Bytes are accessed with `slice`")
+        }
+        fn slice(&self, offset: Offset, size: usize) -> Option<&[u8]> {
+            Some(&self.0[offset.force_into_usize()..offset.force_into_usize() + size])
+        }
+    }
+
+    fn block_with_crc(size: usize) -> Vec<u8> {
+        let mut data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        let crc = CRC.checksum(&data);
+        data.extend_from_slice(&crc.to_be_bytes());
+        data
+    }
+
+    fn sources(data: Vec<u8>) -> [Arc<dyn Source>; 2] {
+        [
+            Arc::new(CustomSource::new(ReadSource(data.clone()))),
+            Arc::new(CustomSource::new(SliceSource(data))),
+        ]
+    }
+
+    #[rustest::test]
+    fn test_read_exact_at() {
+        let source = ReadSource((0..10).collect());
+        let mut buf = [0; 8];
+        source.read_exact_at(Offset::from(1_u64), &mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let err = source
+            .read_exact_at(Offset::from(5_u64), &mut buf)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[rustest::test]
+    fn test_get_slice() {
+        let data = block_with_crc(100);
+        let region = ARegion::new(Offset::zero(), Offset::from(100_u64));
+        for source in sources(data.clone()) {
+            let slice = source.get_slice(region, BlockCheck::Crc32).unwrap();
+            assert_eq!(&*slice, &data[..100]);
+        }
+
+        let mut corrupted = data.clone();
+        corrupted[10] ^= 0xFF;
+        for source in sources(corrupted) {
+            let err = source.get_slice(region, BlockCheck::Crc32).unwrap_err();
+            assert!(matches!(*err, ErrorKind::Corrupted(_)));
+            assert!(source.get_slice(region, BlockCheck::None).is_ok());
+        }
+    }
+
+    #[rustest::test]
+    fn test_cut() {
+        let data = block_with_crc(100);
+        let region = Region::new_from_size(Offset::zero(), Size::from(100_u64));
+        for in_memory in [true, false] {
+            for source in sources(data.clone()) {
+                let (cut, cut_region) = source.cut(region, BlockCheck::Crc32, in_memory).unwrap();
+                assert_eq!(cut_region.size(), region.size());
+                let slice = cut
+                    .get_slice(cut_region.try_into().unwrap(), BlockCheck::None)
+                    .unwrap();
+                assert_eq!(&*slice, &data[..100]);
+            }
+
+            let mut corrupted = data.clone();
+            corrupted[99] ^= 0xFF;
+            for source in sources(corrupted) {
+                let err = source
+                    .cut(region, BlockCheck::Crc32, in_memory)
+                    .unwrap_err();
+                assert!(matches!(*err, ErrorKind::Corrupted(_)));
+            }
+        }
+    }
+}
@@ -1,20 +1,23 @@
+use super::checked::CheckedRegions;
 use crate::bases::*;
-use log::warn;
 #[cfg(unix)]
 use memmap2::Advice;
 use memmap2::MmapOptions;
 use std::borrow::Cow;
 use std::fs::File;
-use std::io;
-use std::io::{Read, Seek, SeekFrom};
-use std::ops::Deref;
+use std::io::{Seek, SeekFrom};
 use std::sync::Arc;
-use std::sync::Mutex;
 
+/// A source reading a file.
+///
+/// Reads are positional (`pread` like) and do not share a file cursor,
+/// so concurrent reads from different threads do not block each other.
+/// On platforms without positional reads (neither unix nor windows), reads are serialized.
 pub struct FileSource {
-    source: Mutex<io::BufReader<File>>,
+    source: File,
     path: std::path::PathBuf,
     len: u64,
+    checked_regions: CheckedRegions,
 }
 
 impl FileSource {
@@ -26,20 +29,71 @@ impl FileSource {
 
     pub fn new(mut source: File) -> std::io::Result<Self> {
         let len = source.seek(SeekFrom::End(0))?;
-        source.seek(SeekFrom::Start(0))?;
-        let source = io::BufReader::with_capacity(1024, source);
         Ok(FileSource {
-            source: Mutex::new(source),
+            source,
             len,
             path: "".into(),
+            checked_regions: Default::default(),
         })
     }
+
+    fn is_checked(&self, region: Region) -> bool {
+        self.checked_regions.contains(region)
+    }
+
+    fn mark_checked(&self, region: Region) {
+        self.checked_regions.insert(region)
+    }
+
+    /// Check the crc of `region` (the crc is stored just after the region)
+    /// without loading the whole region in memory.
+    ///
+    /// The check is done only once per region.
+    fn check_region(&self, region: Region) -> Result<()> {
+        self.checked_regions
+            .check(region, |offset, buf| self.read_exact(offset, buf))
+    }
+}
+
+#[cfg(unix)]
+#[inline]
+fn read_at(file: &File, offset: u64, buf: &mut [u8]) -> std::io::Result<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
 }
 
-impl Deref for FileSource {
-    type Target = Mutex<io::BufReader<File>>;
-    fn deref(&self) -> &Self::Target {
-        &self.source
+#[cfg(windows)]
+#[inline]
+fn read_at(file: &File, offset: u64, buf: &mut [u8]) -> std::io::Result<usize> {
+    // `seek_read` moves the file cursor, but we never use it.
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+#[cfg(not(any(unix, windows)))]
+#[inline]
+fn read_at(file: &File, offset: u64, buf: &mut [u8]) -> std::io::Result<usize> {
+    use std::io::Read;
+    // No positional read on this platform: seek the file cursor and read,
+    // one read at a time as the cursor is shared.
+    static CURSOR_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let _lock = CURSOR_LOCK.lock().unwrap();
+    let mut file = file;
+    file.seek(SeekFrom::Start(offset))?;
+    file.read(buf)
+}
+
+impl std::fmt::Debug for FileSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "File {}", self.path.display())
+    }
+}
+
+impl SourceTrait for FileSource {
+    fn size(&self) -> Size {
+        self.len.into()
+    }
+
+    fn read_at(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<usize> {
+        read_at(&self.source, offset.into_u64(), buf)
     }
 }
 
@@ -66,17 +120,11 @@ impl Source for FileSource {
         (self.len).into()
     }
     fn read(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<usize> {
-        let mut f = self.lock().unwrap();
-        // TODO: Use `read_at`/`seek_read`
-        f.seek(SeekFrom::Start(offset.into_u64()))?;
-        f.read(buf)
+        self.read_at(offset, buf)
     }
 
     fn read_exact(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<()> {
-        let mut f = self.lock().unwrap();
-        // TODO: Use `read_at`/`seek_read`
-        f.seek(SeekFrom::Start(offset.into_u64()))?;
-        f.read_exact(buf)
+        self.read_exact_at(offset, buf)
     }
 
     fn get_slice(&self, region: ARegion, block_check: BlockCheck) -> Result<Cow<'_, [u8]>> {
@@ -97,7 +145,7 @@ impl Source for FileSource {
     ) -> Result<(Arc<dyn Source>, Region)> {
         if !move_to_memory(region) || !in_memory {
             if let BlockCheck::Crc32 = block_check {
-                warn!("Check of not memory block is not implemented");
+                self.check_region(region)?;
             }
             return Ok((self, region));
         }
@@ -105,12 +153,8 @@ impl Source for FileSource {
         // We know from previous test that region.size() is addressable.
         let full_size = ASize::new(region.size().into_u64() as usize + block_check.size());
         if full_size.into_u64() < 4 * 1024 {
-            let mut f = self.lock().unwrap();
-            let mut buf = Vec::with_capacity(full_size.into_usize());
-            f.seek(SeekFrom::Start(region.begin().into_u64()))?;
-            f.by_ref()
-                .take(full_size.into_u64())
-                .read_to_end(&mut buf)?;
+            let mut buf = vec![0; full_size.into_usize()];
+            self.read_exact(region.begin(), &mut buf)?;
             if let BlockCheck::Crc32 = block_check {
                 assert_slice_crc(&buf)?;
             }
@@ -124,11 +168,14 @@ impl Source for FileSource {
                 .offset(region.begin().into_u64())
                 .len(full_size.into_usize())
                 .populate();
-            let mmap = unsafe { mmap_options.map(self.source.lock().unwrap().get_ref())? };
+            let mmap = unsafe { mmap_options.map(&self.source)? };
             #[cfg(unix)]
             mmap.advise(Advice::WillNeed)?;
             if let BlockCheck::Crc32 = block_check {
-                assert_slice_crc(&mmap)?;
+                if !self.is_checked(region) {
+                    assert_slice_crc(&mmap)?;
+                    self.mark_checked(region);
+                }
             }
 
             Ok((
@@ -142,3 +189,90 @@ impl Source for FileSource {
         format!("File {}", self.path.display())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::checked::CHECK_CHUNK_SIZE;
+    use super::*;
+    use std::io::Write;
+
+    fn block_with_crc(size: usize) -> Vec<u8> {
+        let mut data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        let crc = CRC.checksum(&data);
+        data.extend_from_slice(&crc.to_be_bytes());
+        data
+    }
+
+    fn file_source(data: &[u8]) -> Arc<FileSource> {
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(data).unwrap();
+        Arc::new(FileSource::new(file).unwrap())
+    }
+
+    #[rustest::test]
+    fn test_check_not_in_memory() {
+        let data = block_with_crc(3 * CHECK_CHUNK_SIZE + 10);
+        let region = Region::new_from_size(Offset::zero(), Size::from(data.len() - 4));
+        let source = file_source(&data);
+        assert!(!source.is_checked(region));
+        assert!(source.clone().cut(region, BlockCheck::Crc32, false).is_ok());
+        assert!(source.is_checked(region));
+
+        let mut corrupted = data.clone();
+        corrupted[2 * CHECK_CHUNK_SIZE + 5] ^= 0xFF;
+        let source = file_source(&corrupted);
+        let err = source
+            .clone()
+            .cut(region, BlockCheck::Crc32, false)
+            .unwrap_err();
+        assert!(matches!(*err, ErrorKind::Corrupted(_)));
+        assert!(!source.is_checked(region));
+    }
+
+    #[rustest::test]
+    fn test_check_mmap() {
+        let data = block_with_crc(10 * 1024);
+        let region = Region::new_from_size(Offset::zero(), Size::from(data.len() - 4));
+        let source = file_source(&data);
+        assert!(source.clone().cut(region, BlockCheck::Crc32, true).is_ok());
+        assert!(source.is_checked(region));
+
+        let mut corrupted = data.clone();
+        corrupted[0] ^= 0xFF;
+        let source = file_source(&corrupted);
+        let err = source
+            .clone()
+            .cut(region, BlockCheck::Crc32, true)
+            .unwrap_err();
+        assert!(matches!(*err, ErrorKind::Corrupted(_)));
+    }
+
+    #[rustest::test]
+    fn test_concurrent_read() {
+        let data = block_with_crc(64 * 1024);
+        let source = file_source(&data);
+        std::thread::scope(|scope| {
+            for t in 0..8 {
+                let source = &source;
+                let data = &data;
+                scope.spawn(move || {
+                    let mut buf = [0; 100];
+                    for i in 0..100 {
+                        let offset = (t * 7919 + i * 613) % (data.len() - buf.len());
+                        source.read_exact(Offset::from(offset), &mut buf).unwrap();
+                        assert_eq!(&buf, &data[offset..offset + buf.len()]);
+                    }
+                });
+            }
+        });
+        let mut buf = [0; 10];
+        let err = source
+            .read_exact(Offset::from(data.len() - 5), &mut buf)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            source.read(Offset::from(data.len() - 5), &mut buf).unwrap(),
+            5
+        );
+    }
+}
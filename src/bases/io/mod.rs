@@ -1,10 +1,14 @@
 mod buffer;
+mod checked;
 mod compression;
+mod custom;
 mod file;
 
 use crate::bases::types::*;
 use crate::bases::{ARegion, Region};
 pub(crate) use compression::*;
+pub(crate) use custom::CustomSource;
+pub use custom::SourceTrait;
 pub use file::FileSource;
 use std::borrow::Cow;
 use std::fmt;
@@ -51,6 +55,10 @@ mod tests {
         FileSource::new(file).unwrap().into()
     }
 
+    fn create_custom_reader(data: &[u8]) -> CheckReader {
+        CustomSource::new(custom::tests::ReadSource(data.to_vec())).into()
+    }
+
     #[cfg(feature = "lz4")]
     fn create_lz4_reader(data: &[u8]) -> CheckReader {
         let compressed_content = {
@@ -110,6 +118,7 @@ mod tests {
     pub enum TestReaderName {
         Buf,
         File,
+        Custom,
         #[cfg(feature = "lz4")]
         Lz4,
         #[cfg(feature = "lzma")]
@@ -127,6 +136,7 @@ mod tests {
     #[rustest::fixture(params:TestReaderName = [
         TestReaderName::Buf,
         TestReaderName::File,
+        TestReaderName::Custom,
         #[cfg(feature = "lz4")]
         TestReaderName::Lz4,
         #[cfg(feature = "lzma")]
@@ -138,6 +148,7 @@ mod tests {
         match name {
             TestReaderName::Buf => create_buf_reader,
             TestReaderName::File => create_file_reader,
+            TestReaderName::Custom => create_custom_reader,
             #[cfg(feature = "lz4")]
             TestReaderName::Lz4 => create_lz4_reader,
             #[cfg(feature = "lzma")]
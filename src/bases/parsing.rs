@@ -9,6 +9,8 @@ pub trait Parser {
     fn tell(&self) -> Offset;
     fn global_offset(&self) -> Offset;
     fn skip(&mut self, size: usize) -> Result<()>;
+    /// The number of bytes remaining in the parser.
+    fn remaining(&self) -> usize;
 
     fn read_u8(&mut self) -> Result<u8> {
         let slice = self.read_slice(1)?;
@@ -163,6 +165,10 @@ impl Parser for SliceParser<'_> {
         Ok(())
     }
 
+    fn remaining(&self) -> usize {
+        self.slice.len() - self.offset
+    }
+
     #[cfg(test)]
     fn tell(&self) -> Offset {
         self.offset.into()
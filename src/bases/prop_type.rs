@@ -7,10 +7,14 @@ pub(crate) enum PropType {
     ContentAddress = 0b0001_0000,
     UnsignedInt = 0b0010_0000,
     SignedInt = 0b0011_0000,
+    Range = 0b0100_0000,
     Array = 0b0101_0000,
+    Float = 0b0110_0000,
+    Bool = 0b0111_0000,
     VariantId = 0b1000_0000,
     DeportedUnsignedInt = 0b1010_0000,
     DeportedSignedInt = 0b1011_0000,
+    FixedBytes = 0b1100_0000,
 }
 
 impl TryFrom<u8> for PropType {
@@ -21,11 +25,14 @@ impl TryFrom<u8> for PropType {
             0b0001_0000 => Ok(Self::ContentAddress),
             0b0010_0000 => Ok(Self::UnsignedInt),
             0b0011_0000 => Ok(Self::SignedInt),
-            0b0100_0000 => todo!(), // Redirection and SubRange
+            0b0100_0000 => Ok(Self::Range),
             0b0101_0000 => Ok(Self::Array),
+            0b0110_0000 => Ok(Self::Float),
+            0b0111_0000 => Ok(Self::Bool),
             0b1000_0000 => Ok(Self::VariantId),
             0b1010_0000 => Ok(Self::DeportedUnsignedInt),
             0b1011_0000 => Ok(Self::DeportedSignedInt),
+            0b1100_0000 => Ok(Self::FixedBytes),
             _ => Err(format_error!(format!("Invalid property type ({v})"))),
         }
     }
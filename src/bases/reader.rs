@@ -9,7 +9,7 @@ use super::Parsable;
 use super::Parser;
 use super::SizedBlockParsable;
 use super::SliceParser;
-use super::{Region, Source};
+use super::{CustomSource, Region, Source, SourceTrait};
 use std::borrow::Cow;
 use std::sync::Arc;
 
@@ -34,6 +34,11 @@ impl Reader {
         Self { source, region }
     }
 
+    /// Create a reader on a custom source.
+    pub fn from_source(source: impl SourceTrait + 'static) -> Self {
+        Self::from(CustomSource::new(source))
+    }
+
     pub(crate) fn global_offset(&self) -> Offset {
         self.region.begin()
     }
@@ -75,6 +80,15 @@ impl Reader {
         T::finalize(intermediate, sized_offset.offset, self)
     }
 
+    /// Check the crc32 of the block of `size` bytes at `offset`.
+    ///
+    /// The crc32 is stored just after the block.
+    pub(crate) fn check_block(&self, offset: Offset, size: ASize) -> Result<()> {
+        let region = self.region.cut_rel_asize(offset, size);
+        self.source.get_slice(region, BlockCheck::Crc32)?;
+        Ok(())
+    }
+
     pub(crate) fn get_byte_slice(&self, offset: Offset, size: Size) -> ByteSlice<'_> {
         let region = self.region.cut_rel(offset, size);
         ByteSlice::new_from_parts(&self.source, region)
@@ -108,6 +122,30 @@ impl Reader {
         Ok(Self::new_from_parts(source, region))
     }
 
+    /// Call `f` on successive chunks of the `size` bytes at `offset`.
+    ///
+    /// Bytes are loaded in memory (mmapped for file) by chunks, so big regions
+    /// can be processed (hashed) without reading them through a stream.
+    pub(crate) fn read_region_chunks(
+        &self,
+        offset: Offset,
+        size: Size,
+        f: &mut dyn FnMut(&[u8]),
+    ) -> Result<()> {
+        const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+        let mut current = offset;
+        let end = offset + size;
+        while current < end {
+            let chunk_size = Size::from(std::cmp::min(CHUNK_SIZE, (end - current).into_u64()));
+            let check_reader = self.cut_check(current, chunk_size, BlockCheck::None)?;
+            let slice = check_reader
+                .get_slice(Offset::zero(), ASize::from(chunk_size.into_u64() as usize))?;
+            f(&slice);
+            current += chunk_size;
+        }
+        Ok(())
+    }
+
     pub(crate) fn cut_check(
         &self,
         offset: Offset,
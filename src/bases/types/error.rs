@@ -72,6 +72,13 @@ pub struct MissingFeatureError {
     pub msg: &'static str,
 }
 
+#[derive(Error, Debug)]
+#[error("Index {name} is not a {expected} index")]
+pub struct IndexKindError {
+    pub name: String,
+    pub expected: &'static str,
+}
+
 #[derive(Error, Debug)]
 /// Kind of error returned by Jubako.
 pub enum ErrorKind {
@@ -102,6 +109,10 @@ pub enum ErrorKind {
     /// the right feature.
     #[error(transparent)]
     MissingFeature(#[from] MissingFeatureError),
+
+    /// A operation has been requested on a index of the wrong kind.
+    #[error(transparent)]
+    IndexKind(#[from] IndexKindError),
 }
 
 #[derive(Error, Debug)]
@@ -159,6 +170,7 @@ impl_from_error!(FormatError);
 impl_from_error!(VersionError);
 impl_from_error!(MissingFeatureError);
 impl_from_error!(CorruptedFile);
+impl_from_error!(IndexKindError);
 
 impl From<FromUtf8Error> for Error {
     fn from(_e: FromUtf8Error) -> Error {
@@ -0,0 +1,58 @@
+use crate::bases::*;
+use std::ops::Deref;
+
+/// The bytes of a fixed bytes property.
+///
+/// Up to 32 bytes (a uuid, a blake3 or sha256 hash, ...) are stored inline, without allocation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FixedBytes(smallvec::SmallVec<[u8; 32]>);
+
+impl FixedBytes {
+    /// Create a FixedBytes taking `size` bytes from `parser`.
+    pub(crate) fn parse(size: usize, parser: &mut impl Parser) -> Result<Self> {
+        let mut data = smallvec::SmallVec::from_elem(0, size);
+        parser.read_data(&mut data)?;
+        Ok(Self(data))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl From<&[u8]> for FixedBytes {
+    fn from(value: &[u8]) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for FixedBytes {
+    fn from(value: &[u8; N]) -> Self {
+        Self(value.as_slice().into())
+    }
+}
+
+impl Deref for FixedBytes {
+    type Target = [u8];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for FixedBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+#[cfg(feature = "explorable_serde")]
+impl serde::Serialize for FixedBytes {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
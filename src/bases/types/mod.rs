@@ -5,6 +5,7 @@ mod base_array;
 mod byte_size;
 mod count;
 mod delayed;
+mod fixed_bytes;
 mod free_data;
 mod id;
 mod idx;
@@ -26,8 +27,9 @@ pub(crate) use count::Count;
 pub use delayed::Late;
 #[allow(unused_imports)]
 pub(crate) use error::MissingFeatureError;
-pub(crate) use error::{CorruptedFile, FormatError, VersionError};
+pub(crate) use error::{CorruptedFile, FormatError, IndexKindError, VersionError};
 pub use error::{Error, ErrorKind, Result};
+pub use fixed_bytes::FixedBytes;
 pub use free_data::{IndexFreeData, PackFreeData};
 pub(crate) use id::Id;
 pub(crate) use idx::{Idx, IndexTrait};
@@ -1,7 +1,7 @@
 use super::{ASize, EntryIdx, Offset, Size};
 use std::ops::{Add, Sub};
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Copy, Clone)]
 pub struct Range<T>
 where
     T: Copy + Sub,
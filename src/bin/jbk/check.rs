@@ -1,26 +1,77 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use jubako as jbk;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    Plain,
+    #[cfg(feature = "explorable_serde")]
+    Json,
+}
 
 #[derive(Parser, Debug)]
 pub struct Options {
     #[clap(value_parser)]
     infiles: Vec<PathBuf>,
+
+    #[arg(long, value_enum, default_value = "plain")]
+    format: Format,
 }
 
-fn check_one_file(path: &Path) -> jbk::Result<()> {
+#[cfg(feature = "explorable_serde")]
+#[derive(serde::Serialize)]
+struct FileReport<'a> {
+    path: &'a Path,
+    ok: bool,
+    report: jbk::reader::CheckReport,
+}
+
+fn check_one_file(path: &Path) -> jbk::Result<jbk::reader::CheckReport> {
     let pack = jbk::tools::open_pack(path)?;
-    if pack.check()? {
-        println!("Pack {} is ok", path.display());
+    if pack.get_manifest_pack_reader()?.is_some() {
+        // We have a manifest, check all packs of the container
+        jbk::reader::Container::new(path)?.check_report()
     } else {
-        println!("Pack {} s ko", path.display());
+        pack.check_report()
     }
-    Ok(())
 }
 
-pub fn run(options: Options) -> jbk::Result<()> {
-    for path in &options.infiles {
-        check_one_file(path)?;
+pub fn run(options: Options) -> jbk::Result<ExitCode> {
+    let mut all_ok = true;
+    match options.format {
+        Format::Plain => {
+            for path in &options.infiles {
+                let report = check_one_file(path)?;
+                if report.is_ok() {
+                    println!("{} is ok", path.display());
+                } else {
+                    println!("{} is ko", path.display());
+                    all_ok = false;
+                }
+                print!("{report}");
+            }
+        }
+        #[cfg(feature = "explorable_serde")]
+        Format::Json => {
+            let mut reports = Vec::with_capacity(options.infiles.len());
+            for path in &options.infiles {
+                let report = check_one_file(path)?;
+                all_ok &= report.is_ok();
+                reports.push(FileReport {
+                    path,
+                    ok: report.is_ok(),
+                    report,
+                });
+            }
+            serde_json::to_writer_pretty(std::io::stdout(), &reports)
+                .map_err(std::io::Error::from)?;
+            println!();
+        }
     }
-    Ok(())
+    Ok(if all_ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
 }
@@ -4,6 +4,7 @@ mod explore;
 mod locate;
 
 use clap::Parser;
+use std::process::ExitCode;
 
 const VERSION: &str = const_format::formatcp!(
     "{}{}",
@@ -31,20 +32,24 @@ enum Commands {
     Locate(locate::Options),
 }
 
-fn run() -> Result<(), Box<dyn std::error::Error>> {
+fn run() -> Result<ExitCode, Box<dyn std::error::Error>> {
     let options = Options::parse();
 
     match options.command {
-        Commands::Check(options) => check::run(options)?,
+        Commands::Check(options) => return Ok(check::run(options)?),
         Commands::Concat(options) => concat::run(options)?,
         Commands::Explore(options) => explore::run(options)?,
         Commands::Locate(options) => locate::run(options)?,
     }
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
-fn main() {
-    if let Err(e) = run() {
-        eprintln!("Error: {e}");
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
     }
 }
@@ -1,21 +1,61 @@
 use crate::bases::*;
+use sha2::Digest;
 use std::cmp;
 use std::fmt::Debug;
 use std::io::Read;
 
-#[derive(Clone, Copy)]
-pub(crate) enum CheckKind {
+/// The kind of a check stored in a check info.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CheckKind {
+    /// No check.
     None = 0,
+    /// Blake3 hash of the data.
     Blake3 = 1,
+    /// Sha256 hash of the data.
+    Sha256 = 2,
+    /// 64 bits xxh3 hash of the data.
+    /// Cheap to compute but not a cryptographic hash.
+    Xxh3 = 3,
+    /// Size of the data only.
+    Size = 4,
+    /// Ed25519 signature of the blake3 hash of the data.
+    ///
+    /// Signatures need a signing key and cannot be computed as other checks.
+    /// This kind is ignored in the check kinds given to creators,
+    /// use `ManifestSigner` (with feature `signature`) to sign a manifest pack.
+    /// Without feature `signature`, signatures are not verified.
+    Ed25519 = 5,
 }
 
 impl CheckKind {
-    pub(crate) fn block_size(self) -> ASize {
+    /// The size of the data of the check (without the kind byte).
+    fn data_size(self) -> usize {
         match self {
-            Self::None => BlockCheck::Crc32.size() + 1,
-            Self::Blake3 => BlockCheck::Crc32.size() + 33,
+            Self::None => 0,
+            Self::Blake3 => blake3::OUT_LEN,
+            Self::Sha256 => 32,
+            Self::Xxh3 => 8,
+            Self::Size => 8,
+            Self::Ed25519 => ED25519_PUBLIC_KEY_LENGTH + ED25519_SIGNATURE_LENGTH,
         }
-        .into()
+    }
+
+    /// The size of a check of this kind (kind byte and data).
+    pub(crate) fn check_size(self) -> usize {
+        1 + self.data_size()
+    }
+
+    /// The block size (including the crc) of a check info composed of checks of `kinds`.
+    ///
+    /// Signature are not computed from kinds and so are not counted.
+    pub(crate) fn block_size(kinds: &[CheckKind]) -> ASize {
+        let checks_size = kinds
+            .iter()
+            .filter(|k| !matches!(k, Self::None | Self::Ed25519))
+            .map(|k| k.check_size())
+            .sum::<usize>();
+        // An empty check info is a None check.
+        (BlockCheck::Crc32.size() + cmp::max(checks_size, 1)).into()
     }
 }
 
@@ -26,6 +66,10 @@ impl Parsable for CheckKind {
         match kind {
             0_u8 => Ok(CheckKind::None),
             1_u8 => Ok(CheckKind::Blake3),
+            2_u8 => Ok(CheckKind::Sha256),
+            3_u8 => Ok(CheckKind::Xxh3),
+            4_u8 => Ok(CheckKind::Size),
+            5_u8 => Ok(CheckKind::Ed25519),
             _ => Err(format_error!(&format!("Invalid check kind {kind}"), parser)),
         }
     }
@@ -46,20 +90,214 @@ impl Parsable for blake3::Hash {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+/// A ed25519 signature, as stored in the pack.
+///
+/// Key and signature are kept as bytes so signed packs can be read without feature `signature`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Ed25519Signature {
+    public_key: [u8; ED25519_PUBLIC_KEY_LENGTH],
+    signature: [u8; ED25519_SIGNATURE_LENGTH],
+}
+
+impl Ed25519Signature {
+    #[cfg(feature = "signature")]
+    fn verify(&self, digests: &Digests) -> bool {
+        let Ok(public_key) = ed25519_dalek::VerifyingKey::from_bytes(&self.public_key) else {
+            return false;
+        };
+        let signature = ed25519_dalek::Signature::from_bytes(&self.signature);
+        digests.blake3.is_some_and(|hash| {
+            public_key
+                .verify_strict(hash.as_bytes(), &signature)
+                .is_ok()
+        })
+    }
+
+    /// Signatures cannot be verified without feature `signature`: they are ignored,
+    /// as a `None` check.
+    #[cfg(not(feature = "signature"))]
+    fn verify(&self, _digests: &Digests) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Check {
+    Blake3(blake3::Hash),
+    Sha256([u8; 32]),
+    Xxh3(u64),
+    Size(u64),
+    Ed25519(Box<Ed25519Signature>),
+}
+
+impl Check {
+    fn kind(&self) -> CheckKind {
+        match self {
+            Self::Blake3(_) => CheckKind::Blake3,
+            Self::Sha256(_) => CheckKind::Sha256,
+            Self::Xxh3(_) => CheckKind::Xxh3,
+            Self::Size(_) => CheckKind::Size,
+            Self::Ed25519(_) => CheckKind::Ed25519,
+        }
+    }
+}
+
+impl Serializable for Check {
+    fn serialize(&self, ser: &mut Serializer) -> IoResult<usize> {
+        let written = self.kind().serialize(ser)?;
+        Ok(written
+            + match self {
+                Self::Blake3(hash) => ser.write_data(hash.as_bytes())?,
+                Self::Sha256(hash) => ser.write_data(hash)?,
+                Self::Xxh3(hash) => ser.write_u64(*hash)?,
+                Self::Size(size) => ser.write_u64(*size)?,
+                Self::Ed25519(signature) => {
+                    ser.write_data(&signature.public_key)? + ser.write_data(&signature.signature)?
+                }
+            })
+    }
+}
+
+/// The digests of some data, computed by a [`Hasher`].
+///
+/// Only the digests of the kinds given to the hasher are computed.
+#[derive(Debug)]
+pub(crate) struct Digests {
+    blake3: Option<blake3::Hash>,
+    sha256: Option<[u8; 32]>,
+    xxh3: Option<u64>,
+    size: u64,
+}
+
+impl Digests {
+    /// Digests of data of `size` bytes, without any hash.
+    pub(crate) fn new_size(size: Size) -> Self {
+        Self {
+            blake3: None,
+            sha256: None,
+            xxh3: None,
+            size: size.into_u64(),
+        }
+    }
+}
+
+/// Compute the digests of several check kinds in one pass on the data.
+pub(crate) struct Hasher {
+    blake3: Option<blake3::Hasher>,
+    sha256: Option<sha2::Sha256>,
+    xxh3: Option<xxhash_rust::xxh3::Xxh3>,
+    size: u64,
+}
+
+impl Hasher {
+    pub(crate) fn new(kinds: impl IntoIterator<Item = CheckKind>) -> Self {
+        let mut hasher = Self {
+            blake3: None,
+            sha256: None,
+            xxh3: None,
+            size: 0,
+        };
+        for kind in kinds {
+            match kind {
+                // Signature is done on the blake3 hash.
+                CheckKind::Blake3 | CheckKind::Ed25519 => {
+                    hasher.blake3.get_or_insert_with(blake3::Hasher::new);
+                }
+                CheckKind::Sha256 => {
+                    hasher.sha256.get_or_insert_with(sha2::Sha256::new);
+                }
+                CheckKind::Xxh3 => {
+                    hasher.xxh3.get_or_insert_with(xxhash_rust::xxh3::Xxh3::new);
+                }
+                CheckKind::None | CheckKind::Size => {}
+            }
+        }
+        hasher
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        if let Some(hasher) = &mut self.blake3 {
+            // Blake3 can use multiple threads on big chunks of data.
+            if data.len() >= 128 * 1024 {
+                hasher.update_rayon(data);
+            } else {
+                hasher.update(data);
+            }
+        }
+        if let Some(hasher) = &mut self.sha256 {
+            hasher.update(data);
+        }
+        if let Some(hasher) = &mut self.xxh3 {
+            hasher.update(data);
+        }
+        self.size += data.len() as u64;
+    }
+
+    pub(crate) fn update_reader(&mut self, source: &mut dyn Read) -> std::io::Result<()> {
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            match source.read(&mut buf) {
+                Ok(0) => return Ok(()),
+                Ok(n) => self.update(&buf[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    pub(crate) fn finalize(self) -> Digests {
+        Digests {
+            blake3: self.blake3.map(|h| h.finalize()),
+            sha256: self.sha256.map(|h| h.finalize().into()),
+            xxh3: self.xxh3.map(|h| h.digest()),
+            size: self.size,
+        }
+    }
+}
+
+/// The checks of a pack.
+///
+/// A check info is a series of checks, all of them must be valid.
+/// A check info without check is valid (but doesn't check anything).
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CheckInfo {
-    b3hash: Option<blake3::Hash>,
+    checks: Vec<Check>,
 }
 
 impl Parsable for CheckInfo {
     type Output = Self;
     fn parse(parser: &mut impl Parser) -> Result<Self> {
-        let kind = CheckKind::parse(parser)?;
-        let b3hash = match kind {
-            CheckKind::Blake3 => Some(blake3::Hash::parse(parser)?),
-            _ => None,
-        };
-        Ok(Self { b3hash })
+        let mut checks = vec![];
+        // Checks are chained up to the end of the block.
+        while parser.remaining() > 0 {
+            let kind = CheckKind::parse(parser)?;
+            let check = match kind {
+                CheckKind::None => continue,
+                CheckKind::Blake3 => Check::Blake3(blake3::Hash::parse(parser)?),
+                CheckKind::Sha256 => {
+                    let mut v = [0_u8; 32];
+                    parser.read_data(&mut v)?;
+                    Check::Sha256(v)
+                }
+                CheckKind::Xxh3 => Check::Xxh3(parser.read_u64()?),
+                CheckKind::Size => Check::Size(parser.read_u64()?),
+                CheckKind::Ed25519 => {
+                    let mut public_key = [0_u8; ED25519_PUBLIC_KEY_LENGTH];
+                    parser.read_data(&mut public_key)?;
+                    let mut signature = [0_u8; ED25519_SIGNATURE_LENGTH];
+                    parser.read_data(&mut signature)?;
+                    Check::Ed25519(Box::new(Ed25519Signature {
+                        public_key,
+                        signature,
+                    }))
+                }
+            };
+            checks.push(check);
+        }
+        Ok(Self { checks })
     }
 }
 
@@ -67,38 +305,124 @@ impl BlockParsable for CheckInfo {}
 
 impl Serializable for CheckInfo {
     fn serialize(&self, ser: &mut Serializer) -> IoResult<usize> {
-        match self.b3hash {
-            None => CheckKind::None.serialize(ser),
-            Some(hash) => {
-                CheckKind::Blake3.serialize(ser)?;
-                ser.write_data(hash.as_bytes())?;
-                Ok(33)
-            }
+        if self.checks.is_empty() {
+            return CheckKind::None.serialize(ser);
+        }
+        let mut written = 0;
+        for check in &self.checks {
+            written += check.serialize(ser)?;
         }
+        Ok(written)
     }
 }
 
 impl CheckInfo {
     pub(crate) fn new_none() -> Self {
-        Self { b3hash: None }
+        Self { checks: vec![] }
     }
 
-    pub(crate) fn new_blake3(source: &mut dyn Read) -> std::io::Result<Self> {
-        let mut hasher = blake3::Hasher::new();
+    /// Create a check info with checks of `kinds` (in the same order) on the data of `source`.
+    pub(crate) fn new(kinds: &[CheckKind], source: &mut dyn Read) -> std::io::Result<Self> {
+        let mut hasher = Hasher::new(kinds.iter().copied());
         hasher.update_reader(source)?;
-        let hash = hasher.finalize();
-        Ok(Self { b3hash: Some(hash) })
+        Ok(Self::from_digests(kinds, &hasher.finalize()))
     }
 
-    pub(crate) fn check(&self, source: &mut dyn Read) -> std::io::Result<bool> {
-        if let Some(b3hash) = self.b3hash {
-            let mut hasher = blake3::Hasher::new();
-            hasher.update_reader(source)?;
-            let hash = hasher.finalize();
-            Ok(hash == b3hash)
-        } else {
-            Ok(true)
+    /// Create a check info with checks of `kinds` (in the same order) from digests.
+    ///
+    /// Signatures are not created here, see [`Self::add_signature`].
+    pub(crate) fn from_digests(kinds: &[CheckKind], digests: &Digests) -> Self {
+        let checks = kinds
+            .iter()
+            .filter_map(|kind| match kind {
+                CheckKind::None | CheckKind::Ed25519 => None,
+                CheckKind::Blake3 => Some(Check::Blake3(digests.blake3.unwrap())),
+                CheckKind::Sha256 => Some(Check::Sha256(digests.sha256.unwrap())),
+                CheckKind::Xxh3 => Some(Check::Xxh3(digests.xxh3.unwrap())),
+                CheckKind::Size => Some(Check::Size(digests.size)),
+            })
+            .collect();
+        Self { checks }
+    }
+
+    /// Sign the data (of `digests`) with `signer` and add the signature to the checks.
+    #[cfg(feature = "signature")]
+    pub(crate) fn add_signature(
+        &mut self,
+        signer: &dyn crate::creator::ManifestSigner,
+        digests: &Digests,
+    ) {
+        let hash = digests
+            .blake3
+            .expect("Digests for signature must include blake3");
+        self.checks.push(Check::Ed25519(Box::new(Ed25519Signature {
+            public_key: signer.verifying_key().to_bytes(),
+            signature: signer.sign(hash.as_bytes()).to_bytes(),
+        })));
+    }
+
+    /// The kinds of the checks in this check info.
+    pub(crate) fn kinds(&self) -> impl Iterator<Item = CheckKind> + '_ {
+        self.checks.iter().map(|c| c.kind())
+    }
+
+    /// Does this check info need the data to be hashed to check them.
+    pub(crate) fn need_hash(&self) -> bool {
+        self.kinds().any(|k| k != CheckKind::Size)
+    }
+
+    /// Check the (already computed) digests of the data.
+    ///
+    /// `digests` must have been computed with (at least) all kinds of this check info.
+    pub(crate) fn check_digests(&self, digests: &Digests) -> bool {
+        self.checks.iter().all(|check| match check {
+            Check::Blake3(hash) => digests.blake3 == Some(*hash),
+            Check::Sha256(hash) => digests.sha256 == Some(*hash),
+            Check::Xxh3(hash) => digests.xxh3 == Some(*hash),
+            Check::Size(size) => digests.size == *size,
+            // Without a trusted key, we can only check the signature is valid for
+            // the public key stored with it.
+            Check::Ed25519(signature) => signature.verify(digests),
+        })
+    }
+
+    /// Does this check info contain a signature done with the key of `public_key`.
+    #[cfg(feature = "signature")]
+    pub(crate) fn has_signature(&self, public_key: &ed25519_dalek::VerifyingKey) -> bool {
+        self.checks.iter().any(
+            |check| matches!(check, Check::Ed25519(s) if s.public_key == *public_key.as_bytes()),
+        )
+    }
+
+    /// Check the data (of `digests`) has been signed with the key of `public_key`.
+    #[cfg(feature = "signature")]
+    pub(crate) fn check_signature(
+        &self,
+        public_key: &ed25519_dalek::VerifyingKey,
+        digests: &Digests,
+    ) -> bool {
+        self.checks.iter().any(|check| match check {
+            Check::Ed25519(signature) if signature.public_key == *public_key.as_bytes() => {
+                signature.verify(digests)
+            }
+            _ => false,
+        })
+    }
+
+    /// Check the `size` first bytes of `reader`.
+    pub(crate) fn check_reader(&self, reader: &Reader, size: Size) -> Result<bool> {
+        if !self.need_hash() {
+            return Ok(self.check_digests(&Digests::new_size(size)));
         }
+        let mut hasher = Hasher::new(self.kinds());
+        reader.read_region_chunks(Offset::zero(), size, &mut |chunk| hasher.update(chunk))?;
+        Ok(self.check_digests(&hasher.finalize()))
+    }
+
+    pub(crate) fn check(&self, source: &mut dyn Read) -> std::io::Result<bool> {
+        let mut hasher = Hasher::new(self.kinds());
+        hasher.update_reader(source)?;
+        Ok(self.check_digests(&hasher.finalize()))
     }
 }
 
@@ -173,3 +497,115 @@ impl<S: Read> Read for ManifestCheckStream<'_, S> {
         Ok(read_size)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustest::test]
+    fn test_chained_check_info() {
+        let data = b"Some data to check";
+        let kinds = [CheckKind::Sha256, CheckKind::Xxh3, CheckKind::Size];
+        let check_info = CheckInfo::new(&kinds, &mut &data[..]).unwrap();
+
+        let mut cursor = std::io::Cursor::new(vec![]);
+        let written = cursor.ser_write(&check_info).unwrap();
+        assert_eq!(
+            written + BlockCheck::Crc32.size(),
+            CheckKind::block_size(&kinds).into_usize()
+        );
+        let content = cursor.into_inner();
+        assert_eq!(content[0], 2); // Sha256
+        assert_eq!(content[33], 3); // Xxh3
+        assert_eq!(content[42], 4); // Size
+        assert_eq!(&content[43..51], &(data.len() as u64).to_le_bytes());
+
+        let reader = Reader::from(content);
+        let parsed = reader
+            .parse_block_in::<CheckInfo>(Offset::zero(), ASize::from(written))
+            .unwrap();
+        assert_eq!(parsed, check_info);
+        assert!(parsed.check(&mut &data[..]).unwrap());
+        assert!(!parsed.check(&mut &data[1..]).unwrap());
+    }
+
+    #[rustest::test]
+    fn test_none_check_info() {
+        let check_info = CheckInfo::new(&[CheckKind::None], &mut &b"data"[..]).unwrap();
+        assert_eq!(check_info, CheckInfo::new_none());
+
+        let mut cursor = std::io::Cursor::new(vec![]);
+        let written = cursor.ser_write(&check_info).unwrap();
+        assert_eq!(
+            written + BlockCheck::Crc32.size(),
+            CheckKind::block_size(&[]).into_usize()
+        );
+        let content = cursor.into_inner();
+        assert_eq!(content[0], 0);
+
+        let reader = Reader::from(content);
+        let parsed = reader
+            .parse_block_in::<CheckInfo>(Offset::zero(), ASize::from(written))
+            .unwrap();
+        assert_eq!(parsed, check_info);
+        assert!(!parsed.need_hash());
+        assert!(parsed.check(&mut &b"other data"[..]).unwrap());
+    }
+
+    #[rustest::test]
+    fn test_signature_bytes() {
+        // Not a valid signature, but signatures are parsed even without feature `signature`.
+        let check_info = CheckInfo {
+            checks: vec![Check::Ed25519(Box::new(Ed25519Signature {
+                public_key: [0x42; ED25519_PUBLIC_KEY_LENGTH],
+                signature: [0x24; ED25519_SIGNATURE_LENGTH],
+            }))],
+        };
+        let mut cursor = std::io::Cursor::new(vec![]);
+        let written = cursor.ser_write(&check_info).unwrap();
+        assert_eq!(written, CheckKind::Ed25519.check_size());
+        let reader = Reader::from(cursor.into_inner());
+        let parsed = reader
+            .parse_block_in::<CheckInfo>(Offset::zero(), ASize::from(written))
+            .unwrap();
+        assert_eq!(parsed, check_info);
+        // Signatures are ignored if they cannot be verified.
+        assert_eq!(
+            parsed.check(&mut &b"Some data"[..]).unwrap(),
+            !cfg!(feature = "signature")
+        );
+    }
+
+    #[cfg(feature = "signature")]
+    #[rustest::test]
+    fn test_signed_check_info() {
+        let data = b"Some data to sign";
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[0x42; 32]);
+        let mut hasher = Hasher::new([CheckKind::Size, CheckKind::Ed25519]);
+        hasher.update(data);
+        let digests = hasher.finalize();
+        let mut check_info = CheckInfo::from_digests(&[CheckKind::Size], &digests);
+        check_info.add_signature(&signing_key, &digests);
+
+        let mut cursor = std::io::Cursor::new(vec![]);
+        let written = cursor.ser_write(&check_info).unwrap();
+        assert_eq!(
+            written,
+            CheckKind::Size.check_size() + CheckKind::Ed25519.check_size()
+        );
+        let reader = Reader::from(cursor.into_inner());
+        let parsed = reader
+            .parse_block_in::<CheckInfo>(Offset::zero(), ASize::from(written))
+            .unwrap();
+        assert_eq!(parsed, check_info);
+        assert!(parsed.check(&mut &data[..]).unwrap());
+        assert!(!parsed.check(&mut &b"Some data to sigN"[..]).unwrap());
+
+        let public_key = signing_key.verifying_key();
+        assert!(parsed.has_signature(&public_key));
+        assert!(parsed.check_signature(&public_key, &digests));
+        let other_key = ed25519_dalek::SigningKey::from_bytes(&[0x24; 32]).verifying_key();
+        assert!(!parsed.has_signature(&other_key));
+        assert!(!parsed.check_signature(&other_key, &digests));
+    }
+}
@@ -1,6 +1,9 @@
 use crate::bases::*;
 use std::fmt::Debug;
 
+/// The address of a content.
+///
+/// Content addresses are ordered by pack id, then by content id.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub struct ContentAddress {
     pub pack_id: PackId,
@@ -17,7 +20,16 @@ impl ContentAddress {
 }
 
 impl PartialOrd for ContentAddress {
-    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
-        None
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ContentAddress {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.pack_id
+            .into_u16()
+            .cmp(&other.pack_id.into_u16())
+            .then(self.content_id.cmp(&other.content_id))
     }
 }
@@ -0,0 +1,125 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// A floating point value with a total order.
+///
+/// Values are compared using [`f64::total_cmp`]:
+/// `-0.0` is lower than `+0.0` and NaN values are ordered (negative NaNs before
+/// all other values, positive NaNs after). Two floats are equal if they have the same bits.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+#[repr(transparent)]
+pub struct Float(f64);
+
+impl Float {
+    pub fn new(v: f64) -> Self {
+        Self(v)
+    }
+
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+
+    /// Get the value as a f32 if it can be stored as a f32 without loss.
+    pub fn as_f32(&self) -> Option<f32> {
+        let v = self.0 as f32;
+        if (v as f64).to_bits() == self.0.to_bits() {
+            Some(v)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<f64> for Float {
+    fn from(v: f64) -> Self {
+        Self(v)
+    }
+}
+
+impl From<f32> for Float {
+    fn from(v: f32) -> Self {
+        Self(v as f64)
+    }
+}
+
+impl From<Float> for f64 {
+    fn from(v: Float) -> Self {
+        v.0
+    }
+}
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustest::test]
+    fn test_float_order() {
+        let mut values = [
+            Float::from(f64::NAN),
+            Float::from(1.5),
+            Float::from(0.0),
+            Float::from(f64::INFINITY),
+            Float::from(-0.0),
+            Float::from(-f64::NAN),
+            Float::from(f64::NEG_INFINITY),
+            Float::from(-2.0),
+        ];
+        values.sort();
+        let values = values.map(|v| v.get());
+        assert!(values[0].is_nan() && values[0].is_sign_negative());
+        assert_eq!(values[1], f64::NEG_INFINITY);
+        assert_eq!(values[2], -2.0);
+        assert!(values[3] == 0.0 && values[3].is_sign_negative());
+        assert!(values[4] == 0.0 && values[4].is_sign_positive());
+        assert_eq!(values[5], 1.5);
+        assert_eq!(values[6], f64::INFINITY);
+        assert!(values[7].is_nan() && values[7].is_sign_positive());
+    }
+
+    #[rustest::test]
+    fn test_float_eq() {
+        assert_eq!(Float::from(f64::NAN), Float::from(f64::NAN));
+        assert_ne!(Float::from(0.0), Float::from(-0.0));
+        assert_eq!(Float::from(1.5_f32), Float::from(1.5_f64));
+    }
+
+    #[rustest::test]
+    fn test_float_as_f32() {
+        assert_eq!(Float::from(1.5).as_f32(), Some(1.5));
+        assert_eq!(
+            Float::from(-0.0).as_f32().map(f32::to_bits),
+            Some((-0.0_f32).to_bits())
+        );
+        assert_eq!(Float::from(f64::INFINITY).as_f32(), Some(f32::INFINITY));
+        assert!(Float::from(f64::NAN).as_f32().unwrap().is_nan());
+        assert_eq!(Float::from(0.1).as_f32(), None);
+        assert_eq!(Float::from(1e300).as_f32(), None);
+    }
+}
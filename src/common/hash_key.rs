@@ -0,0 +1,49 @@
+use super::Value;
+use xxhash_rust::xxh3::xxh3_64;
+
+/// The hash of `value` as a key of a hash index.
+///
+/// Only unsigned, signed and array (and fixed bytes) values can be hashed.
+/// Integers are hashed as their 8 bytes little endian representation.
+pub(crate) fn hash_key(value: &Value) -> Option<u64> {
+    match value {
+        Value::Unsigned(v) => Some(xxh3_64(&v.to_le_bytes())),
+        Value::Signed(v) => Some(xxh3_64(&v.to_le_bytes())),
+        Value::Array(v) => Some(xxh3_64(v)),
+        _ => None,
+    }
+}
+
+/// The number of slots of the hash table of a hash index of `entry_count` entries.
+///
+/// This is the smallest power of two greater or equal to `2*entry_count` (and at least 1).
+pub(crate) fn slot_count(entry_count: u32) -> u64 {
+    (2 * entry_count as u64).next_power_of_two()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustest::test]
+    fn test_hash_key() {
+        assert_eq!(
+            hash_key(&Value::Unsigned(5)),
+            Some(xxh3_64(&[5, 0, 0, 0, 0, 0, 0, 0]))
+        );
+        assert_eq!(hash_key(&Value::Signed(-1)), Some(xxh3_64(&[0xFF; 8])));
+        assert_eq!(
+            hash_key(&Value::Array("Jubako".into())),
+            Some(xxh3_64(b"Jubako"))
+        );
+        assert_eq!(hash_key(&Value::Bool(true)), None);
+    }
+
+    #[rustest::test]
+    fn test_slot_count() {
+        assert_eq!(slot_count(0), 1);
+        assert_eq!(slot_count(1), 2);
+        assert_eq!(slot_count(3), 8);
+        assert_eq!(slot_count(4), 8);
+    }
+}
@@ -61,7 +61,14 @@ impl Parsable for PackHeader {
         let app_vendor_id = VendorId::parse(parser)?;
         let major_version = parser.read_u8()?;
         let minor_version = parser.read_u8()?;
-        if (major_version, minor_version) != (0, 2) {
+        // Directory packs 0.3 store the kind and the sort keys in their index headers.
+        // Manifest packs 0.3 store percent-encoded pack locations.
+        let known_version = match (major_version, minor_version) {
+            (0, 2) => true,
+            (0, 3) => matches!(magic, PackKind::Directory | PackKind::Manifest),
+            _ => false,
+        };
+        if !known_version {
             return Err(VersionError {
                 major: major_version,
                 minor: minor_version,
@@ -206,4 +213,31 @@ mod tests {
             }
         );
     }
+
+    #[rustest::test]
+    fn test_packheader_version() {
+        let header = |kind: u8, crc: [u8; 4]| {
+            let mut content = vec![
+                0x6a, 0x62, 0x6b, kind, // magic
+                0x00, 0x00, 0x00, 0x01, // app_vendor_id
+                0x00, // major_version
+                0x03, // minor_version
+                0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+                0x0e, 0x0f, // uuid
+                0x00, // flags
+                0x00, 0x00, 0x00, 0x00, 0x00, // padding
+                0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // file_size
+                0xee, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // check_info_pos
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, // padding
+            ];
+            content.extend_from_slice(&crc);
+            Reader::from(content).parse_block_at::<PackHeader>(Offset::zero())
+        };
+        let pack_header = header(b'm', [0xFD, 0x36, 0x8A, 0x8D]).unwrap();
+        assert_eq!(pack_header.magic, PackKind::Manifest);
+        assert_eq!(pack_header.minor_version, 3);
+        let err = header(b'c', [0x9B, 0x68, 0x18, 0x70]).unwrap_err();
+        assert!(matches!(*err, ErrorKind::Version(_)), "{err:?}");
+    }
 }
@@ -2,24 +2,30 @@ mod check;
 mod compression_type;
 mod content_address;
 mod content_info;
+mod float;
+mod hash_key;
 mod headers;
 mod pack_info;
 mod pack_kind;
 mod pack_locator;
+mod sort_key;
 mod value;
 
 use uuid::Uuid;
 
 use crate::bases::*;
-pub(crate) use check::CheckKind;
-pub(crate) use check::{CheckInfo, ManifestCheckStream};
+pub use check::CheckKind;
+pub(crate) use check::{CheckInfo, Digests, Hasher, ManifestCheckStream};
 pub use compression_type::CompressionType;
 pub use content_address::ContentAddress;
 pub use content_info::ContentInfo;
+pub use float::Float;
+pub(crate) use hash_key::{hash_key, slot_count};
 pub(crate) use headers::*;
 pub use pack_info::PackInfo;
 pub use pack_kind::PackKind;
 pub(crate) use pack_locator::PackLocator;
+pub use sort_key::{IndexSortKey, SortOrder};
 pub use value::Value;
 
 pub(crate) use pack_kind::FullPackKind;
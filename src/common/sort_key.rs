@@ -0,0 +1,96 @@
+use crate::bases::*;
+use std::cmp::Ordering;
+
+/// The direction in which entries are sorted on a property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    /// Apply the direction to a ascending `ordering`.
+    pub fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            Self::Ascending => ordering,
+            Self::Descending => ordering.reverse(),
+        }
+    }
+}
+
+/// A property on which the entries of an index are sorted.
+///
+/// The property is identified by its position in the common part of the entry store layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+pub struct IndexSortKey {
+    pub property_idx: PropertyIdx,
+    pub order: SortOrder,
+}
+
+impl IndexSortKey {
+    /// The maximum position of a sort key property.
+    pub(crate) const MAX_PROPERTY_IDX: u8 = 0b0111_1111;
+
+    /// The maximum number of sort keys of an index.
+    pub(crate) const MAX_COUNT: u8 = 0b0011_1111;
+
+    pub(crate) fn new(property_idx: PropertyIdx, order: SortOrder) -> Self {
+        assert!(property_idx.into_u8() <= Self::MAX_PROPERTY_IDX);
+        Self {
+            property_idx,
+            order,
+        }
+    }
+}
+
+impl Parsable for IndexSortKey {
+    type Output = Self;
+    fn parse(parser: &mut impl Parser) -> Result<Self> {
+        let byte = parser.read_u8()?;
+        let order = if byte & 0b1000_0000 != 0 {
+            SortOrder::Descending
+        } else {
+            SortOrder::Ascending
+        };
+        Ok(Self {
+            property_idx: PropertyIdx::from(byte & Self::MAX_PROPERTY_IDX),
+            order,
+        })
+    }
+}
+
+impl SizedParsable for IndexSortKey {
+    const SIZE: usize = 1;
+}
+
+impl Serializable for IndexSortKey {
+    fn serialize(&self, ser: &mut Serializer) -> IoResult<usize> {
+        let order_bit = match self.order {
+            SortOrder::Ascending => 0,
+            SortOrder::Descending => 0b1000_0000,
+        };
+        ser.write_u8(self.property_idx.into_u8() | order_bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustest::test]
+    fn test_index_sort_key() {
+        let reader = CheckReader::from(vec![0x02, 0x85]);
+        let mut parser = reader.create_parser(Offset::zero(), 2.into()).unwrap();
+        assert_eq!(
+            IndexSortKey::parse(&mut parser).unwrap(),
+            IndexSortKey::new(PropertyIdx::from(2), SortOrder::Ascending)
+        );
+        assert_eq!(
+            IndexSortKey::parse(&mut parser).unwrap(),
+            IndexSortKey::new(PropertyIdx::from(5), SortOrder::Descending)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::ContentAddress;
+use super::{ContentAddress, Float};
 use crate::bases::*;
 
 #[derive(PartialEq, Eq, Debug, Clone, PartialOrd)]
@@ -7,4 +7,7 @@ pub enum Value {
     Unsigned(u64),
     Signed(i64),
     Array(SmallBytes),
+    Range(EntryRange),
+    Float(Float),
+    Bool(bool),
 }
@@ -5,12 +5,14 @@ use pathdiff::diff_utf8_paths;
 
 use super::{
     content_pack::{CompHint, ContentAdder},
-    AtomicOutFile, Compression, ContainerPackCreator, ContentPackCreator, DirectoryPackCreator,
-    InContainerFile, InputReader, ManifestPackCreator, PackRecipient, Progress,
+    AtomicOutFile, CheckKind, Compression, ContainerPackCreator, ContentPackCreator,
+    DirectoryPackCreator, InContainerFile, InputReader, ManifestPackCreator, PackData,
+    PackRecipient, Progress,
 };
 use crate::{
     bases::*,
     creator::{Error, Result},
+    reader::encode_location,
     ContentAddress,
 };
 
@@ -35,7 +37,7 @@ pub trait EntryStoreCreatorTrait {
     ///
     /// Most custom entry store creator will wrap value and entry stores.
     /// This method must add them to `directory_pack`.
-    fn finalize(self: Box<Self>, directory_pack: &mut DirectoryPackCreator);
+    fn finalize(self: Box<Self>, directory_pack: &mut DirectoryPackCreator) -> Result<()>;
 }
 
 /// BasicCreator provides a simplify way to create a Jubako container.
@@ -50,6 +52,10 @@ pub struct BasicCreator {
     concat_mode: ConcatMode,
     vendor_id: VendorId,
     outpath: Utf8PathBuf,
+    reused_packs: Vec<(PackData, Utf8PathBuf)>,
+    check_kinds: Vec<CheckKind>,
+    #[cfg(feature = "signature")]
+    signer: Option<Arc<dyn super::ManifestSigner>>,
 }
 
 fn new_with_extension(path: &Utf8Path, extension: &str) -> Utf8PathBuf {
@@ -70,6 +76,24 @@ impl BasicCreator {
         vendor_id: VendorId,
         compression: Compression,
         progress: Arc<dyn Progress>,
+    ) -> Result<Self> {
+        Self::new_with_content_pack_id(
+            outpath,
+            concat_mode,
+            vendor_id,
+            compression,
+            progress,
+            PackId::from(1),
+        )
+    }
+
+    pub(super) fn new_with_content_pack_id(
+        outpath: impl AsRef<Utf8Path>,
+        concat_mode: ConcatMode,
+        vendor_id: VendorId,
+        compression: Compression,
+        progress: Arc<dyn Progress>,
+        content_pack_id: PackId,
     ) -> Result<Self> {
         let outpath = camino::absolute_utf8(outpath.as_ref())?;
         let atomic_content_pack_file = if let ConcatMode::OneFile = concat_mode {
@@ -86,7 +110,7 @@ impl BasicCreator {
 
         let content_pack = ContentPackCreator::new_from_output_with_progress(
             tmp_content_pack,
-            PackId::from(1),
+            content_pack_id,
             vendor_id,
             Default::default(),
             compression,
@@ -102,15 +126,69 @@ impl BasicCreator {
             concat_mode,
             vendor_id,
             outpath,
+            reused_packs: Vec::new(),
+            check_kinds: vec![CheckKind::Blake3],
+            #[cfg(feature = "signature")]
+            signer: None,
         })
     }
 
+    /// Set the kinds of the checks stored in the check info of all created packs.
+    ///
+    /// Checks are chained in the given order. Default is a blake3 check.
+    pub fn set_check_kinds(&mut self, check_kinds: Vec<CheckKind>) {
+        self.content_pack.set_check_kinds(check_kinds.clone());
+        self.directory_pack.set_check_kinds(check_kinds.clone());
+        self.check_kinds = check_kinds;
+    }
+
+    /// Set the signer used to sign the manifest pack of the container.
+    #[cfg(feature = "signature")]
+    pub fn set_signer(&mut self, signer: Arc<dyn super::ManifestSigner>) {
+        self.signer = Some(signer);
+    }
+
+    /// Reference a existing pack (located at `location`) in the container to create.
+    ///
+    /// The pack is not copied, only referenced in the manifest pack.
+    pub(super) fn reuse_pack(&mut self, pack_data: PackData, location: Utf8PathBuf) {
+        self.reused_packs.push((pack_data, location));
+    }
+
+    /// Check that pack ids of `extra_content_pack_creators` are not used by another pack.
+    fn check_extra_pack_ids(
+        &self,
+        extra_content_pack_creators: &[ContentPackCreator<dyn PackRecipient>],
+    ) -> Result<()> {
+        let mut used_ids = vec![PackId::from(0), self.content_pack.pack_id()];
+        used_ids.extend(
+            self.reused_packs
+                .iter()
+                .map(|(pack_data, _)| pack_data.pack_id),
+        );
+        for extra_creator in extra_content_pack_creators {
+            let pack_id = extra_creator.pack_id();
+            if used_ids.contains(&pack_id) {
+                return Err(Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Pack id {pack_id} of a extra content pack is already used"),
+                )));
+            }
+            used_ids.push(pack_id);
+        }
+        Ok(())
+    }
+
     /// Finalize the creation of Jubako container and create the archive as `outfile`.
+    ///
+    /// Fails if a pack of `extra_content_pack_creators` has the pack id of another pack
+    /// of the container.
     pub fn finalize(
         mut self,
         entry_store_creator: Box<dyn EntryStoreCreatorTrait>,
         extra_content_pack_creators: Vec<ContentPackCreator<dyn PackRecipient>>,
     ) -> Result<()> {
+        self.check_extra_pack_ids(&extra_content_pack_creators)?;
         let parent_path = self
             .outpath
             .parent()
@@ -119,10 +197,11 @@ impl BasicCreator {
             if p.as_str().is_empty() {
                 p
             } else {
-                diff_utf8_paths(p, parent_path).expect("outfile is absolute")
+                let path = diff_utf8_paths(p, parent_path).expect("outfile is absolute");
+                Utf8PathBuf::from(encode_location(path.as_str()))
             }
         };
-        entry_store_creator.finalize(&mut self.directory_pack);
+        entry_store_creator.finalize(&mut self.directory_pack)?;
         let finalized_directory_pack_creator = self.directory_pack.finalize()?;
 
         let (content_pack_file, content_pack_info) = self.content_pack.finalize()?;
@@ -187,9 +266,18 @@ impl BasicCreator {
 
         // Time to build our manifest
         let mut manifest_creator = ManifestPackCreator::new(self.vendor_id, Default::default());
+        manifest_creator.set_check_kinds(self.check_kinds);
+        #[cfg(feature = "signature")]
+        if let Some(signer) = self.signer {
+            manifest_creator.set_signer(signer);
+        }
         manifest_creator.add_pack(directory_pack_info, relative_locator(directory_locator));
         manifest_creator.add_pack(content_pack_info, relative_locator(content_locator));
 
+        for (reused_pack_info, reused_locator) in self.reused_packs {
+            manifest_creator.add_pack(reused_pack_info, relative_locator(reused_locator));
+        }
+
         for (extra_pack_info, extra_locator) in extra_locators {
             manifest_creator.add_pack(extra_pack_info, relative_locator(extra_locator));
         }
@@ -45,6 +45,7 @@ pub struct ContentPackCreator<O: PackRecipient + ?Sized> {
     cluster_writer: ClusterWriterProxy<Box<O>>,
     progress: Arc<dyn Progress>,
     compression: Compression,
+    check_kinds: Vec<CheckKind>,
 }
 
 macro_rules! open_cluster_ref {
@@ -150,9 +151,21 @@ impl<O: PackRecipient + 'static + ?Sized> ContentPackCreator<O> {
             cluster_writer,
             progress,
             compression,
+            check_kinds: vec![CheckKind::Blake3],
         })
     }
 
+    /// Set the kinds of the checks stored in the check info of the pack.
+    ///
+    /// Checks are chained in the given order. Default is a blake3 check.
+    pub fn set_check_kinds(&mut self, check_kinds: Vec<CheckKind>) {
+        self.check_kinds = check_kinds;
+    }
+
+    pub(crate) fn pack_id(&self) -> PackId {
+        self.pack_id
+    }
+
     fn open_cluster(&self, compressed: bool) -> ClusterCreator {
         let cluster_id = self.next_cluster_id.replace(self.next_cluster_id.get() + 1);
         self.progress.new_cluster(cluster_id, compressed);
@@ -281,7 +294,8 @@ impl<O: PackRecipient + 'static + ?Sized> ContentPackCreator<O> {
         })?;
         let check_offset = buffered.tell();
         let pack_size: Size =
-            (check_offset + CheckKind::Blake3.block_size() + PackHeader::BLOCK_SIZE).into();
+            (check_offset + CheckKind::block_size(&self.check_kinds) + PackHeader::BLOCK_SIZE)
+                .into();
         buffered.rewind()?;
 
         info!("----- Write pack header -----");
@@ -306,7 +320,7 @@ impl<O: PackRecipient + 'static + ?Sized> ContentPackCreator<O> {
 
         info!("----- Compute checksum -----");
         file.rewind()?;
-        let check_info = CheckInfo::new_blake3(&mut file)?;
+        let check_info = CheckInfo::new(&self.check_kinds, &mut file)?;
         file.ser_write(&check_info)?;
 
         file.rewind()?;
@@ -86,22 +86,31 @@ impl<Wrapped: ContentAdder> CachedContentAdder<Wrapped> {
 
     fn add_content(
         &mut self,
-        mut reader: Box<dyn InputReader>,
+        reader: Box<dyn InputReader>,
         comp_hint: CompHint,
     ) -> std::io::Result<crate::ContentAddress> {
-        let mut hasher = blake3::Hasher::new();
-        if reader.size() < cluster::CLUSTER_SIZE {
-            let mut buf = Vec::with_capacity(reader.size().into_u64() as usize);
-            reader.read_to_end(&mut buf)?;
-            hasher.update(&buf);
-            let hash = hasher.finalize();
-            self.cache_content(hash, Box::new(Cursor::new(buf)), comp_hint)
-        } else {
-            hasher.update_reader(&mut reader)?;
-            reader.rewind()?;
-            let hash = hasher.finalize();
-            self.cache_content(hash, reader, comp_hint)
-        }
+        let (hash, reader) = hash_content(reader)?;
+        self.cache_content(hash, reader, comp_hint)
+    }
+}
+
+/// Compute the blake3 hash of a content.
+///
+/// Return the hash and a reader (at its start) on the same content.
+/// Small contents are read in memory to avoid reading them twice.
+pub(crate) fn hash_content(
+    mut reader: Box<dyn InputReader>,
+) -> std::io::Result<(Hash, Box<dyn InputReader>)> {
+    let mut hasher = blake3::Hasher::new();
+    if reader.size() < cluster::CLUSTER_SIZE {
+        let mut buf = Vec::with_capacity(reader.size().into_u64() as usize);
+        reader.read_to_end(&mut buf)?;
+        hasher.update(&buf);
+        Ok((hasher.finalize(), Box::new(Cursor::new(buf))))
+    } else {
+        hasher.update_reader(&mut reader)?;
+        reader.rewind()?;
+        Ok((hasher.finalize(), reader))
     }
 }
 
@@ -1,4 +1,4 @@
-use super::{entry_store, value_store, Index};
+use super::{entry_store, value_store, Index, IndexKind};
 use crate::bases::*;
 use crate::common::{
     CheckInfo, CheckKind, DirectoryPackHeader, PackHeader, PackHeaderInfo, PackKind,
@@ -18,6 +18,7 @@ pub struct DirectoryPackCreator {
     value_stores: Vec<StoreHandle>,
     entry_stores: Vec<Box<dyn EntryStoreTrait>>,
     indexes: Vec<Index>,
+    check_kinds: Vec<CheckKind>,
 }
 
 impl DirectoryPackCreator {
@@ -29,9 +30,17 @@ impl DirectoryPackCreator {
             value_stores: vec![],
             entry_stores: vec![],
             indexes: vec![],
+            check_kinds: vec![CheckKind::Blake3],
         }
     }
 
+    /// Set the kinds of the checks stored in the check info of the pack.
+    ///
+    /// Checks are chained in the given order. Default is a blake3 check.
+    pub fn set_check_kinds(&mut self, check_kinds: Vec<CheckKind>) {
+        self.check_kinds = check_kinds;
+    }
+
     pub fn add_value_store(&mut self, value_store: StoreHandle) {
         self.value_stores.push(value_store);
     }
@@ -45,16 +54,105 @@ impl DirectoryPackCreator {
         idx
     }
 
+    /// Create a index on `count` entries of the entry store `store_id`, starting at `offset`.
+    ///
+    /// `index_key` is ignored, the index is sorted as the entry store.
+    #[deprecated(since = "0.5.0", note = "Use `create_range_index` instead")]
     pub fn create_index(
         &mut self,
         name: &str,
         free_data: IndexFreeData,
-        index_key: PropertyIdx,
+        _index_key: PropertyIdx,
         store_id: EntryStoreIdx,
         count: EntryCount,
         offset: EntryIdx,
     ) {
-        let index = Index::new(name, free_data, index_key, store_id, count, offset);
+        self.create_range_index(name, free_data, store_id, count, offset)
+    }
+
+    /// Create a index on `count` entries of the entry store `store_id`, starting at `offset`.
+    ///
+    /// The index is sorted as the entry store.
+    pub fn create_range_index(
+        &mut self,
+        name: &str,
+        free_data: IndexFreeData,
+        store_id: EntryStoreIdx,
+        count: EntryCount,
+        offset: EntryIdx,
+    ) {
+        let sort_keys = self.entry_stores[store_id.into_usize()]
+            .sort_keys()
+            .to_vec();
+        let index = Index::new(
+            name,
+            free_data,
+            sort_keys,
+            store_id,
+            IndexKind::Range { count, offset },
+        );
+        self.indexes.push(index);
+    }
+
+    /// Create a secondary index on all the entries of the entry store `store_id`.
+    ///
+    /// The index is sorted as the secondary order `name` declared in the schema of the entry store
+    /// (see [`Schema::add_secondary_order`](crate::creator::schema::Schema::add_secondary_order)).
+    ///
+    /// # Panics
+    ///
+    /// If the entry store has no secondary order named `name`.
+    pub fn create_secondary_index(
+        &mut self,
+        name: &str,
+        free_data: IndexFreeData,
+        store_id: EntryStoreIdx,
+    ) {
+        let order = self.entry_stores[store_id.into_usize()]
+            .secondary_order(name)
+            .unwrap_or_else(|| panic!("Entry store has no secondary order named {name}"));
+        let index = Index::new(
+            name,
+            free_data,
+            order.sort_keys.clone(),
+            store_id,
+            IndexKind::Permutation(order.entries.clone()),
+        );
+        self.indexes.push(index);
+    }
+
+    /// Create a hash index on all the entries of the entry store `store_id`.
+    ///
+    /// The hash index uses the hash table `name` declared in the schema of the entry store
+    /// (see [`Schema::add_hash_key`](crate::creator::schema::Schema::add_hash_key)),
+    /// allowing readers to find a entry from its key without a binary search.
+    /// As a range index, entries are in the entry store order.
+    ///
+    /// # Panics
+    ///
+    /// If the entry store has no hash table named `name`.
+    pub fn create_hash_index(
+        &mut self,
+        name: &str,
+        free_data: IndexFreeData,
+        store_id: EntryStoreIdx,
+    ) {
+        let entry_store = &self.entry_stores[store_id.into_usize()];
+        let table = entry_store
+            .hash_table(name)
+            .unwrap_or_else(|| panic!("Entry store has no hash table named {name}"));
+        let index = Index::new(
+            name,
+            free_data,
+            entry_store.sort_keys().to_vec(),
+            store_id,
+            IndexKind::Hash {
+                count: EntryCount::from(table.entry_count),
+                offset: EntryIdx::from(0),
+                key: table.key,
+                slots: table.slots.clone(),
+            },
+        );
         self.indexes.push(index);
     }
 
@@ -80,6 +178,7 @@ impl DirectoryPackCreator {
             value_stores: self.value_stores,
             entry_stores: finalized_entry_stores,
             indexes: self.indexes,
+            check_kinds: self.check_kinds,
         })
     }
 }
@@ -91,6 +190,7 @@ pub struct FinalizedDirectoryPackCreator {
     value_stores: Vec<StoreHandle>,
     entry_stores: Vec<Box<dyn WritableTell>>,
     indexes: Vec<Index>,
+    check_kinds: Vec<CheckKind>,
 }
 
 impl FinalizedDirectoryPackCreator {
@@ -151,16 +251,20 @@ impl FinalizedDirectoryPackCreator {
 
         let check_offset = file.seek(SeekFrom::End(0))? - origin_offset;
         let pack_size: Size = (check_offset
-            + CheckKind::Blake3.block_size().into_u64()
+            + CheckKind::block_size(&self.check_kinds).into_u64()
             + PackHeader::BLOCK_SIZE as u64)
             .into();
         file.seek(SeekFrom::Start(origin_offset))?;
 
         info!("----- Write pack header -----");
-        let pack_header = PackHeader::new(
-            PackKind::Directory,
-            PackHeaderInfo::new(self.app_vendor_id, pack_size, check_offset.into()),
-        );
+        let pack_header = PackHeader {
+            // Index headers are written with the 0.3 layout.
+            minor_version: 3,
+            ..PackHeader::new(
+                PackKind::Directory,
+                PackHeaderInfo::new(self.app_vendor_id, pack_size, check_offset.into()),
+            )
+        };
         file.ser_write(&pack_header)?;
 
         info!("----- Write directory pack header -----");
@@ -183,7 +287,7 @@ impl FinalizedDirectoryPackCreator {
 
         info!("----- Compute checksum -----");
         file.seek(SeekFrom::Start(origin_offset))?;
-        let check_info = CheckInfo::new_blake3(file)?;
+        let check_info = CheckInfo::new(&self.check_kinds, file)?;
         file.ser_write(&check_info)?;
 
         file.seek(SeekFrom::Start(origin_offset))?;
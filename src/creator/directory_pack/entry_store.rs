@@ -1,6 +1,7 @@
 use super::schema;
 use super::{PropertyName, VariantName};
 use crate::bases::*;
+use crate::common::IndexSortKey;
 use crate::creator::private::WritableTell;
 use crate::creator::{EntryTrait, ProcessedEntry, Result};
 
@@ -9,23 +10,80 @@ use log::debug;
 pub struct EntryStore<PN: PropertyName, VN: VariantName> {
     entries: Vec<ProcessedEntry<VN>>,
     pub schema: schema::Schema<PN, VN>,
+    sort_keys: Vec<IndexSortKey>,
+    secondary_orders: Vec<schema::SecondaryOrder>,
+    hash_tables: Vec<schema::HashTable>,
+    entry_checked: bool,
 }
 
 impl<PN: PropertyName, VN: VariantName> EntryStore<PN, VN> {
+    /// Create a entry store from `entries`.
+    ///
+    /// If the schema has sort keys, `entries` must be sorted on them.
+    /// If the schema declares secondary orders or hash keys, they are computed here.
+    ///
+    /// Fails if `entries` are not sorted on the sort keys of the schema, or if a sort key
+    /// or a hash key is not a common property of the schema.
     pub fn new<Entry: EntryTrait<PN, VN>>(
         mut schema: schema::Schema<PN, VN>,
         entries: impl Iterator<Item = Entry>,
-    ) -> Self {
-        let entries = schema.process_entries(entries);
-        Self { entries, schema }
+    ) -> Result<Self> {
+        let sort_keys = schema.index_sort_keys()?;
+        let (entries, secondary_orders, hash_tables) =
+            if schema.secondary_sort_keys.is_empty() && schema.hash_keys.is_empty() {
+                (schema.process_entries(entries)?, vec![], vec![])
+            } else {
+                // Secondary orders and hash tables need all the entries.
+                let entries = entries.collect::<Vec<_>>();
+                let secondary_orders = schema.secondary_orders(&entries)?;
+                let hash_tables = schema.hash_tables(&entries)?;
+                (
+                    schema.process_entries(entries.into_iter())?,
+                    secondary_orders,
+                    hash_tables,
+                )
+            };
+        Ok(Self {
+            entries,
+            schema,
+            sort_keys,
+            secondary_orders,
+            hash_tables,
+            entry_checked: false,
+        })
+    }
+
+    /// Write a crc32 for each entry instead of one for the whole store.
+    ///
+    /// This allow readers to check only the entries they read.
+    pub fn set_entry_checked(&mut self, entry_checked: bool) {
+        self.entry_checked = entry_checked;
     }
 }
 
 pub(crate) trait EntryStoreTrait {
+    /// The properties on which the entries are sorted.
+    fn sort_keys(&self) -> &[IndexSortKey];
+    /// The secondary order `name` of the entries.
+    fn secondary_order(&self, name: &str) -> Option<&schema::SecondaryOrder>;
+    /// The hash table `name` of the entries.
+    fn hash_table(&self, name: &str) -> Option<&schema::HashTable>;
     fn finalize(self: Box<Self>) -> Box<dyn WritableTell>;
 }
 
 impl<PN: PropertyName, VN: VariantName> EntryStoreTrait for EntryStore<PN, VN> {
+    fn sort_keys(&self) -> &[IndexSortKey] {
+        &self.sort_keys
+    }
+
+    fn secondary_order(&self, name: &str) -> Option<&schema::SecondaryOrder> {
+        self.secondary_orders.iter().find(|o| o.name == name)
+    }
+
+    fn hash_table(&self, name: &str) -> Option<&schema::HashTable> {
+        self.hash_tables.iter().find(|t| t.name == name)
+    }
+
     fn finalize(self: Box<Self>) -> Box<dyn WritableTell> {
         debug!("Schema is {:#?}", self.schema);
 
@@ -34,6 +92,7 @@ impl<PN: PropertyName, VN: VariantName> EntryStoreTrait for EntryStore<PN, VN> {
             entry_count: self.entries.len() as u32,
             entries: self.entries.into_iter(),
             layout,
+            entry_checked: self.entry_checked,
         })
     }
 }
@@ -47,6 +106,7 @@ where
     entry_count: u32,
     entries: Store,
     layout: super::layout::Entry<PN, VN>,
+    entry_checked: bool,
 }
 
 impl<PN, VN, Store> WritableTell for FinalEntryStore<PN, VN, Store>
@@ -56,12 +116,19 @@ where
     Store: Iterator<Item = ProcessedEntry<VN>>,
 {
     fn write_data(&mut self, stream: &mut dyn OutStream) -> Result<()> {
-        // [TODO] Handle per entry CRC32
-        let mut serializer = Serializer::new(BlockCheck::Crc32);
-        for entry in &mut self.entries {
-            self.layout.serialize_entry(&entry, &mut serializer)?;
+        if self.entry_checked {
+            for entry in &mut self.entries {
+                let mut serializer = Serializer::new(BlockCheck::Crc32);
+                self.layout.serialize_entry(&entry, &mut serializer)?;
+                stream.write_serializer(serializer)?;
+            }
+        } else {
+            let mut serializer = Serializer::new(BlockCheck::Crc32);
+            for entry in &mut self.entries {
+                self.layout.serialize_entry(&entry, &mut serializer)?;
+            }
+            stream.write_serializer(serializer)?;
         }
-        stream.write_serializer(serializer)?;
         Ok(())
     }
 
@@ -69,8 +136,7 @@ where
         ser.write_u8(0x00)?; // kind
         let entry_count = EntryCount::from(self.entry_count);
         entry_count.serialize(ser)?;
-        // [TODO] handle per entry CRC32
-        ser.write_u8(0x00)?; // flag
+        ser.write_u8(self.entry_checked as u8)?; // flag
         self.layout.serialize(ser)?;
         Ok(())
     }
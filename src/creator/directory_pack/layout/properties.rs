@@ -55,6 +55,7 @@ impl<PN: PropertyName> Properties<PN> {
     ) -> Result<usize> {
         let mut values = values.iter();
         let mut written = 0;
+        let mut bool_byte = 0_u8;
         for key in keys {
             match key {
                 Property::Array {
@@ -210,6 +211,98 @@ impl<PN: PropertyName> Properties<PN> {
                         )));
                     }
                 },
+                Property::Range {
+                    entry_idx_size,
+                    entry_count_size,
+                    default_count,
+                    name,
+                } => match values.next().unwrap() {
+                    ProcessedValue::Range(value) => {
+                        written += ser.write_usized(value.begin().into_u64(), *entry_idx_size)?;
+                        if let Some(d) = default_count {
+                            assert_eq!(*d, value.size().into_u32());
+                        } else {
+                            written +=
+                                ser.write_usized(value.size().into_u64(), *entry_count_size)?;
+                        }
+                    }
+                    _ => {
+                        return Err(Error::wrong_type(format!(
+                            "Value type for {} is not compatible with range",
+                            name.as_str()
+                        )));
+                    }
+                },
+                Property::Float {
+                    size,
+                    default,
+                    name,
+                } => match values.next().unwrap() {
+                    ProcessedValue::Float(value) => {
+                        if let Some(d) = default {
+                            assert_eq!(d, value);
+                        } else {
+                            written += match size {
+                                ByteSize::U4 => ser.write_u32(
+                                    value
+                                        .as_f32()
+                                        .expect("Float size is U4 only if all values fit in f32")
+                                        .to_bits(),
+                                )?,
+                                _ => ser.write_u64(value.get().to_bits())?,
+                            };
+                        }
+                    }
+                    _ => {
+                        return Err(Error::wrong_type(format!(
+                            "Value type for {} is not compatible with float",
+                            name.as_str()
+                        )));
+                    }
+                },
+                Property::Bool {
+                    bit,
+                    closing,
+                    default,
+                    name,
+                } => match values.next().unwrap() {
+                    ProcessedValue::Bool(value) => {
+                        if let Some(d) = default {
+                            assert_eq!(d, value);
+                        } else {
+                            bool_byte |= (*value as u8) << bit;
+                            if *closing {
+                                written += ser.write_u8(bool_byte)?;
+                                bool_byte = 0;
+                            }
+                        }
+                    }
+                    _ => {
+                        return Err(Error::wrong_type(format!(
+                            "Value type for {} is not compatible with bool",
+                            name.as_str()
+                        )));
+                    }
+                },
+                Property::FixedBytes {
+                    size: _,
+                    default,
+                    name,
+                } => match values.next().unwrap() {
+                    ProcessedValue::FixedBytes(value) => {
+                        if let Some(d) = default {
+                            assert_eq!(d, value.as_ref());
+                        } else {
+                            written += ser.write_data(value)?;
+                        }
+                    }
+                    _ => {
+                        return Err(Error::wrong_type(format!(
+                            "Value type for {} is not compatible with fixed bytes",
+                            name.as_str()
+                        )));
+                    }
+                },
                 Property::Padding(size) => {
                     let data = vec![0x00; *size as usize];
                     written += ser.write_data(&data)?;
@@ -2,6 +2,7 @@ use super::super::PropertyName;
 use super::StoreHandle;
 use crate::bases::Serializable;
 use crate::bases::*;
+use crate::common::Float;
 
 pub(crate) enum Property<PN: PropertyName> {
     VariantId(&'static str),
@@ -32,6 +33,29 @@ pub(crate) enum Property<PN: PropertyName> {
         default: Option<i64>,
         name: PN,
     },
+    Range {
+        entry_idx_size: ByteSize,
+        entry_count_size: ByteSize,
+        default_count: Option<u32>,
+        name: PN,
+    },
+    Float {
+        size: ByteSize,
+        default: Option<Float>,
+        name: PN,
+    },
+    Bool {
+        bit: u8,
+        // The last bool stored in a byte "closes" the byte and is the one counting it in its size.
+        closing: bool,
+        default: Option<bool>,
+        name: PN,
+    },
+    FixedBytes {
+        size: u16,
+        default: Option<SmallBytes>,
+        name: PN,
+    },
     Padding(/*size*/ u8),
 }
 
@@ -102,6 +126,52 @@ impl<PN: PropertyName> std::fmt::Debug for Property<PN> {
                 .field("size", &self.size())
                 .field("name", &name.as_str())
                 .finish(),
+            Range {
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+                name,
+            } => f
+                .debug_struct("Range")
+                .field("entry_idx_size", &entry_idx_size)
+                .field("entry_count_size", &entry_count_size)
+                .field("default_count", &default_count)
+                .field("size", &self.size())
+                .field("name", &name.as_str())
+                .finish(),
+            Float {
+                size,
+                default,
+                name,
+            } => f
+                .debug_struct("Float")
+                .field("size", &size)
+                .field("default", &default)
+                .field("name", &name.as_str())
+                .finish(),
+            Bool {
+                bit,
+                closing,
+                default,
+                name,
+            } => f
+                .debug_struct("Bool")
+                .field("bit", &bit)
+                .field("closing", &closing)
+                .field("default", &default)
+                .field("size", &self.size())
+                .field("name", &name.as_str())
+                .finish(),
+            FixedBytes {
+                size,
+                default,
+                name,
+            } => f
+                .debug_struct("FixedBytes")
+                .field("size", &size)
+                .field("default", &default)
+                .field("name", &name.as_str())
+                .finish(),
             Padding(_size) => f
                 .debug_struct("Padding")
                 .field("size", &self.size())
@@ -168,6 +238,53 @@ impl<PN: PropertyName> Property<PN> {
                     *size as u16
                 }
             }
+            Property::Range {
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+                name: _,
+            } => {
+                *entry_idx_size as u16
+                    + if default_count.is_some() {
+                        0
+                    } else {
+                        *entry_count_size as u16
+                    }
+            }
+            Property::Float {
+                size,
+                default,
+                name: _,
+            } => {
+                if default.is_some() {
+                    0
+                } else {
+                    *size as u16
+                }
+            }
+            Property::Bool {
+                bit: _,
+                closing,
+                default,
+                name: _,
+            } => {
+                if default.is_none() && *closing {
+                    1
+                } else {
+                    0
+                }
+            }
+            Property::FixedBytes {
+                size,
+                default,
+                name: _,
+            } => {
+                if default.is_some() {
+                    0
+                } else {
+                    *size
+                }
+            }
             Property::Padding(size) => *size as u16,
         }
     }
@@ -277,6 +394,95 @@ impl<PN: PropertyName> Serializable for Property<PN> {
                 written += PString::serialize_string(name.as_str(), ser)?;
                 Ok(written)
             }
+            Property::Range {
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+                name,
+            } => {
+                let mut key_type = PropType::Range as u8;
+                key_type += *entry_idx_size as u8 - 1;
+                let mut written = match default_count {
+                    None => {
+                        let mut written = ser.write_u8(key_type)?;
+                        written += ser.write_u8(*entry_count_size as u8 - 1)?;
+                        written
+                    }
+                    Some(d) => {
+                        let mut written = ser.write_u8(key_type + 0b0000_1000)?;
+                        written += ser.write_u8(*entry_count_size as u8 - 1)?;
+                        written += ser.write_usized(*d as u64, *entry_count_size)?;
+                        written
+                    }
+                };
+                written += PString::serialize_string(name.as_str(), ser)?;
+                Ok(written)
+            }
+            Property::Float {
+                size,
+                default,
+                name,
+            } => {
+                let mut key_type = PropType::Float as u8;
+                key_type += *size as u8 - 1;
+                let mut written = match default {
+                    None => ser.write_u8(key_type)?,
+                    Some(d) => {
+                        let mut written = ser.write_u8(key_type + 0b0000_1000)?;
+                        written += match size {
+                            ByteSize::U4 => ser.write_u32(
+                                d.as_f32()
+                                    .expect("Float size is U4 only if all values fit in f32")
+                                    .to_bits(),
+                            )?,
+                            _ => ser.write_u64(d.get().to_bits())?,
+                        };
+                        written
+                    }
+                };
+                written += PString::serialize_string(name.as_str(), ser)?;
+                Ok(written)
+            }
+            Property::Bool {
+                bit,
+                closing,
+                default,
+                name,
+            } => {
+                let key_type = PropType::Bool as u8;
+                let mut written = match default {
+                    None => {
+                        let mut written = ser.write_u8(key_type + bit)?;
+                        written += ser.write_u8(*closing as u8)?;
+                        written
+                    }
+                    Some(d) => ser.write_u8(key_type + 0b0000_1000 + *d as u8)?,
+                };
+                written += PString::serialize_string(name.as_str(), ser)?;
+                Ok(written)
+            }
+            Property::FixedBytes {
+                size,
+                default,
+                name,
+            } => {
+                let key_type = PropType::FixedBytes as u8;
+                let mut written = match default {
+                    None => {
+                        let mut written = ser.write_u8(key_type)?;
+                        written += ser.write_u8((*size - 1) as u8)?;
+                        written
+                    }
+                    Some(d) => {
+                        let mut written = ser.write_u8(key_type + 0b0000_1000)?;
+                        written += ser.write_u8((*size - 1) as u8)?;
+                        written += ser.write_data(d)?;
+                        written
+                    }
+                };
+                written += PString::serialize_string(name.as_str(), ser)?;
+                Ok(written)
+            }
             Property::Padding(size) => {
                 let key_type = PropType::Padding as u8;
                 ser.write_u8(key_type + (size - 1))
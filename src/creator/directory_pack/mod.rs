@@ -9,7 +9,7 @@ mod value_store;
 
 use crate::bases::*;
 use crate::common;
-use crate::creator::Result;
+use crate::creator::{Error, Result};
 pub use directory_pack::DirectoryPackCreator;
 pub use entry_store::EntryStore;
 pub use simple_entry::SimpleEntry;
@@ -23,23 +23,26 @@ pub trait EntryTrait<PN: PropertyName, VN: VariantName> {
     fn variant_name(&self) -> Option<VN>;
     fn value(&self, name: &PN) -> common::Value;
     fn value_count(&self) -> PropertyCount;
-    fn compare<'i, I>(&self, sort_keys: &'i I, other: &Self) -> std::cmp::Ordering
-    where
-        I: IntoIterator<Item = &'i PN> + Copy,
-    {
-        for property_name in sort_keys.into_iter() {
-            let self_value = self.value(property_name);
-            let other_value = other.value(property_name);
+
+    /// Compare `self` to `other` on the `sort_keys`, in the direction of each key.
+    ///
+    /// Entries with the same values for all keys are equal.
+    fn compare(&self, sort_keys: &[schema::SortKey<PN>], other: &Self) -> Result<cmp::Ordering> {
+        for key in sort_keys {
+            let self_value = self.value(&key.name);
+            let other_value = other.value(&key.name);
             match self_value.partial_cmp(&other_value) {
-                None => return cmp::Ordering::Greater,
-                Some(c) => match c {
-                    cmp::Ordering::Less => return cmp::Ordering::Less,
-                    cmp::Ordering::Greater => return cmp::Ordering::Greater,
-                    cmp::Ordering::Equal => continue,
-                },
+                None => {
+                    return Err(Error::sort(format!(
+                        "Values of property {} cannot be compared",
+                        key.name.as_str()
+                    )))
+                }
+                Some(cmp::Ordering::Equal) => continue,
+                Some(c) => return Ok(key.order.apply(c)),
             }
         }
-        cmp::Ordering::Greater
+        Ok(cmp::Ordering::Equal)
     }
 }
 
@@ -153,6 +156,38 @@ impl<PN: PropertyName, VN: VariantName, Entry: EntryTrait<PN, VN>> Iterator
                             panic!("Invalid value type");
                         }
                     }
+                    schema::Property::Range(prop, name) => {
+                        let value = self.entry.value(name);
+                        if let common::Value::Range(v) = value {
+                            return Some(prop.absorb(v));
+                        } else {
+                            panic!("Invalid value type");
+                        }
+                    }
+                    schema::Property::Float(prop, name) => {
+                        let value = self.entry.value(name);
+                        if let common::Value::Float(v) = value {
+                            return Some(prop.absorb(v));
+                        } else {
+                            panic!("Invalid value type");
+                        }
+                    }
+                    schema::Property::Bool(prop, name) => {
+                        let value = self.entry.value(name);
+                        if let common::Value::Bool(v) = value {
+                            return Some(prop.absorb(v));
+                        } else {
+                            panic!("Invalid value type");
+                        }
+                    }
+                    schema::Property::FixedBytes(prop, name) => {
+                        let value = self.entry.value(name);
+                        if let common::Value::Array(data) = value {
+                            return Some(prop.absorb(data));
+                        } else {
+                            panic!("Invalid value type");
+                        }
+                    }
                     schema::Property::Padding(_) => {}
                 },
             }
@@ -160,46 +195,98 @@ impl<PN: PropertyName, VN: VariantName, Entry: EntryTrait<PN, VN>> Iterator
     }
 }
 
+enum IndexKind {
+    /// `count` entries of the entry store, starting at `offset`.
+    Range { count: EntryCount, offset: EntryIdx },
+    /// The listed entries of the entry store.
+    Permutation(Vec<EntryIdx>),
+    /// `count` entries of the entry store, starting at `offset`, with a hash table on `key`.
+    Hash {
+        count: EntryCount,
+        offset: EntryIdx,
+        key: PropertyIdx,
+        slots: Vec<u32>,
+    },
+}
+
 struct Index {
+    kind: IndexKind,
     store_id: EntryStoreIdx,
     free_data: IndexFreeData,
-    index_key: PropertyIdx,
+    sort_keys: Vec<common::IndexSortKey>,
     name: String,
-    count: EntryCount,
-    offset: EntryIdx,
 }
 
 impl Index {
     pub fn new(
         name: &str,
         free_data: IndexFreeData,
-        index_key: PropertyIdx,
+        sort_keys: Vec<common::IndexSortKey>,
         store_id: EntryStoreIdx,
-        count: EntryCount,
-        offset: EntryIdx,
+        kind: IndexKind,
     ) -> Self {
         Index {
+            kind,
             store_id,
             free_data,
-            index_key,
+            sort_keys,
             name: name.to_string(),
-            count,
-            offset,
         }
     }
+
+    fn idx_size(entries: &[EntryIdx]) -> ByteSize {
+        needed_bytes(entries.iter().max().map_or(0, |idx| idx.into_u32()))
+    }
 }
 
 impl super::private::WritableTell for Index {
-    fn write_data(&mut self, _stream: &mut dyn OutStream) -> Result<()> {
-        // No data to write
+    fn write_data(&mut self, stream: &mut dyn OutStream) -> Result<()> {
+        if let IndexKind::Permutation(entries) = &self.kind {
+            let idx_size = Self::idx_size(entries);
+            let mut serializer = Serializer::new(BlockCheck::Crc32);
+            for idx in entries {
+                serializer.write_usized(idx.into_u64(), idx_size)?;
+            }
+            stream.write_serializer(serializer)?;
+        }
+        if let IndexKind::Hash { count, slots, .. } = &self.kind {
+            let slot_size = needed_bytes(count.into_u32());
+            let mut serializer = Serializer::new(BlockCheck::Crc32);
+            for slot in slots {
+                serializer.write_usized(*slot as u64, slot_size)?;
+            }
+            stream.write_serializer(serializer)?;
+        }
         Ok(())
     }
     fn serialize_tail(&mut self, ser: &mut Serializer) -> IoResult<()> {
+        let (kind, count, offset) = match &self.kind {
+            IndexKind::Range { count, offset } => (0x00, *count, *offset),
+            IndexKind::Permutation(entries) => (
+                0x01,
+                EntryCount::from(entries.len() as u32),
+                EntryIdx::from(0),
+            ),
+            IndexKind::Hash { count, offset, .. } => (0x02, *count, *offset),
+        };
         self.store_id.serialize(ser)?;
-        self.count.serialize(ser)?;
-        self.offset.serialize(ser)?;
+        count.serialize(ser)?;
+        offset.serialize(ser)?;
         self.free_data.serialize(ser)?;
-        self.index_key.serialize(ser)?;
+        ser.write_u8((kind << 6) | self.sort_keys.len() as u8)?;
+        for sort_key in &self.sort_keys {
+            sort_key.serialize(ser)?;
+        }
+        match &self.kind {
+            IndexKind::Range { .. } => {}
+            IndexKind::Permutation(entries) => {
+                Self::idx_size(entries).serialize(ser)?;
+            }
+            IndexKind::Hash { count, key, .. } => {
+                key.serialize(ser)?;
+                needed_bytes(count.into_u32()).serialize(ser)?;
+            }
+        }
         PString::serialize_string(&self.name, ser)?;
         Ok(())
     }
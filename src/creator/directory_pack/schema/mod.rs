@@ -2,43 +2,110 @@ mod properties;
 mod property;
 
 pub use properties::{CommonProperties, VariantProperties};
-pub use property::{Array, ContentAddress, IndirectArray, Property, SignedInt, UnsignedInt};
+pub use property::{
+    Array, Bool, ContentAddress, FixedBytes, Float, IndirectArray, Property, Range, SignedInt,
+    UnsignedInt,
+};
+use std::cmp;
 use std::collections::HashMap;
 
-use crate::creator::{directory_pack::ValueTransformer, ProcessedEntry};
+use crate::bases::{EntryIdx, PropertyIdx};
+use crate::common::{hash_key, slot_count, IndexSortKey};
+use crate::creator::{directory_pack::ValueTransformer, Error, ProcessedEntry, Result};
 
 use super::{
     layout, EntryTrait, ProcessedValue, PropertyName, StoreHandle, ValueStoreKind, VariantName,
 };
 use properties::Properties;
 
+pub use crate::common::SortOrder;
+
+/// A property on which the entries are sorted.
+///
+/// A property name converts into a ascending sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey<PN> {
+    pub name: PN,
+    pub order: SortOrder,
+}
+
+impl<PN> SortKey<PN> {
+    pub fn ascending(name: PN) -> Self {
+        Self {
+            name,
+            order: SortOrder::Ascending,
+        }
+    }
+
+    pub fn descending(name: PN) -> Self {
+        Self {
+            name,
+            order: SortOrder::Descending,
+        }
+    }
+}
+
+impl<PN> From<PN> for SortKey<PN> {
+    fn from(name: PN) -> Self {
+        Self::ascending(name)
+    }
+}
+
+/// The entries of a entry store, sorted on other keys than the entry store ones.
+#[derive(Debug)]
+pub(crate) struct SecondaryOrder {
+    pub name: String,
+    pub sort_keys: Vec<IndexSortKey>,
+    pub entries: Vec<EntryIdx>,
+}
+
+/// The hash table of the entries of a entry store, on a key property.
+#[derive(Debug)]
+pub(crate) struct HashTable {
+    pub name: String,
+    pub key: PropertyIdx,
+    pub entry_count: u32,
+    /// The index of the entry stored in each slot, plus one. 0 for empty slots.
+    pub slots: Vec<u32>,
+}
+
 #[derive(Debug)]
 pub struct Schema<PN: PropertyName, VN: VariantName> {
     pub(crate) common: Properties<PN>,
     pub(crate) variants: Vec<(VN, Properties<PN>)>,
-    pub(crate) sort_keys: Option<Vec<PN>>,
+    pub(crate) sort_keys: Option<Vec<SortKey<PN>>>,
+    pub(crate) secondary_sort_keys: Vec<(String, Vec<SortKey<PN>>)>,
+    pub(crate) hash_keys: Vec<(String, PN)>,
 }
 
+/// Iterate on `iter`, checking that each item is not lower than the previous one.
+///
+/// `compare` returns `Ok(true)` if the two items are in order.
+/// Iteration stops after the first error.
 struct SortedIter<Item, Iter, CompFunc>
 where
     Iter: Iterator<Item = Item>,
-    CompFunc: Fn(&Item, &Item) -> bool,
+    CompFunc: Fn(&Item, &Item) -> Result<bool>,
 {
     previous: Option<Item>,
     iter: Iter,
     compare: CompFunc,
+    idx: usize,
+    failed: bool,
 }
 
 impl<Item, Iter, CompFunc> SortedIter<Item, Iter, CompFunc>
 where
     Iter: Iterator<Item = Item>,
-    CompFunc: Fn(&Item, &Item) -> bool,
+    CompFunc: Fn(&Item, &Item) -> Result<bool>,
 {
     fn new(iter: Iter, compare: CompFunc) -> Self {
         Self {
             previous: None,
             iter,
             compare,
+            idx: 0,
+            failed: false,
         }
     }
 }
@@ -46,11 +113,14 @@ where
 impl<Item, Iter, CompFunc> Iterator for SortedIter<Item, Iter, CompFunc>
 where
     Iter: Iterator<Item = Item>,
-    CompFunc: Fn(&Item, &Item) -> bool,
+    CompFunc: Fn(&Item, &Item) -> Result<bool>,
 {
-    type Item = Item;
+    type Item = Result<Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
         if self.previous.is_none() {
             self.previous = self.iter.next();
         }
@@ -58,24 +128,38 @@ where
         let next = self.iter.next();
         match (&self.previous, next) {
             (None, _) => None,
-            (Some(_p), None) => self.previous.take(),
+            (Some(_p), None) => self.previous.take().map(Ok),
             (Some(p), Some(n)) => {
-                assert!(
-                    //p.compare(&self.keys, &n).is_le(),
-                    (self.compare)(p, &n),
-                    "Entry store is not sorted."
-                );
-                self.previous.replace(n)
+                self.idx += 1;
+                match (self.compare)(p, &n) {
+                    Ok(true) => self.previous.replace(n).map(Ok),
+                    Ok(false) => {
+                        self.failed = true;
+                        Some(Err(Error::sort(format!(
+                            "Entry store is not sorted: entry {} is lower than entry {}.",
+                            self.idx,
+                            self.idx - 1
+                        ))))
+                    }
+                    Err(e) => {
+                        self.failed = true;
+                        Some(Err(e))
+                    }
+                }
             }
         }
     }
 }
 
 impl<PN: PropertyName, VN: VariantName> Schema<PN, VN> {
+    /// Create a new schema.
+    ///
+    /// If `sort_keys` is given, entries must be provided sorted on these keys
+    /// (the first key first, the next ones to break ties).
     pub fn new(
         common: CommonProperties<PN>,
         variants: Vec<(VN, VariantProperties<PN>)>,
-        sort_keys: Option<Vec<PN>>,
+        sort_keys: Option<Vec<SortKey<PN>>>,
     ) -> Self {
         Self {
             common,
@@ -84,9 +168,81 @@ impl<PN: PropertyName, VN: VariantName> Schema<PN, VN> {
                 .map(|(n, p)| (n, Properties::from(p)))
                 .collect(),
             sort_keys,
+            secondary_sort_keys: vec![],
+            hash_keys: vec![],
         }
     }
 
+    /// Declare a secondary order of the entries, named `name`, on `sort_keys`.
+    ///
+    /// Entries are not reordered. The entry store computes the permutation of its entries
+    /// sorted on `sort_keys` (entries with equal keys staying in the entry store order),
+    /// which is used to create a secondary index of the same name
+    /// (see [`DirectoryPackCreator::create_secondary_index`](crate::creator::DirectoryPackCreator::create_secondary_index)).
+    ///
+    /// As for the entry store sort keys, `sort_keys` must be common properties.
+    pub fn add_secondary_order(&mut self, name: impl Into<String>, sort_keys: Vec<SortKey<PN>>) {
+        self.secondary_sort_keys.push((name.into(), sort_keys));
+    }
+
+    /// Declare a hash table of the entries, named `name`, on the property `key`.
+    ///
+    /// The entry store computes a hash table of its entries on `key`,
+    /// which is used to create a hash index of the same name
+    /// (see [`DirectoryPackCreator::create_hash_index`](crate::creator::DirectoryPackCreator::create_hash_index)).
+    ///
+    /// `key` must be a common property of type unsigned int, signed int, array or fixed bytes.
+    pub fn add_hash_key(&mut self, name: impl Into<String>, key: PN) {
+        self.hash_keys.push((name.into(), key));
+    }
+
+    /// The sort keys, as stored in the indexes of the entry store.
+    ///
+    /// Sort keys must be common properties.
+    pub(crate) fn index_sort_keys(&self) -> Result<Vec<IndexSortKey>> {
+        self.to_index_sort_keys(self.sort_keys.iter().flatten())
+    }
+
+    fn to_index_sort_keys<'a>(
+        &self,
+        sort_keys: impl Iterator<Item = &'a SortKey<PN>>,
+    ) -> Result<Vec<IndexSortKey>>
+    where
+        PN: 'a,
+    {
+        let sort_keys = sort_keys
+            .map(|key| {
+                let position = self
+                    .common
+                    .iter()
+                    .position(|p| p.name() == Some(&key.name))
+                    .ok_or_else(|| {
+                        Error::sort(format!(
+                            "Sort key {} is not a common property",
+                            key.name.as_str()
+                        ))
+                    })?;
+                if position > IndexSortKey::MAX_PROPERTY_IDX as usize {
+                    return Err(Error::sort(format!(
+                        "Sort key {} is defined too far in the common properties",
+                        key.name.as_str()
+                    )));
+                }
+                Ok(IndexSortKey::new(
+                    PropertyIdx::from(position as u8),
+                    key.order,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if sort_keys.len() > IndexSortKey::MAX_COUNT as usize {
+            return Err(Error::sort(format!(
+                "Too many sort keys ({})",
+                sort_keys.len()
+            )));
+        }
+        Ok(sort_keys)
+    }
+
     pub fn build_entry(&mut self, entry: impl EntryTrait<PN, VN>) -> ProcessedEntry<VN> {
         let variant_name = entry.variant_name();
         let value_transformer = ValueTransformer::new(self, entry);
@@ -96,15 +252,102 @@ impl<PN: PropertyName, VN: VariantName> Schema<PN, VN> {
         }
     }
 
+    /// Compute the declared secondary orders of `entries`.
+    pub(crate) fn secondary_orders<Entry: EntryTrait<PN, VN>>(
+        &self,
+        entries: &[Entry],
+    ) -> Result<Vec<SecondaryOrder>> {
+        self.secondary_sort_keys
+            .iter()
+            .map(|(name, keys)| {
+                let sort_keys = self.to_index_sort_keys(keys.iter())?;
+                let mut order = (0..entries.len() as u32)
+                    .map(EntryIdx::from)
+                    .collect::<Vec<_>>();
+                let mut error = None;
+                // Stable sort: entries with equal keys keep the entry store order.
+                order.sort_by(|a, b| {
+                    entries[a.into_usize()]
+                        .compare(keys, &entries[b.into_usize()])
+                        .unwrap_or_else(|e| {
+                            error.get_or_insert(e);
+                            cmp::Ordering::Equal
+                        })
+                });
+                if let Some(e) = error {
+                    return Err(e);
+                }
+                Ok(SecondaryOrder {
+                    name: name.clone(),
+                    sort_keys,
+                    entries: order,
+                })
+            })
+            .collect()
+    }
+
+    /// Compute the declared hash tables of `entries`.
+    ///
+    /// Tables use open addressing with linear probing.
+    /// Entries are inserted in order, so entries with the same key are found in order.
+    pub(crate) fn hash_tables<Entry: EntryTrait<PN, VN>>(
+        &self,
+        entries: &[Entry],
+    ) -> Result<Vec<HashTable>> {
+        self.hash_keys
+            .iter()
+            .map(|(name, key)| {
+                let position = self
+                    .common
+                    .iter()
+                    .position(|p| p.name() == Some(key))
+                    .ok_or_else(|| {
+                        Error::wrong_type(format!(
+                            "Hash key {} is not a common property",
+                            key.as_str()
+                        ))
+                    })?;
+                let position = u8::try_from(position).map_err(|_| {
+                    Error::wrong_type(format!(
+                        "Hash key {} is defined too far in the common properties",
+                        key.as_str()
+                    ))
+                })?;
+                let entry_count = entries.len() as u32;
+                let slot_count = slot_count(entry_count);
+                let mut slots = vec![0; slot_count as usize];
+                for (idx, entry) in entries.iter().enumerate() {
+                    let hash = hash_key(&entry.value(key)).ok_or_else(|| {
+                        Error::wrong_type(format!(
+                            "Values of property {} cannot be hashed",
+                            key.as_str()
+                        ))
+                    })?;
+                    let mut slot = hash % slot_count;
+                    while slots[slot as usize] != 0 {
+                        slot = (slot + 1) % slot_count;
+                    }
+                    slots[slot as usize] = idx as u32 + 1;
+                }
+                Ok(HashTable {
+                    name: name.clone(),
+                    key: PropertyIdx::from(position),
+                    entry_count,
+                    slots,
+                })
+            })
+            .collect()
+    }
+
     pub(crate) fn process_entries<Entry: EntryTrait<PN, VN>>(
         &mut self,
         entries: impl Iterator<Item = Entry>,
-    ) -> Vec<ProcessedEntry<VN>> {
-        if let Some(keys) = &self.sort_keys.take() {
-            let sorted_iter = SortedIter::new(entries, |p, n| p.compare(&keys, n).is_le());
-            sorted_iter.map(|e| self.build_entry(e)).collect()
+    ) -> Result<Vec<ProcessedEntry<VN>>> {
+        if let Some(keys) = self.sort_keys.take() {
+            let sorted_iter = SortedIter::new(entries, |p, n| Ok(p.compare(&keys, n)?.is_le()));
+            sorted_iter.map(|e| Ok(self.build_entry(e?))).collect()
         } else {
-            entries.map(|e| self.build_entry(e)).collect()
+            Ok(entries.map(|e| self.build_entry(e)).collect())
         }
     }
 
@@ -143,36 +386,220 @@ impl<PN: PropertyName, VN: VariantName> Schema<PN, VN> {
 #[cfg(test)]
 mod tests {
     use rustest::test;
+    use std::collections::HashMap;
+
+    use crate::bases::EntryIdx;
+    use crate::bases::PropertyIdx;
+    use crate::common::{hash_key, IndexSortKey};
+    use crate::creator::schema::{
+        CommonProperties, Property, Schema, SortKey, SortOrder, SortedIter,
+    };
+    use crate::creator::{EntryTrait, Error, SimpleEntry};
+    use crate::{ContentAddress, Value};
 
-    use crate::creator::schema::SortedIter;
+    type Entry = SimpleEntry<&'static str, ()>;
+
+    fn entry(date: u64, content_id: u32) -> Entry {
+        HashMap::from([
+            ("Date", Value::Unsigned(date)),
+            (
+                "Content",
+                Value::Content(ContentAddress::new(0.into(), content_id.into())),
+            ),
+        ])
+        .into()
+    }
+
+    fn schema(sort_keys: Vec<SortKey<&'static str>>) -> Schema<&'static str, ()> {
+        Schema::new(
+            CommonProperties::new(vec![
+                Property::new_uint("Date"),
+                Property::new_content_address("Content"),
+            ]),
+            vec![],
+            Some(sort_keys),
+        )
+    }
 
     #[test]
     fn sorted_iter_empty() {
         let iter = std::iter::empty::<u32>();
-        let mut sorted_iter = SortedIter::new(iter, |_, _| true);
-        assert_eq!(sorted_iter.next(), None);
+        let mut sorted_iter = SortedIter::new(iter, |_, _| Ok(true));
+        assert!(sorted_iter.next().is_none());
     }
 
     #[test]
     fn sorted_iter_once() {
         let iter = std::iter::once(5);
-        let mut sorted_iter = SortedIter::new(iter, |_, _| true);
-        assert_eq!(sorted_iter.next(), Some(5));
-        assert_eq!(sorted_iter.next(), None);
+        let mut sorted_iter = SortedIter::new(iter, |_, _| Ok(true));
+        assert_eq!(sorted_iter.next().unwrap().unwrap(), 5);
+        assert!(sorted_iter.next().is_none());
     }
 
     #[test]
     fn sorted_iter_sorted() {
         let iter = vec![5, 6, 8, 10].into_iter();
-        let sorted_iter = SortedIter::new(iter, |a, b| a < b);
-        assert_eq!(sorted_iter.collect::<Vec<_>>(), [5, 6, 8, 10]);
+        let sorted_iter = SortedIter::new(iter, |a, b| Ok(a < b));
+        assert_eq!(
+            sorted_iter.collect::<Result<Vec<_>, _>>().unwrap(),
+            [5, 6, 8, 10]
+        );
     }
 
     #[test]
-    #[xfail]
     fn sorted_iter_not_sorted() {
-        let iter = vec![5, 6, 8, 10, 9].into_iter();
-        let sorted_iter = SortedIter::new(iter, |a, b| a < b);
-        assert_eq!(sorted_iter.collect::<Vec<_>>(), [5, 6, 8, 10, 9]);
+        let iter = vec![5, 6, 8, 10, 9, 11].into_iter();
+        let mut sorted_iter = SortedIter::new(iter, |a, b| Ok(a < b));
+        assert_eq!(sorted_iter.next().unwrap().unwrap(), 5);
+        assert_eq!(sorted_iter.next().unwrap().unwrap(), 6);
+        assert_eq!(sorted_iter.next().unwrap().unwrap(), 8);
+        assert!(matches!(sorted_iter.next(), Some(Err(Error::Sort(_)))));
+        assert!(sorted_iter.next().is_none());
+    }
+
+    #[test]
+    fn sorted_iter_compare_error() {
+        let iter = vec![5, 6].into_iter();
+        let sorted_iter = SortedIter::new(iter, |_, _| Err(Error::sort("Cannot compare")));
+        assert!(matches!(
+            sorted_iter.collect::<Result<Vec<_>, _>>(),
+            Err(Error::Sort(_))
+        ));
+    }
+
+    #[test]
+    fn sort_key_from_name() {
+        let key: SortKey<&str> = "Date".into();
+        assert_eq!(key, SortKey::ascending("Date"));
+        assert_eq!(SortKey::descending("Date").order, SortOrder::Descending);
+    }
+
+    #[test]
+    fn entry_compare() {
+        let keys = [SortKey::descending("Date"), SortKey::ascending("Content")];
+        assert_eq!(
+            entry(5, 0).compare(&keys, &entry(4, 0)).unwrap(),
+            std::cmp::Ordering::Less
+        );
+        assert_eq!(
+            entry(5, 0).compare(&keys, &entry(5, 1)).unwrap(),
+            std::cmp::Ordering::Less
+        );
+        assert_eq!(
+            entry(5, 1).compare(&keys, &entry(5, 1)).unwrap(),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(
+            entry(4, 0).compare(&keys, &entry(5, 1)).unwrap(),
+            std::cmp::Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn process_entries_sorted() {
+        let mut schema = schema(vec![SortKey::descending("Date"), "Content".into()]);
+        let entries = vec![entry(30, 1), entry(30, 2), entry(20, 0), entry(20, 0)];
+        assert_eq!(
+            schema.process_entries(entries.into_iter()).unwrap().len(),
+            4
+        );
+    }
+
+    #[test]
+    fn process_entries_not_sorted() {
+        let mut schema = schema(vec![SortKey::descending("Date")]);
+        let entries = vec![entry(30, 0), entry(20, 0), entry(25, 0)];
+        assert!(matches!(
+            schema.process_entries(entries.into_iter()),
+            Err(Error::Sort(_))
+        ));
+    }
+
+    #[test]
+    fn secondary_orders() {
+        let mut schema = schema(vec![SortKey::descending("Date")]);
+        schema.add_secondary_order("ByContent", vec!["Content".into()]);
+        schema.add_secondary_order(
+            "ByContentDesc",
+            vec![SortKey::descending("Content"), "Date".into()],
+        );
+        let entries = vec![entry(30, 2), entry(20, 0), entry(20, 1), entry(10, 0)];
+        let orders = schema.secondary_orders(&entries).unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].name, "ByContent");
+        assert_eq!(
+            orders[0].sort_keys,
+            [IndexSortKey::new(1.into(), SortOrder::Ascending)]
+        );
+        // Entries with equal keys keep the entry store order.
+        assert_eq!(orders[0].entries, [1, 3, 2, 0].map(EntryIdx::from));
+        assert_eq!(orders[1].name, "ByContentDesc");
+        assert_eq!(orders[1].entries, [0, 2, 3, 1].map(EntryIdx::from));
+    }
+
+    #[test]
+    fn secondary_orders_not_common() {
+        let mut schema = schema(vec![]);
+        schema.add_secondary_order("ByName", vec!["Name".into()]);
+        assert!(matches!(
+            schema.secondary_orders(&[entry(30, 2)]),
+            Err(Error::Sort(_))
+        ));
+    }
+
+    #[test]
+    fn hash_tables() {
+        let mut schema = schema(vec![]);
+        schema.add_hash_key("ByDate", "Date");
+        let entries = vec![entry(30, 2), entry(20, 0), entry(20, 1), entry(10, 0)];
+        let tables = schema.hash_tables(&entries).unwrap();
+        assert_eq!(tables.len(), 1);
+        let table = &tables[0];
+        assert_eq!(table.name, "ByDate");
+        assert_eq!(table.key, PropertyIdx::from(0));
+        assert_eq!(table.entry_count, 4);
+        assert_eq!(table.slots.len(), 8);
+        let mut stored = table
+            .slots
+            .iter()
+            .filter(|s| **s != 0)
+            .copied()
+            .collect::<Vec<_>>();
+        stored.sort();
+        assert_eq!(stored, [1, 2, 3, 4]);
+        // Each entry is found by probing from the slot of its hash,
+        // the first entry of the duplicated date before the second one.
+        let probe = |date: u64| {
+            let mut slot = hash_key(&Value::Unsigned(date)).unwrap() % 8;
+            let mut found = vec![];
+            while table.slots[slot as usize] != 0 {
+                let idx = table.slots[slot as usize] - 1;
+                if entries[idx as usize].value(&"Date") == Value::Unsigned(date) {
+                    found.push(idx);
+                }
+                slot = (slot + 1) % 8;
+            }
+            found
+        };
+        assert_eq!(probe(30), [0]);
+        assert_eq!(probe(20), [1, 2]);
+        assert_eq!(probe(10), [3]);
+        assert_eq!(probe(40), []);
+    }
+
+    #[test]
+    fn hash_tables_wrong_key() {
+        let mut schema = schema(vec![]);
+        schema.add_hash_key("ByContent", "Content");
+        assert!(matches!(
+            schema.hash_tables(&[entry(30, 2)]),
+            Err(Error::WrongType(_))
+        ));
+        let mut schema = self::schema(vec![]);
+        schema.add_hash_key("ByName", "Name");
+        assert!(matches!(
+            schema.hash_tables(&[entry(30, 2)]),
+            Err(Error::WrongType(_))
+        ));
     }
 }
@@ -42,9 +42,105 @@ impl<PN: PropertyName> Properties<PN> {
 
     pub(crate) fn finalize(self, variant_name: Option<&'static str>) -> layout::Properties<PN> {
         let variant = variant_name.map(layout::Property::VariantId);
-        variant
+        let mut properties = variant
             .into_iter()
             .chain(self.0.into_iter().map(|p| p.finalize()))
-            .collect()
+            .collect::<layout::Properties<PN>>();
+        pack_bools(&mut properties);
+        properties
+    }
+}
+
+/// Pack consecutive stored bool properties in the same byte.
+///
+/// Each stored bool gets the next bit of the current byte. The last bool of a byte closes it.
+/// A byte is closed when it is full or when a property stored in the entry follows.
+fn pack_bools<PN: PropertyName>(properties: &mut [layout::Property<PN>]) {
+    // The index of the last bool put in the current byte and the next free bit.
+    let mut open_byte: Option<(usize, u8)> = None;
+    for idx in 0..properties.len() {
+        if let layout::Property::Bool { default: None, .. } = properties[idx] {
+            let next_bit = match open_byte {
+                Some((last, 8)) => {
+                    close_bool(&mut properties[last]);
+                    0
+                }
+                Some((_, next_bit)) => next_bit,
+                None => 0,
+            };
+            if let layout::Property::Bool { bit, .. } = &mut properties[idx] {
+                *bit = next_bit;
+            }
+            open_byte = Some((idx, next_bit + 1));
+        } else if properties[idx].size() != 0 {
+            if let Some((last, _)) = open_byte.take() {
+                close_bool(&mut properties[last]);
+            }
+        }
+    }
+    if let Some((last, _)) = open_byte {
+        close_bool(&mut properties[last]);
+    }
+}
+
+fn close_bool<PN: PropertyName>(property: &mut layout::Property<PN>) {
+    if let layout::Property::Bool { closing, .. } = property {
+        *closing = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bool_info(property: &layout::Property<&'static str>) -> Option<(u8, bool)> {
+        match property {
+            layout::Property::Bool { bit, closing, .. } => Some((*bit, *closing)),
+            _ => None,
+        }
+    }
+
+    #[rustest::test]
+    fn test_pack_bools() {
+        let mut properties = Properties::new(
+            (0..10)
+                .map(|_| Property::new_bool("bool"))
+                .chain([Property::new_uint("int"), Property::new_bool("bool")])
+                .collect(),
+        );
+        for (idx, property) in properties.iter_mut().enumerate() {
+            match property {
+                Property::Bool(prop, _) => {
+                    prop.absorb(true);
+                    prop.absorb(false);
+                }
+                Property::UnsignedInt(prop, _) => {
+                    prop.absorb(idx as u64);
+                    prop.absorb(0);
+                }
+                _ => unreachable!(),
+            }
+        }
+        let layout = properties.finalize(None);
+        let infos = layout.iter().map(bool_info).collect::<Vec<_>>();
+        assert_eq!(
+            infos,
+            [
+                Some((0, false)),
+                Some((1, false)),
+                Some((2, false)),
+                Some((3, false)),
+                Some((4, false)),
+                Some((5, false)),
+                Some((6, false)),
+                Some((7, true)),
+                Some((0, false)),
+                Some((1, true)),
+                None,
+                Some((0, true)),
+            ]
+        );
+        // Two bytes for the 10 first bools, one for the int and one for the last bool.
+        assert_eq!(layout.entry_size(), 4);
     }
 }
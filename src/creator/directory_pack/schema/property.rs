@@ -242,12 +242,139 @@ impl ContentAddress {
         }
     }
 }
+#[derive(Default)]
+pub struct Range {
+    count_counter: ValueCounter<u32>,
+    entry_idx_size: PropertySize<u32>,
+    entry_count_size: PropertySize<u32>,
+}
+
+impl Range {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn absorb(&mut self, v: EntryRange) -> ProcessedValue {
+        self.count_counter.process(v.size().into_u32());
+        self.entry_idx_size.process(v.begin().into_u32());
+        self.entry_count_size.process(v.size().into_u32());
+        ProcessedValue::Range(v)
+    }
+
+    fn finalize<PN: PropertyName>(self, name: PN) -> layout::Property<PN> {
+        layout::Property::Range {
+            entry_idx_size: self.entry_idx_size.into(),
+            entry_count_size: self.entry_count_size.into(),
+            default_count: self.count_counter.into(),
+            name,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Float {
+    counter: ValueCounter<crate::common::Float>,
+    need_f64: bool,
+}
+
+impl Float {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn absorb(&mut self, v: crate::common::Float) -> ProcessedValue {
+        self.counter.process(v);
+        self.need_f64 |= v.as_f32().is_none();
+        ProcessedValue::Float(v)
+    }
+
+    fn finalize<PN: PropertyName>(self, name: PN) -> layout::Property<PN> {
+        layout::Property::Float {
+            size: if self.need_f64 {
+                ByteSize::U8
+            } else {
+                ByteSize::U4
+            },
+            default: self.counter.into(),
+            name,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Bool {
+    counter: ValueCounter<bool>,
+}
+
+impl Bool {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn absorb(&mut self, v: bool) -> ProcessedValue {
+        self.counter.process(v);
+        ProcessedValue::Bool(v)
+    }
+
+    fn finalize<PN: PropertyName>(self, name: PN) -> layout::Property<PN> {
+        // Bit and closing are set when all properties are finalized.
+        layout::Property::Bool {
+            bit: 0,
+            closing: false,
+            default: self.counter.into(),
+            name,
+        }
+    }
+}
+
+pub struct FixedBytes {
+    size: u16,
+    counter: ValueCounter<SmallBytes>,
+}
+
+impl FixedBytes {
+    pub fn new(size: u16) -> Self {
+        assert!(
+            (1..=256).contains(&size),
+            "Fixed bytes size must be in [1, 256]"
+        );
+        Self {
+            size,
+            counter: Default::default(),
+        }
+    }
+
+    pub fn absorb(&mut self, data: SmallBytes) -> ProcessedValue {
+        assert_eq!(
+            data.len(),
+            self.size as usize,
+            "Fixed bytes value must have the size of the property"
+        );
+        if !matches!(self.counter, ValueCounter::Many) {
+            self.counter.process(data.clone());
+        }
+        ProcessedValue::FixedBytes(Box::new(data))
+    }
+
+    fn finalize<PN: PropertyName>(self, name: PN) -> layout::Property<PN> {
+        layout::Property::FixedBytes {
+            size: self.size,
+            default: self.counter.into(),
+            name,
+        }
+    }
+}
+
 pub enum Property<PN: PropertyName> {
     UnsignedInt(UnsignedInt, PN),
     SignedInt(SignedInt, PN),
     Array(Array, PN),
     IndirectArray(IndirectArray, PN),
     ContentAddress(ContentAddress, PN),
+    Range(Range, PN),
+    Float(Float, PN),
+    Bool(Bool, PN),
+    FixedBytes(FixedBytes, PN),
     Padding(/*size*/ u8),
 }
 
@@ -301,6 +428,37 @@ impl<PN: PropertyName> std::fmt::Debug for Property<PN> {
                 .field("content_id_size", &content_id_size)
                 .field("name", &name.as_str())
                 .finish(),
+            Self::Range(
+                Range {
+                    count_counter,
+                    entry_idx_size,
+                    entry_count_size,
+                },
+                name,
+            ) => f
+                .debug_struct("Range")
+                .field("count_counter", &count_counter)
+                .field("entry_idx_size", &entry_idx_size)
+                .field("entry_count_size", &entry_count_size)
+                .field("name", &name.as_str())
+                .finish(),
+            Self::Float(Float { counter, need_f64 }, name) => f
+                .debug_struct("Float")
+                .field("counter", &counter)
+                .field("need_f64", &need_f64)
+                .field("name", &name.as_str())
+                .finish(),
+            Self::Bool(Bool { counter }, name) => f
+                .debug_struct("Bool")
+                .field("counter", &counter)
+                .field("name", &name.as_str())
+                .finish(),
+            Self::FixedBytes(FixedBytes { size, counter }, name) => f
+                .debug_struct("FixedBytes")
+                .field("size", &size)
+                .field("counter", &counter)
+                .field("name", &name.as_str())
+                .finish(),
             Self::Padding(s) => f.debug_tuple("Padding").field(&s).finish(),
         }
     }
@@ -316,7 +474,12 @@ impl<PN: PropertyName> Property<PN> {
     }
 
     pub fn new_array(fixed_array_len: usize, store_handle: StoreHandle, name: PN) -> Self {
-        if fixed_array_len == 0 && store_handle.kind() == ValueStoreKind::Indexed {
+        if fixed_array_len == 0
+            && matches!(
+                store_handle.kind(),
+                ValueStoreKind::Indexed | ValueStoreKind::SizedIndexed
+            )
+        {
             Property::IndirectArray(IndirectArray { store_handle }, name)
         } else {
             Property::Array(
@@ -334,6 +497,50 @@ impl<PN: PropertyName> Property<PN> {
         Property::ContentAddress(Default::default(), name)
     }
 
+    /// A range of entries in the same entry store.
+    ///
+    /// If all ranges have the same count (a redirection to one entry for example),
+    /// only the index of the first entry is stored in the entries.
+    pub fn new_range(name: PN) -> Self {
+        Property::Range(Default::default(), name)
+    }
+
+    /// A floating point value.
+    ///
+    /// Values are stored as f32 if all of them can be stored as f32 without loss, as f64 else.
+    pub fn new_float(name: PN) -> Self {
+        Property::Float(Default::default(), name)
+    }
+
+    /// A boolean value.
+    ///
+    /// Consecutive bool properties are packed together, up to 8 bools per byte.
+    pub fn new_bool(name: PN) -> Self {
+        Property::Bool(Default::default(), name)
+    }
+
+    /// A opaque array of `size` bytes (a uuid, a hash, ...).
+    ///
+    /// All values must be `size` long. `size` must be in [1, 256].
+    pub fn new_fixed_bytes(size: u16, name: PN) -> Self {
+        Property::FixedBytes(FixedBytes::new(size), name)
+    }
+
+    pub(crate) fn name(&self) -> Option<&PN> {
+        match self {
+            Self::UnsignedInt(_, name)
+            | Self::SignedInt(_, name)
+            | Self::Array(_, name)
+            | Self::IndirectArray(_, name)
+            | Self::ContentAddress(_, name)
+            | Self::Range(_, name)
+            | Self::Float(_, name)
+            | Self::Bool(_, name)
+            | Self::FixedBytes(_, name) => Some(name),
+            Self::Padding(_) => None,
+        }
+    }
+
     pub(crate) fn finalize(self) -> layout::Property<PN> {
         match self {
             Self::UnsignedInt(prop, name) => prop.finalize(name),
@@ -341,6 +548,10 @@ impl<PN: PropertyName> Property<PN> {
             Self::Array(prop, name) => prop.finalize(name),
             Self::IndirectArray(prop, name) => prop.finalize(name),
             Self::ContentAddress(prop, name) => prop.finalize(name),
+            Self::Range(prop, name) => prop.finalize(name),
+            Self::Float(prop, name) => prop.finalize(name),
+            Self::Bool(prop, name) => prop.finalize(name),
+            Self::FixedBytes(prop, name) => prop.finalize(name),
             Self::Padding(size) => layout::Property::Padding(size),
         }
     }
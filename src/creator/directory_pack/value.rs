@@ -1,5 +1,6 @@
 use super::ValueHandle;
-use crate::common::ContentAddress;
+use crate::bases::{EntryRange, SmallBytes};
+use crate::common::{ContentAddress, Float};
 use std::cmp;
 
 #[derive(Debug, PartialEq)]
@@ -83,6 +84,10 @@ pub enum ProcessedValue {
     Content(ContentAddress),
     Unsigned(u64),
     Signed(i64),
+    Range(EntryRange),
+    Float(Float),
+    Bool(bool),
+    FixedBytes(Box<SmallBytes>),
     IndirectArray(Box<ValueHandle>),
     Array0(Box<ArrayS<0>>),
     Array1(Box<ArrayS<1>>),
@@ -94,7 +99,10 @@ impl PartialOrd for ProcessedValue {
     fn partial_cmp(&self, other: &ProcessedValue) -> Option<cmp::Ordering> {
         use ProcessedValue::*;
         match self {
-            Content(_) => None,
+            Content(v) => match other {
+                Content(o) => Some(v.cmp(o)),
+                _ => None,
+            },
             Unsigned(v) => match other {
                 Unsigned(o) => Some(v.cmp(o)),
                 _ => None,
@@ -103,6 +111,22 @@ impl PartialOrd for ProcessedValue {
                 Signed(o) => Some(v.cmp(o)),
                 _ => None,
             },
+            Range(v) => match other {
+                Range(o) => v.partial_cmp(o),
+                _ => None,
+            },
+            Float(v) => match other {
+                Float(o) => Some(v.cmp(o)),
+                _ => None,
+            },
+            Bool(v) => match other {
+                Bool(o) => Some(v.cmp(o)),
+                _ => None,
+            },
+            FixedBytes(v) => match other {
+                FixedBytes(o) => Some(v.cmp(o)),
+                _ => None,
+            },
             Array(v) => match other {
                 Array(other) => Some(v.cmp(other)),
                 Array0(other) => Some(v.cmp_array_s(other)),
@@ -9,6 +9,7 @@ use std::sync::{Arc, RwLock};
 pub(crate) enum ValueStoreKind {
     Plain,
     Indexed,
+    SizedIndexed,
 }
 
 pub struct ValueHandle {
@@ -107,6 +108,7 @@ impl std::ops::Deref for StoreHandle {
 pub enum ValueStore {
     Plain(PlainValueStore),
     Indexed(IndexedValueStore),
+    SizedIndexed(SizedIndexedValueStore),
 }
 
 impl ValueStore {
@@ -118,10 +120,24 @@ impl ValueStore {
         Self::Indexed(IndexedValueStore(BaseValueStore::new(Some(0)))).into()
     }
 
+    /// Create a indexed value store where each index entry stores the offset and the size of the value.
+    ///
+    /// Identical values are stored only once and a value being a prefix of another one
+    /// shares the data of the longer value.
+    pub fn new_sized_indexed(size_hint: Option<usize>) -> StoreHandle {
+        Self::SizedIndexed(SizedIndexedValueStore {
+            base: BaseValueStore::new(size_hint),
+            entries: vec![],
+            written: vec![],
+        })
+        .into()
+    }
+
     pub(crate) fn finalize(&mut self, idx: ValueStoreIdx) {
         match self {
             Self::Plain(ref mut s) => s.finalize(idx),
             Self::Indexed(ref mut s) => s.finalize(idx),
+            Self::SizedIndexed(ref mut s) => s.finalize(idx),
         }
     }
 
@@ -129,6 +145,7 @@ impl ValueStore {
         match self {
             Self::Plain(s) => s.add_value(data),
             Self::Indexed(s) => s.add_value(data),
+            Self::SizedIndexed(s) => s.add_value(data),
         }
     }
 
@@ -136,6 +153,7 @@ impl ValueStore {
         match &self {
             Self::Plain(s) => s.key_size(),
             Self::Indexed(s) => s.key_size(),
+            Self::SizedIndexed(s) => s.key_size(),
         }
     }
 
@@ -143,6 +161,7 @@ impl ValueStore {
         match &self {
             Self::Plain(s) => s.get_idx(),
             Self::Indexed(s) => s.get_idx(),
+            Self::SizedIndexed(s) => s.get_idx(),
         }
     }
 
@@ -150,6 +169,7 @@ impl ValueStore {
         match &self {
             Self::Plain(_) => ValueStoreKind::Plain,
             Self::Indexed(_) => ValueStoreKind::Indexed,
+            Self::SizedIndexed(_) => ValueStoreKind::SizedIndexed,
         }
     }
 
@@ -157,6 +177,7 @@ impl ValueStore {
         match self {
             Self::Plain(s) => s.get(idx),
             Self::Indexed(s) => s.get(idx),
+            Self::SizedIndexed(s) => s.get(idx),
         }
     }
 }
@@ -166,6 +187,7 @@ impl WritableTell for ValueStore {
         match self {
             Self::Plain(s) => s.write_data(stream),
             Self::Indexed(s) => s.write_data(stream),
+            Self::SizedIndexed(s) => s.write_data(stream),
         }
     }
 
@@ -173,6 +195,7 @@ impl WritableTell for ValueStore {
         match self {
             Self::Plain(s) => s.serialize_tail(ser),
             Self::Indexed(s) => s.serialize_tail(ser),
+            Self::SizedIndexed(s) => s.serialize_tail(ser),
         }
     }
 }
@@ -402,6 +425,115 @@ impl std::fmt::Debug for IndexedValueStore {
     }
 }
 
+pub struct SizedIndexedValueStore {
+    base: BaseValueStore,
+    entries: Vec<(u64, u64)>, // The (offset, size) of each index entry, in sorted order.
+    written: Vec<usize>,      // The data keys of the values actually written, in writing order.
+}
+// data[key].1 is the index of the entry of the value.
+
+impl SizedIndexedValueStore {
+    pub(self) fn finalize(&mut self, idx: ValueStoreIdx) {
+        self.base.idx = Some(idx);
+        let data = &mut self.base.data;
+        self.base
+            .sorted_indirect
+            .par_sort_unstable_by_key(|e| &data[*e].0);
+
+        // Deduplicate identical values. They share the same index entry.
+        let mut unique_keys: Vec<usize> = Vec::with_capacity(self.base.sorted_indirect.len());
+        for data_key in &self.base.sorted_indirect {
+            if let Some(last) = unique_keys.last() {
+                if data[*data_key].0 == data[*last].0 {
+                    data[*data_key].1 = data[*last].1;
+                    continue;
+                }
+            }
+            data[*data_key].1 = unique_keys.len() as u64;
+            unique_keys.push(*data_key);
+        }
+
+        // Values are sorted. Going backward, a value which is a prefix of another one
+        // is a prefix of the last written value.
+        self.entries = vec![(0, 0); unique_keys.len()];
+        let mut offset = 0;
+        let mut last_written: Option<(usize, u64)> = None;
+        for (entry_idx, data_key) in unique_keys.iter().enumerate().rev() {
+            let value = &data[*data_key].0;
+            let value_offset = match last_written {
+                Some((written_key, written_offset)) if data[written_key].0.starts_with(value) => {
+                    written_offset
+                }
+                _ => {
+                    let value_offset = offset;
+                    offset += value.len() as u64;
+                    self.written.push(*data_key);
+                    last_written = Some((*data_key, value_offset));
+                    value_offset
+                }
+            };
+            self.entries[entry_idx] = (value_offset, value.len() as u64);
+        }
+        self.base.size = offset.into();
+        self.base.finalized = true;
+    }
+
+    pub(self) fn add_value(&mut self, data: impl Into<Box<[u8]>>) -> usize {
+        self.base.add_value(data)
+    }
+
+    pub(self) fn get(&self, idx: usize) -> ValueIdx {
+        self.base.get(idx)
+    }
+
+    pub(self) fn key_size(&self) -> ByteSize {
+        if !self.base.finalized {
+            panic!("We can get sized indexed value store key size only on finalized store.");
+        }
+        needed_bytes(self.entries.len())
+    }
+
+    pub(self) fn get_idx(&self) -> Option<ValueStoreIdx> {
+        self.base.idx
+    }
+}
+
+impl WritableTell for SizedIndexedValueStore {
+    fn write_data(&mut self, stream: &mut dyn OutStream) -> Result<()> {
+        let mut serializer = Serializer::new(BlockCheck::Crc32);
+        for data_key in &self.written {
+            serializer.write_data(&self.base.data[*data_key].0)?;
+        }
+        stream.write_serializer(serializer)?;
+        Ok(())
+    }
+
+    fn serialize_tail(&mut self, ser: &mut Serializer) -> IoResult<()> {
+        ser.write_u8(0x02)?;
+        ser.write_u64(self.entries.len() as u64)?; // key count
+        let data_size = self.base.size.into_u64();
+        let offset_size = needed_bytes(data_size);
+        offset_size.serialize(ser)?; // offset_size
+        ser.write_usized(data_size, offset_size)?; // data size
+        for (offset, size) in &self.entries {
+            ser.write_usized(*offset, offset_size)?;
+            ser.write_usized(*size, offset_size)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for SizedIndexedValueStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SizedIndexedValueStore")
+            .field("idx", &self.get_idx())
+            .field("size", &self.base.size)
+            .field("entry count", &self.entries.len())
+            .field("data count", &self.base.data.len())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,4 +557,54 @@ mod tests {
         creator.write().unwrap().write(&mut out_stream)?;
         Ok(())
     }
+
+    #[rustest::test]
+    fn test_empty_sized_indexed() -> rustest::Result {
+        let creator = ValueStore::new_sized_indexed(None);
+        creator.finalize(0.into());
+        let buffer = vec![];
+        let mut out_stream = std::io::Cursor::new(buffer);
+        creator.write().unwrap().write(&mut out_stream)?;
+        Ok(())
+    }
+
+    #[rustest::test]
+    fn test_sized_indexed() -> rustest::Result {
+        let creator = ValueStore::new_sized_indexed(None);
+        let values = ["abc", "ab", "abd", "abc", "b", "a"]
+            .into_iter()
+            .map(|v| creator.add_value(v.as_bytes()))
+            .collect::<Vec<_>>();
+        creator.finalize(0.into());
+        // Sorted unique values are "a", "ab", "abc", "abd", "b"
+        assert_eq!(
+            values
+                .iter()
+                .map(|v| v.get().into_u64())
+                .collect::<Vec<_>>(),
+            vec![2, 1, 3, 2, 4, 0]
+        );
+        assert_eq!(creator.key_size(), ByteSize::U1);
+        let mut out_stream = std::io::Cursor::new(vec![]);
+        creator.write().unwrap().write(&mut out_stream)?;
+        let data = out_stream.into_inner();
+        // "b", "abd" and "abc" are written, "ab" and "a" are prefix of "abc".
+        assert_eq!(&data[..7], b"babdabc");
+        #[rustfmt::skip]
+        assert_eq!(
+            &data[11..32],
+            &[
+                0x02, // kind
+                0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // entry count
+                0x01, // offset_size
+                0x07, // data_size
+                0x04, 0x01, // "a"
+                0x04, 0x02, // "ab"
+                0x04, 0x03, // "abc"
+                0x01, 0x03, // "abd"
+                0x00, 0x01, // "b"
+            ]
+        );
+        Ok(())
+    }
 }
@@ -11,6 +11,16 @@ pub enum Error {
     /// This almost always because of a bug in the calling code.
     /// This could, and maybe will, be replaced by assert.
     WrongType(String),
+
+    /// Entries cannot be sorted as declared by the schema sort keys.
+    ///
+    /// Entries are not provided in order or values of a sort key cannot be compared.
+    Sort(String),
+
+    /// Error while reading an existing container.
+    ///
+    /// Can be raised when creating a container on top of an existing one.
+    Jbk(crate::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -21,10 +31,20 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<crate::Error> for Error {
+    fn from(value: crate::Error) -> Self {
+        Error::Jbk(value)
+    }
+}
+
 impl Error {
     pub fn wrong_type(msg: impl Into<String>) -> Self {
         Error::WrongType(msg.into())
     }
+
+    pub fn sort(msg: impl Into<String>) -> Self {
+        Error::Sort(msg.into())
+    }
 }
 
 impl fmt::Display for Error {
@@ -32,6 +52,8 @@ impl fmt::Display for Error {
         match self {
             Error::Io(e) => writeln!(f, "IO error {e}"),
             Error::WrongType(e) => writeln!(f, "{e}"),
+            Error::Sort(e) => writeln!(f, "{e}"),
+            Error::Jbk(e) => writeln!(f, "Jubako error {e}"),
         }
     }
 }
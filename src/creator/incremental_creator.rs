@@ -0,0 +1,191 @@
+use std::collections::{hash_map::Entry, HashMap};
+use std::sync::Arc;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use log::warn;
+
+use super::{
+    content_pack::{hash_content, CompHint, ContentAdder},
+    BasicCreator, CheckKind, Compression, ConcatMode, ContentPackCreator, EntryStoreCreatorTrait,
+    InputReader, PackData, PackRecipient, Progress,
+};
+use crate::{
+    bases::*,
+    common::PackKind,
+    creator::{Error, Result},
+    reader::{resolve_location, Container, MayMissPack, PackLocation},
+    ContentAddress,
+};
+
+/// IncrementalCreator creates a Jubako container on top of an existing one.
+///
+/// Content packs of the existing (base) container are reused in the new container:
+/// they are referenced by their uuid and are not copied.
+/// Only contents not already present in the base container are written in a new content pack.
+/// Contents are identified by their blake3 hash.
+///
+/// As the new container references packs of the base container, the base container
+/// must stay accessible (at the same relative location) to read the new container.
+pub struct IncrementalCreator {
+    creator: BasicCreator,
+    known_contents: HashMap<blake3::Hash, ContentAddress>,
+}
+
+impl IncrementalCreator {
+    /// Create a IncrementalCreator on top of the container at `base_path`.
+    ///
+    /// All contents of the base container are read to compute their hash.
+    /// Content packs of the base container which cannot be found are still referenced
+    /// but their contents will not be reused.
+    pub fn new(
+        base_path: impl AsRef<Utf8Path>,
+        outpath: impl AsRef<Utf8Path>,
+        concat_mode: ConcatMode,
+        vendor_id: VendorId,
+        compression: Compression,
+        progress: Arc<dyn Progress>,
+    ) -> Result<Self> {
+        let base_path = camino::absolute_utf8(base_path.as_ref())?;
+        let outpath = camino::absolute_utf8(outpath.as_ref())?;
+        if base_path == outpath {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Base container cannot be overwritten by the new container",
+            )));
+        }
+        let base_dir = base_path
+            .parent()
+            .expect("base_path is absolute and have a parent");
+
+        let base_container = Container::new(&base_path)?;
+        let manifest_pack = base_container.get_manifest_pack();
+        let mut creator = BasicCreator::new_with_content_pack_id(
+            outpath,
+            concat_mode,
+            vendor_id,
+            compression,
+            progress,
+            PackId::from(manifest_pack.max_id() + 1),
+        )?;
+
+        let mut known_contents = HashMap::new();
+        for pack_info in manifest_pack.get_pack_infos() {
+            if pack_info.pack_kind != PackKind::Content {
+                continue;
+            }
+            match base_container.get_pack(pack_info.pack_id)? {
+                Some(MayMissPack::FOUND(content_pack)) => {
+                    for content_idx in content_pack.get_content_count() {
+                        let bytes = content_pack
+                            .get_content(content_idx)?
+                            .expect("content_idx comes from content count");
+                        let mut hasher = blake3::Hasher::new();
+                        hasher.update_reader(bytes.stream())?;
+                        known_contents
+                            .entry(hasher.finalize())
+                            .or_insert(ContentAddress::new(pack_info.pack_id, content_idx));
+                    }
+                }
+                _ => warn!(
+                    "Content pack {} is missing. Its contents will not be reused.",
+                    pack_info.uuid
+                ),
+            }
+
+            let pack_data = PackData {
+                uuid: pack_info.uuid,
+                pack_size: pack_info.pack_size,
+                pack_kind: pack_info.pack_kind,
+                pack_id: pack_info.pack_id,
+                free_data: manifest_pack
+                    .get_pack_free_data_uuid(pack_info.uuid)?
+                    .unwrap_or_default()
+                    .to_vec(),
+                check_info: manifest_pack
+                    .get_pack_check_info(pack_info.uuid)?
+                    .expect("pack_info comes from manifest pack"),
+            };
+            let location =
+                match resolve_location(base_dir.as_std_path(), pack_info.pack_location.as_str())? {
+                    // Pack is stored in the base container file itself.
+                    PackLocation::Container => base_path.clone(),
+                    PackLocation::Path(path) => {
+                        Utf8PathBuf::from_path_buf(path).map_err(|path| {
+                            Error::Io(std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("Pack location {} is not utf8", path.display()),
+                            ))
+                        })?
+                    }
+                    PackLocation::Url(url) => {
+                        return Err(Error::Io(std::io::Error::new(
+                            std::io::ErrorKind::Unsupported,
+                            format!("Pack location {url} is not a local file"),
+                        )))
+                    }
+                };
+            creator.reuse_pack(pack_data, location);
+        }
+
+        Ok(Self {
+            creator,
+            known_contents,
+        })
+    }
+
+    /// Set the kinds of the checks stored in the check info of all created packs.
+    ///
+    /// Reused packs keep their own checks.
+    pub fn set_check_kinds(&mut self, check_kinds: Vec<CheckKind>) {
+        self.creator.set_check_kinds(check_kinds);
+    }
+
+    /// Set the signer used to sign the manifest pack of the container.
+    #[cfg(feature = "signature")]
+    pub fn set_signer(&mut self, signer: Arc<dyn super::ManifestSigner>) {
+        self.creator.set_signer(signer);
+    }
+
+    /// Finalize the creation of Jubako container and create the archive as `outfile`.
+    ///
+    /// Packs of `extra_content_pack_creators` must not use the pack ids of the base
+    /// container packs, nor the pack id `max_id + 1` of the new content pack.
+    pub fn finalize(
+        self,
+        entry_store_creator: Box<dyn EntryStoreCreatorTrait>,
+        extra_content_pack_creators: Vec<ContentPackCreator<dyn PackRecipient>>,
+    ) -> Result<()> {
+        self.creator
+            .finalize(entry_store_creator, extra_content_pack_creators)
+    }
+
+    /// Add a content to the container.
+    ///
+    /// If the content is already in the base container (or has already been added),
+    /// the existing content address is returned and content is not written again.
+    pub fn add_content(
+        &mut self,
+        content: Box<dyn InputReader>,
+        comp_hint: CompHint,
+    ) -> std::io::Result<ContentAddress> {
+        let (hash, content) = hash_content(content)?;
+        match self.known_contents.entry(hash) {
+            Entry::Occupied(e) => Ok(*e.get()),
+            Entry::Vacant(e) => {
+                let content_address = self.creator.add_content(content, comp_hint)?;
+                e.insert(content_address);
+                Ok(content_address)
+            }
+        }
+    }
+}
+
+impl ContentAdder for IncrementalCreator {
+    fn add_content(
+        &mut self,
+        content: Box<dyn InputReader>,
+        comp_hint: CompHint,
+    ) -> std::io::Result<ContentAddress> {
+        self.add_content(content, comp_hint)
+    }
+}
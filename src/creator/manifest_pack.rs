@@ -3,17 +3,47 @@ use camino::Utf8PathBuf;
 use super::{private::WritableTell, PackData, StoreHandle, ValueStore};
 use crate::bases::*;
 use crate::common::{
-    CheckInfo, CheckKind, ManifestCheckStream, ManifestPackHeader, PackHeader, PackHeaderInfo,
-    PackInfo, PackKind,
+    CheckInfo, CheckKind, Hasher, ManifestCheckStream, ManifestPackHeader, PackHeader,
+    PackHeaderInfo, PackInfo, PackKind,
 };
 use crate::creator::Result;
 use std::io::SeekFrom;
+#[cfg(feature = "signature")]
+use std::sync::Arc;
+
+/// A signer of manifest pack.
+///
+/// The signer is given the blake3 hash of the manifest pack
+/// (computed as for the manifest pack check info) and must sign it.
+/// This allow to sign with a key not directly accessible (hardware key, remote service, ...).
+#[cfg(feature = "signature")]
+pub trait ManifestSigner: Send + Sync {
+    /// The public key corresponding to the signing key.
+    fn verifying_key(&self) -> ed25519_dalek::VerifyingKey;
+
+    /// Sign the `message`.
+    fn sign(&self, message: &[u8]) -> ed25519_dalek::Signature;
+}
+
+#[cfg(feature = "signature")]
+impl ManifestSigner for ed25519_dalek::SigningKey {
+    fn verifying_key(&self) -> ed25519_dalek::VerifyingKey {
+        self.verifying_key()
+    }
+
+    fn sign(&self, message: &[u8]) -> ed25519_dalek::Signature {
+        ed25519_dalek::Signer::sign(self, message)
+    }
+}
 
 pub struct ManifestPackCreator {
     app_vendor_id: VendorId,
     free_data: PackFreeData,
     packs: Vec<(PackData, Utf8PathBuf)>,
     value_store: StoreHandle,
+    check_kinds: Vec<CheckKind>,
+    #[cfg(feature = "signature")]
+    signer: Option<Arc<dyn ManifestSigner>>,
 }
 
 impl ManifestPackCreator {
@@ -23,9 +53,27 @@ impl ManifestPackCreator {
             free_data,
             packs: vec![],
             value_store: ValueStore::new_indexed(),
+            check_kinds: vec![CheckKind::Blake3],
+            #[cfg(feature = "signature")]
+            signer: None,
         }
     }
 
+    /// Set the kinds of the checks stored in the check info of the pack.
+    ///
+    /// Checks are chained in the given order. Default is a blake3 check.
+    pub fn set_check_kinds(&mut self, check_kinds: Vec<CheckKind>) {
+        self.check_kinds = check_kinds;
+    }
+
+    /// Set the signer used to sign the manifest pack at finalization.
+    ///
+    /// The signature is added to the check info of the manifest pack.
+    #[cfg(feature = "signature")]
+    pub fn set_signer(&mut self, signer: Arc<dyn ManifestSigner>) {
+        self.signer = Some(signer);
+    }
+
     pub fn add_pack(&mut self, pack_info: PackData, locator: impl Into<Utf8PathBuf>) {
         self.packs.push((pack_info, locator.into()));
     }
@@ -50,9 +98,7 @@ impl ManifestPackCreator {
 
         for ((pack_data, locator), free_data_id) in self.packs.into_iter().zip(free_data_ids) {
             let check_info_pos = file.stream_position()? - origin_offset;
-            file.ser_write(&pack_data.check_info)?;
-            let check_info_size = file.stream_position()? - origin_offset - check_info_pos;
-            let check_info_size: usize = check_info_size.try_into().unwrap();
+            let check_info_size = file.ser_write(&pack_data.check_info)?;
             pack_infos.push(PackInfo::new(
                 pack_data,
                 0,
@@ -71,24 +117,55 @@ impl ManifestPackCreator {
         }
 
         let check_offset = file.stream_position()? - origin_offset;
-        let pack_size: Size = (check_offset
-            + CheckKind::Blake3.block_size().into_u64()
-            + PackHeader::BLOCK_SIZE as u64)
-            .into();
+        #[cfg(feature = "signature")]
+        let signed = self.signer.is_some();
+        #[cfg(not(feature = "signature"))]
+        let signed = false;
+        let mut check_info_size = CheckKind::block_size(&self.check_kinds).into_u64();
+        if signed {
+            check_info_size += CheckKind::Ed25519.check_size() as u64;
+            if self
+                .check_kinds
+                .iter()
+                .all(|k| matches!(k, CheckKind::None | CheckKind::Ed25519))
+            {
+                // The None check is replaced by the signature.
+                check_info_size -= CheckKind::None.check_size() as u64;
+            }
+        }
+        let pack_size: Size =
+            (check_offset + check_info_size + PackHeader::BLOCK_SIZE as u64).into();
 
         file.seek(SeekFrom::Start(origin_offset))?;
 
-        let pack_header = PackHeader::new(
-            PackKind::Manifest,
-            PackHeaderInfo::new(self.app_vendor_id, pack_size, check_offset.into()),
-        );
+        let pack_header = PackHeader {
+            // Pack locations are percent-encoded since 0.3.
+            minor_version: 3,
+            ..PackHeader::new(
+                PackKind::Manifest,
+                PackHeaderInfo::new(self.app_vendor_id, pack_size, check_offset.into()),
+            )
+        };
         file.ser_write(&pack_header)?;
         let header = ManifestPackHeader::new(self.free_data, nb_packs.into(), value_store_pos);
         file.ser_write(&header)?;
         file.seek(SeekFrom::Start(origin_offset))?;
 
         let mut check_stream = ManifestCheckStream::new(file, packs_offset.into(), nb_packs.into());
-        let check_info = CheckInfo::new_blake3(&mut check_stream)?;
+        let mut hasher = Hasher::new(
+            self.check_kinds
+                .iter()
+                .copied()
+                .chain(signed.then_some(CheckKind::Ed25519)),
+        );
+        hasher.update_reader(&mut check_stream)?;
+        let digests = hasher.finalize();
+        #[cfg_attr(not(feature = "signature"), allow(unused_mut))]
+        let mut check_info = CheckInfo::from_digests(&self.check_kinds, &digests);
+        #[cfg(feature = "signature")]
+        if let Some(signer) = &self.signer {
+            check_info.add_signature(signer.as_ref(), &digests);
+        }
         file.ser_write(&check_info)?;
 
         file.seek(SeekFrom::Start(origin_offset))?;
@@ -3,12 +3,14 @@ mod container_pack;
 mod content_pack;
 mod directory_pack;
 mod errors;
+mod incremental_creator;
 mod manifest_pack;
 
 pub use crate::bases::FileSource;
 use crate::bases::InOutStream;
 pub(crate) use crate::bases::OutStream;
 use crate::bases::*;
+pub use crate::common::CheckKind;
 use crate::common::{CheckInfo, CompressionType, PackKind};
 pub use basic_creator::{BasicCreator, ConcatMode, EntryStoreCreatorTrait};
 use camino::{Utf8Path, Utf8PathBuf};
@@ -21,7 +23,10 @@ pub use directory_pack::{
     ValueStore,
 };
 pub use errors::{Error, Result};
+pub use incremental_creator::IncrementalCreator;
 pub use manifest_pack::ManifestPackCreator;
+#[cfg(feature = "signature")]
+pub use manifest_pack::ManifestSigner;
 use std::fs::OpenOptions;
 use std::io::{self, Read, Seek, SeekFrom};
 use std::path::Path;
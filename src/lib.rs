@@ -15,14 +15,16 @@ pub mod cmd_utils;
 pub use const_format::concatcp;
 
 pub use crate::bases::{
-    ContentIdx, EntryCount, EntryIdx, EntryRange, Error, ErrorKind, FileSource, MayRef, Offset,
-    PackId, PropertyCount, PropertyIdx, PropertyName, Reader, Result, Size, SmallBytes,
-    SmallString, VariantIdx, VariantName, VendorId,
+    ContentIdx, EntryCount, EntryIdx, EntryRange, Error, ErrorKind, FileSource, FixedBytes, MayRef,
+    Offset, PackId, PropertyCount, PropertyIdx, PropertyName, Reader, Result, Size, SmallBytes,
+    SmallString, SourceTrait, VariantIdx, VariantName, VendorId,
 };
-pub use crate::common::{CompressionType, ContentAddress, Pack, Value};
+pub use crate::common::{CompressionType, ContentAddress, Float, Pack, SortOrder, Value};
 //use crate::reader::directory_pack::layout;
 //
 pub use camino::{Utf8Path, Utf8PathBuf};
+#[cfg(feature = "signature")]
+pub use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
 
 #[cfg(doctest)]
 use bases::BaseArray;
@@ -0,0 +1,308 @@
+use super::{ContentPack, DirectoryPack, ManifestPack};
+use crate::bases::*;
+use crate::common::{CheckInfo, Digests, Hasher, Pack, PackHeader, PackKind};
+use std::fmt;
+use uuid::Uuid;
+
+/// Progress of a check.
+///
+/// As packs are checked in parallel, methods may be called from different threads.
+pub trait CheckProgress: Send + Sync {
+    fn start_pack(&self, _uuid: Uuid, _size: Size) {}
+    fn hashed(&self, _uuid: Uuid, _size: Size) {}
+    fn end_pack(&self, _report: &PackCheckReport) {}
+}
+
+impl CheckProgress for () {}
+
+/// Kind of block checked inside a pack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+pub enum BlockKind {
+    Cluster,
+    Index,
+    EntryStore,
+    ValueStore,
+}
+
+impl fmt::Display for BlockKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cluster => write!(f, "cluster"),
+            Self::Index => write!(f, "index"),
+            Self::EntryStore => write!(f, "entry store"),
+            Self::ValueStore => write!(f, "value store"),
+        }
+    }
+}
+
+/// A block of a pack which cannot be read (wrong crc or invalid data).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+pub struct BlockFailure {
+    pub kind: BlockKind,
+    /// The index of the block (cluster index, index idx, ...)
+    pub idx: u32,
+    pub error: String,
+}
+
+impl BlockFailure {
+    /// Create a BlockFailure from the error raised while reading the block.
+    ///
+    /// Io errors are not failures of the block and are returned as error.
+    pub(crate) fn new(kind: BlockKind, idx: u32, error: Error) -> Result<Self> {
+        Ok(Self {
+            kind,
+            idx,
+            error: failure_message(error)?,
+        })
+    }
+}
+
+/// Get the message of a error raised by a check.
+///
+/// Io errors are not failures of the checked data and are returned as error.
+fn failure_message(error: Error) -> Result<String> {
+    match *error {
+        ErrorKind::Io(_) => Err(error),
+        _ => Ok(error.to_string()),
+    }
+}
+
+/// The result of the check of one pack.
+///
+/// Checks which have not been done (because pack is missing or a previous step failed)
+/// are `None`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+pub struct PackCheckReport {
+    pub uuid: Uuid,
+    /// The kind of the pack, as declared in the manifest or read in the pack header.
+    pub kind: Option<PackKind>,
+    /// The location of the pack, as declared in the manifest.
+    pub location: Option<String>,
+    pub found: bool,
+    /// Pack header crc is valid.
+    pub header_ok: Option<bool>,
+    /// Pack content matches the check info stored at the end of the pack.
+    pub tail_check_ok: Option<bool>,
+    /// Pack content matches the check info stored in the manifest pack.
+    pub manifest_check_ok: Option<bool>,
+    pub block_failures: Vec<BlockFailure>,
+    /// Error which prevents to check (a part of) the pack.
+    pub error: Option<String>,
+}
+
+impl PackCheckReport {
+    pub(crate) fn new(uuid: Uuid, kind: Option<PackKind>, location: Option<String>) -> Self {
+        Self {
+            uuid,
+            kind,
+            location,
+            found: false,
+            header_ok: None,
+            tail_check_ok: None,
+            manifest_check_ok: None,
+            block_failures: vec![],
+            error: None,
+        }
+    }
+
+    /// Is the pack valid.
+    ///
+    /// A missing pack is not considered as a failure.
+    pub fn is_ok(&self) -> bool {
+        !self.found
+            || (self.header_ok != Some(false)
+                && self.tail_check_ok != Some(false)
+                && self.manifest_check_ok != Some(false)
+                && self.block_failures.is_empty()
+                && self.error.is_none())
+    }
+
+    pub(crate) fn set_error(&mut self, error: Error) -> Result<()> {
+        self.error = Some(failure_message(error)?);
+        Ok(())
+    }
+
+    /// Check the pack in `reader` and fill the report.
+    ///
+    /// If given, the pack is also checked against `manifest_check_info`.
+    /// Return the pack header if it is valid.
+    pub(crate) fn check_reader(
+        &mut self,
+        reader: &Reader,
+        manifest_check_info: Option<&CheckInfo>,
+        progress: &dyn CheckProgress,
+    ) -> Result<Option<PackHeader>> {
+        self.found = true;
+        let pack_header = match reader.parse_block_at::<PackHeader>(Offset::zero()) {
+            Ok(pack_header) => pack_header,
+            Err(e) => {
+                self.header_ok = Some(false);
+                self.set_error(e)?;
+                return Ok(None);
+            }
+        };
+        self.header_ok = Some(true);
+        self.kind.get_or_insert(pack_header.magic);
+        progress.start_pack(self.uuid, pack_header.file_size);
+        if let Err(e) = self.check_pack(&pack_header, reader, manifest_check_info, progress) {
+            self.set_error(e)?;
+        }
+        Ok(Some(pack_header))
+    }
+
+    fn check_pack(
+        &mut self,
+        pack_header: &PackHeader,
+        reader: &Reader,
+        manifest_check_info: Option<&CheckInfo>,
+        progress: &dyn CheckProgress,
+    ) -> Result<()> {
+        match pack_header.magic {
+            PackKind::Manifest => {
+                // Manifest pack is not hashed as a plain data. Let it check itself.
+                let pack = ManifestPack::new(reader.clone())?;
+                self.tail_check_ok = Some(pack.check()?);
+                progress.hashed(self.uuid, pack_header.file_size);
+                return Ok(());
+            }
+            PackKind::Directory => {
+                self.block_failures = DirectoryPack::new(reader.clone())?.block_failures()?;
+            }
+            PackKind::Content => {
+                self.block_failures = ContentPack::new(reader.clone())?.block_failures()?;
+            }
+            // Nested container packs are flattened by ContainerPack,
+            // but a custom locator may return a reader on a container pack.
+            PackKind::Container => {
+                return Err(format_error!("Nested container pack not flattened"));
+            }
+        }
+
+        let tail_check_info = reader.parse_block_in::<CheckInfo>(
+            pack_header.check_info_pos,
+            pack_header.check_info_size(),
+        )?;
+        // Hash the pack only once for both check infos.
+        let size = Size::from(pack_header.check_info_pos);
+        let need_hash =
+            tail_check_info.need_hash() || manifest_check_info.is_some_and(|c| c.need_hash());
+        let digests = if need_hash {
+            let kinds = tail_check_info
+                .kinds()
+                .chain(manifest_check_info.into_iter().flat_map(|c| c.kinds()));
+            let mut hasher = Hasher::new(kinds);
+            reader.read_region_chunks(Offset::zero(), size, &mut |chunk| {
+                hasher.update(chunk);
+                progress.hashed(self.uuid, Size::from(chunk.len()));
+            })?;
+            hasher.finalize()
+        } else {
+            Digests::new_size(size)
+        };
+        let check = |check_info: &CheckInfo| check_info.check_digests(&digests);
+        self.tail_check_ok = Some(check(&tail_check_info));
+        self.manifest_check_ok = manifest_check_info.map(check);
+        Ok(())
+    }
+}
+
+fn display_check(check: Option<bool>) -> &'static str {
+    match check {
+        None => "not checked",
+        Some(true) => "ok",
+        Some(false) => "mismatch",
+    }
+}
+
+impl fmt::Display for PackCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pack {}", self.uuid)?;
+        if let Some(kind) = self.kind {
+            write!(f, " ({kind:?})")?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, " at `{location}`")?;
+        }
+        if !self.found {
+            return writeln!(f, ": missing");
+        }
+        if self.is_ok() {
+            return writeln!(f, ": ok");
+        }
+        writeln!(f, ": KO")?;
+        if self.header_ok == Some(false) {
+            writeln!(f, "  header: corrupted")?;
+        }
+        writeln!(f, "  check info: {}", display_check(self.tail_check_ok))?;
+        writeln!(
+            f,
+            "  manifest check info: {}",
+            display_check(self.manifest_check_ok)
+        )?;
+        for failure in &self.block_failures {
+            writeln!(f, "  {} {}: {}", failure.kind, failure.idx, failure.error)?;
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "  error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of the check of a container (or a set of packs).
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+pub struct CheckReport {
+    pub packs: Vec<PackCheckReport>,
+}
+
+impl CheckReport {
+    /// Are all packs valid.
+    ///
+    /// Missing packs are not considered as failures.
+    pub fn is_ok(&self) -> bool {
+        self.packs.iter().all(|p| p.is_ok())
+    }
+
+    /// Iterate on packs which failed their check.
+    pub fn failures(&self) -> impl Iterator<Item = &PackCheckReport> {
+        self.packs.iter().filter(|p| !p.is_ok())
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pack in &self.packs {
+            write!(f, "{pack}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::creator::ContainerPackCreator;
+
+    #[rustest::test]
+    fn test_check_container_pack() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = camino::Utf8PathBuf::try_from(temp_dir.path().join("container.jbk")).unwrap();
+        let mut creator = ContainerPackCreator::new(&path, Default::default()).unwrap();
+        creator
+            .add_pack(Uuid::new_v4(), &mut std::io::Cursor::new([0_u8; 64]))
+            .unwrap();
+        creator.finalize().unwrap();
+
+        // A reader on a (not flattened) container pack is reported as a failure.
+        let reader = Reader::from(FileSource::open(&path).unwrap());
+        let mut report = PackCheckReport::new(Uuid::new_v4(), None, None);
+        report.check_reader(&reader, None, &()).unwrap();
+        assert_eq!(report.kind, Some(PackKind::Container));
+        assert!(report.error.is_some());
+        assert!(!report.is_ok());
+    }
+}
@@ -1,16 +1,33 @@
-use super::{ContentPack, DirectoryPack, ManifestPack, PackLocatorTrait};
+use super::{CheckProgress, CheckReport, PackCheckReport, PackLocatorTrait};
 use crate::bases::*;
-use crate::common::{ContainerPackHeader, Pack, PackHeader, PackKind, PackLocator};
-use std::collections::HashMap;
+use crate::common::{ContainerPackHeader, PackHeader, PackKind, PackLocator};
+use rayon::prelude::*;
+use std::collections::{hash_map::Entry, HashMap};
 use uuid::Uuid;
 
+/// The maximum nesting depth of container packs.
+const MAX_NESTING_DEPTH: usize = 16;
+
 pub struct ContainerPack {
     packs_uuid: Vec<Uuid>,
     packs: HashMap<Uuid, Reader>,
 }
 
 impl ContainerPack {
+    /// Open a container pack.
+    ///
+    /// Nested container packs are flattened: their packs are accessible as if they
+    /// were directly stored in this container.
     pub fn new(reader: Reader) -> Result<Self> {
+        let mut container = Self {
+            packs_uuid: vec![],
+            packs: HashMap::new(),
+        };
+        container.add_packs_from(reader, 0)?;
+        Ok(container)
+    }
+
+    fn add_packs_from(&mut self, reader: Reader, depth: usize) -> Result<()> {
         let pack_header = reader.parse_block_at::<PackHeader>(Offset::zero())?;
         if pack_header.magic != PackKind::Container {
             return Err(format_error!("Pack Magic is not Container Pack"));
@@ -19,16 +36,41 @@ impl ContainerPack {
         let header =
             reader.parse_block_at::<ContainerPackHeader>(Offset::from(PackHeader::BLOCK_SIZE))?;
         let mut pack_offset = header.pack_locators_pos;
-        let mut packs_uuid = Vec::with_capacity(header.pack_count.into_usize());
-        let mut packs = HashMap::with_capacity(header.pack_count.into_usize());
+        self.packs_uuid.reserve(header.pack_count.into_usize());
+        self.packs.reserve(header.pack_count.into_usize());
         for _idx in header.pack_count {
             let pack_locator = reader.parse_block_at::<PackLocator>(pack_offset)?;
             pack_offset += PackLocator::BLOCK_SIZE;
             let pack_reader = reader.cut(pack_locator.pack_pos, pack_locator.pack_size, false)?;
-            packs.insert(pack_locator.uuid, pack_reader);
-            packs_uuid.push(pack_locator.uuid);
+            // A pack with a invalid header is kept as it is. It will be detected at check.
+            let is_container = matches!(
+                pack_reader.parse_block_at::<PackHeader>(Offset::zero()),
+                Ok(PackHeader {
+                    magic: PackKind::Container,
+                    ..
+                })
+            );
+            if is_container {
+                // A nested container must be strictly smaller than its parent,
+                // else a (crafted) container could contain itself.
+                if pack_locator.pack_size >= reader.size() {
+                    return Err(format_error!(&format!(
+                        "Nested container pack {} is not smaller than its parent",
+                        pack_locator.uuid
+                    )));
+                }
+                if depth >= MAX_NESTING_DEPTH {
+                    return Err(format_error!(&format!(
+                        "Container packs are nested more than {MAX_NESTING_DEPTH} times"
+                    )));
+                }
+                self.add_packs_from(pack_reader, depth + 1)?;
+            } else if let Entry::Vacant(e) = self.packs.entry(pack_locator.uuid) {
+                e.insert(pack_reader);
+                self.packs_uuid.push(pack_locator.uuid);
+            }
         }
-        Ok(Self { packs_uuid, packs })
+        Ok(())
     }
 
     pub fn new_fake(reader: Reader, uuid: Uuid) -> Self {
@@ -70,19 +112,31 @@ impl ContainerPack {
     }
 
     pub fn check(&self) -> Result<bool> {
-        for reader in self.packs.values() {
-            let pack_header = reader.parse_block_at::<PackHeader>(Offset::zero())?;
-            let ok = match pack_header.magic {
-                PackKind::Manifest => ManifestPack::new(reader.clone())?.check()?,
-                PackKind::Directory => DirectoryPack::new(reader.clone())?.check()?,
-                PackKind::Content => ContentPack::new(reader.clone())?.check()?,
-                PackKind::Container => todo!(),
-            };
-            if !ok {
-                return Ok(false);
-            }
-        }
-        Ok(true)
+        Ok(self.check_report()?.is_ok())
+    }
+
+    /// Check all packs in the container and report the result of the check of each pack.
+    ///
+    /// As there is no manifest, packs are only checked against their own check info.
+    pub fn check_report(&self) -> Result<CheckReport> {
+        self.check_report_with_progress(&())
+    }
+
+    /// Check all packs in the container and report the result of the check of each pack.
+    ///
+    /// Packs are checked in parallel and `progress` is notified as packs are checked.
+    pub fn check_report_with_progress(&self, progress: &dyn CheckProgress) -> Result<CheckReport> {
+        let packs = self
+            .packs_uuid
+            .par_iter()
+            .map(|uuid| {
+                let mut report = PackCheckReport::new(*uuid, None, None);
+                report.check_reader(&self.packs[uuid], None, progress)?;
+                progress.end_pack(&report);
+                Ok(report)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(CheckReport { packs })
     }
 }
 
@@ -126,6 +180,7 @@ impl graphex::Display for ContainerPack {
 #[cfg(feature = "explorable")]
 impl graphex::Node for ContainerPack {
     fn next(&self, item: &str) -> graphex::ExploreResult<'_> {
+        use super::{ContentPack, DirectoryPack, ManifestPack};
         let reader = if let Ok(index) = item.parse::<usize>() {
             let uuid = self
                 .packs_uuid
@@ -159,3 +214,54 @@ impl graphex::Node for ContainerPack {
         Some(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{CheckInfo, PackHeaderInfo};
+
+    // A container pack with one pack, located by `pack_locator`.
+    fn container_pack(pack_locator: impl Fn(Size) -> PackLocator) -> Vec<u8> {
+        let pack_locators_pos =
+            Offset::from(PackHeader::BLOCK_SIZE + ContainerPackHeader::BLOCK_SIZE);
+        let check_info_pos = pack_locators_pos + PackLocator::BLOCK_SIZE;
+        let check_info = CheckInfo::new_none();
+        let check_info_size = check_info
+            .serialize(&mut Serializer::new(BlockCheck::Crc32))
+            .unwrap();
+        let file_size = Size::from(
+            check_info_pos.into_u64() as usize
+                + check_info_size
+                + BlockCheck::Crc32.size()
+                + PackHeader::BLOCK_SIZE,
+        );
+        let mut out = std::io::Cursor::new(Vec::new());
+        out.ser_write(&PackHeader::new(
+            PackKind::Container,
+            PackHeaderInfo::new(VendorId::from([0, 0, 0, 0]), file_size, check_info_pos),
+        ))
+        .unwrap();
+        out.ser_write(&ContainerPackHeader::new(
+            pack_locators_pos,
+            PackCount::from(1),
+            Default::default(),
+        ))
+        .unwrap();
+        out.ser_write(&pack_locator(file_size)).unwrap();
+        out.ser_write(&check_info).unwrap();
+        let mut content = out.into_inner();
+        let mut footer = content[..PackHeader::BLOCK_SIZE].to_vec();
+        footer.reverse();
+        content.extend(footer);
+        assert_eq!(content.len() as u64, file_size.into_u64());
+        content
+    }
+
+    #[rustest::test]
+    fn test_self_referencing_container() {
+        let content =
+            container_pack(|file_size| PackLocator::new(Uuid::new_v4(), file_size, Offset::zero()));
+        let err = ContainerPack::new(Reader::from(content)).err().unwrap();
+        assert!(matches!(*err, ErrorKind::Format(_)), "{err:?}");
+    }
+}
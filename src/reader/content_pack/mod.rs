@@ -9,7 +9,7 @@ use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex, OnceLock};
 use uuid::Uuid;
 
-use super::ByteRegion;
+use super::{BlockFailure, BlockKind, ByteRegion};
 
 pub struct ContentPack {
     pack_header: PackHeader,
@@ -91,6 +91,22 @@ impl ContentPack {
     pub fn get_free_data(&self) -> &[u8] {
         self.header.free_data.as_ref()
     }
+
+    /// Check all clusters of the pack and return the ones which cannot be read.
+    pub(crate) fn block_failures(&self) -> Result<Vec<BlockFailure>> {
+        let mut failures = vec![];
+        for cluster_idx in self.header.cluster_count {
+            let cluster_info = self.cluster_ptrs.index(*cluster_idx)?;
+            if let Err(e) = self.reader.parse_data_block::<Cluster>(cluster_info) {
+                failures.push(BlockFailure::new(
+                    BlockKind::Cluster,
+                    cluster_idx.into_u32(),
+                    e,
+                )?);
+            }
+        }
+        Ok(failures)
+    }
 }
 
 #[cfg(feature = "explorable_serde")]
@@ -188,12 +204,7 @@ impl Pack for ContentPack {
             )?);
         }
         let check_info = self.check_info.get().unwrap();
-        let mut check_stream = self.reader.create_stream(
-            Offset::zero(),
-            Size::from(self.pack_header.check_info_pos),
-            false,
-        )?;
-        Ok(check_info.check(&mut check_stream)?)
+        check_info.check_reader(&self.reader, Size::from(self.pack_header.check_info_pos))
     }
 }
 
@@ -3,16 +3,17 @@ mod property;
 use inner::FromLayoutProperty;
 
 use super::entry_store::EntryStore;
+use super::index::Index;
 use super::layout::Properties as LProperties;
 use super::layout::VariantPart;
 use super::property_compare::PropertyCompare;
 use super::raw_value::RawValue;
+use super::EntryStorage;
 use super::LazyEntry;
 use crate::bases::*;
-use crate::common::Value;
+use crate::common::{IndexSortKey, SortOrder, Value};
 use crate::reader::directory_pack::private::ValueStorageTrait;
 use std::collections::HashMap;
-use std::rc::Rc;
 use std::sync::Arc;
 
 pub use self::property::*;
@@ -77,8 +78,9 @@ pub(crate) struct LazyEntryProperties {
 }
 
 pub struct AnyBuilder {
-    properties: Rc<LazyEntryProperties>,
+    properties: Arc<LazyEntryProperties>,
     store: Arc<EntryStore>,
+    sort_keys: Box<[(SmallString, SortOrder)]>,
 }
 
 impl AnyBuilder {
@@ -107,23 +109,107 @@ impl AnyBuilder {
                 })
             }
         };
-        let properties = Rc::new(LazyEntryProperties {
+        let properties = Arc::new(LazyEntryProperties {
             common,
             variant_part,
         });
-        Ok(Self { properties, store })
+        Ok(Self {
+            properties,
+            store,
+            sort_keys: Box::new([]),
+        })
+    }
+
+    /// Create a builder for the entries of `index`.
+    ///
+    /// PropertyCompare built from this builder are ordered if the index is sorted
+    /// on the compared properties, and so use a binary search on any range of the index
+    /// entries (as a [`EntryRange`](crate::EntryRange)).
+    pub fn new_for_index<ValueStorage>(
+        index: &Index,
+        entry_storage: &EntryStorage,
+        value_storage: &ValueStorage,
+    ) -> Result<Self>
+    where
+        ValueStorage: ValueStorageTrait,
+    {
+        let store = index.get_store(entry_storage)?;
+        let sort_keys = index
+            .sort_keys()
+            .iter()
+            .map(|key| {
+                store
+                    .layout()
+                    .common
+                    .name_at(key.property_idx)
+                    .map(|name| (name.clone(), key.order))
+                    .ok_or_else(|| {
+                        format_error!("Index sort key is not a property of the entry store")
+                    })
+            })
+            .collect::<Result<_>>()?;
+        let mut builder = Self::new(store, value_storage)?;
+        builder.sort_keys = sort_keys;
+        Ok(builder)
+    }
+
+    /// The name of the common property at `idx`.
+    pub(super) fn property_name(&self, idx: PropertyIdx) -> Option<&SmallString> {
+        self.store.layout().common.name_at(idx)
+    }
+
+    /// The sort orders of `property_names` if they are the first sort keys of the entries.
+    pub(super) fn sort_orders(&self, property_names: &[String]) -> Option<Box<[SortOrder]>> {
+        if property_names.len() > self.sort_keys.len() {
+            return None;
+        }
+        std::iter::zip(property_names, self.sort_keys.iter())
+            .map(|(name, (key, order))| (name.as_str() == key.as_str()).then_some(*order))
+            .collect()
+    }
+
+    /// The sort orders of `property_names` if they are the first of `sort_keys`.
+    pub(super) fn sort_orders_on(
+        &self,
+        property_names: &[String],
+        sort_keys: &[IndexSortKey],
+    ) -> Option<Box<[SortOrder]>> {
+        if property_names.len() > sort_keys.len() {
+            return None;
+        }
+        std::iter::zip(property_names, sort_keys)
+            .map(|(name, key)| {
+                let key_name = self.property_name(key.property_idx)?;
+                (name.as_str() == key_name.as_str()).then_some(key.order)
+            })
+            .collect()
     }
 
     /// Build a new PropertyCompare to search in a Range.
     ///
     /// Search will panic if property_name is not in the entry or if value is not of the right type.
+    /// Search in a index is a binary search if the index is sorted on `property_name` first.
     pub fn new_property_compare(&self, property_name: String, value: Value) -> PropertyCompare<'_> {
         PropertyCompare::new(self, vec![property_name], vec![value])
     }
 
+    /// Build a new PropertyCompare matching the entries whose `property_name` starts with `prefix`.
+    ///
+    /// Property must be a array (or fixed bytes) property.
+    /// Use it with [`RangeTrait::equal_range`](super::RangeTrait::equal_range) to get all the
+    /// matching entries of a index sorted on `property_name`.
+    pub fn new_prefix_compare(
+        &self,
+        property_name: String,
+        prefix: impl Into<SmallBytes>,
+    ) -> PropertyCompare<'_> {
+        PropertyCompare::new_prefix(self, vec![property_name], vec![Value::Array(prefix.into())])
+    }
+
     /// Build a new PropertyCompare from a set of propertie to search in a Range.
     ///
     /// Search will panic if property_names are not in the entry or if values are not of the right type.
+    /// Search in a index is a binary search if `property_names` are the first sort keys of the index.
     pub fn new_multiple_property_compare(
         &self,
         property_names: Vec<String>,
@@ -139,8 +225,8 @@ impl BuilderTrait for AnyBuilder {
     fn create_entry(&self, idx: EntryIdx) -> Result<Option<LazyEntry>> {
         Ok(self
             .store
-            .get_entry_reader(idx)
-            .map(|reader| LazyEntry::new(Rc::clone(&self.properties), reader.into())))
+            .get_entry_reader(idx)?
+            .map(|reader| LazyEntry::new(Arc::clone(&self.properties), reader.into())))
     }
 }
 
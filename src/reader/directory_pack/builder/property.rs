@@ -1,7 +1,7 @@
 use inner::FromLayoutProperty;
 
 use crate::bases::*;
-use crate::common::ContentAddress;
+use crate::common::{ContentAddress, Float};
 use crate::reader::directory_pack::layout;
 use crate::reader::directory_pack::private::ValueStorageTrait;
 use crate::reader::directory_pack::raw_layout::{DeportedDefault, DeportedInfo, PropertyKind};
@@ -482,6 +482,248 @@ impl PropertyBuilderTrait for ContentProperty {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RangeProperty {
+    offset: Offset,
+    entry_idx_size: ByteSize,
+    entry_count_size: ByteSize,
+    default_count: Option<EntryCount>,
+}
+
+impl RangeProperty {
+    pub(self) fn new(
+        offset: Offset,
+        entry_idx_size: ByteSize,
+        entry_count_size: ByteSize,
+        default_count: Option<EntryCount>,
+    ) -> Self {
+        Self {
+            offset,
+            entry_idx_size,
+            entry_count_size,
+            default_count,
+        }
+    }
+}
+
+impl FromLayoutProperty for RangeProperty {
+    fn from_property(
+        p: &layout::Property,
+        _value_storage: &impl ValueStorageTrait,
+    ) -> Result<Option<Self>>
+    where
+        Self: Sized,
+    {
+        Ok(match p.kind {
+            layout::PropertyKind::Range {
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+            } => Some(RangeProperty::new(
+                p.offset,
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+            )),
+            _ => None,
+        })
+    }
+}
+
+impl PropertyBuilderTrait for RangeProperty {
+    type Output = EntryRange;
+    fn create(&self, parser: &impl RandomParser) -> Result<Self::Output> {
+        let mut seq_parser = parser.create_parser(self.offset)?;
+        let entry_idx = EntryIdx::from(seq_parser.read_usized(self.entry_idx_size)? as u32);
+        let entry_count = match self.default_count {
+            None => EntryCount::from(seq_parser.read_usized(self.entry_count_size)? as u32),
+            Some(d) => d,
+        };
+        Ok(EntryRange::new_from_size(entry_idx, entry_count))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FloatProperty {
+    offset: Offset,
+    size: ByteSize,
+    default: Option<Float>,
+}
+
+impl FloatProperty {
+    pub(self) fn new(offset: Offset, size: ByteSize, default: Option<Float>) -> Self {
+        Self {
+            offset,
+            size,
+            default,
+        }
+    }
+}
+
+impl FromLayoutProperty for FloatProperty {
+    fn from_property(
+        p: &layout::Property,
+        _value_storage: &impl ValueStorageTrait,
+    ) -> Result<Option<Self>>
+    where
+        Self: Sized,
+    {
+        Ok(match p.kind {
+            layout::PropertyKind::Float {
+                float_size,
+                default,
+            } => Some(FloatProperty::new(p.offset, float_size, default)),
+            _ => None,
+        })
+    }
+}
+
+impl PropertyBuilderTrait for FloatProperty {
+    type Output = Float;
+    fn create(&self, parser: &impl RandomParser) -> Result<Self::Output> {
+        Ok(match self.default {
+            Some(v) => v,
+            None => match self.size {
+                ByteSize::U4 => Float::from(f32::from_bits(parser.read_u32(self.offset)?)),
+                _ => Float::from(f64::from_bits(parser.read_u64(self.offset)?)),
+            },
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BoolProperty {
+    offset: Offset,
+    bit: u8,
+    default: Option<bool>,
+}
+
+impl BoolProperty {
+    pub(self) fn new(offset: Offset, bit: u8, default: Option<bool>) -> Self {
+        Self {
+            offset,
+            bit,
+            default,
+        }
+    }
+}
+
+impl FromLayoutProperty for BoolProperty {
+    fn from_property(
+        p: &layout::Property,
+        _value_storage: &impl ValueStorageTrait,
+    ) -> Result<Option<Self>>
+    where
+        Self: Sized,
+    {
+        Ok(match p.kind {
+            layout::PropertyKind::Bool { bit, default } => {
+                Some(BoolProperty::new(p.offset, bit, default))
+            }
+            _ => None,
+        })
+    }
+}
+
+impl PropertyBuilderTrait for BoolProperty {
+    type Output = bool;
+    fn create(&self, parser: &impl RandomParser) -> Result<Self::Output> {
+        Ok(match self.default {
+            Some(v) => v,
+            None => (parser.read_u8(self.offset)? >> self.bit) & 0x01 != 0,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FixedBytesProperty {
+    offset: Offset,
+    size: u16,
+    default: Option<FixedBytes>,
+}
+
+impl FixedBytesProperty {
+    pub(self) fn new(offset: Offset, size: u16, default: Option<FixedBytes>) -> Self {
+        Self {
+            offset,
+            size,
+            default,
+        }
+    }
+}
+
+impl FromLayoutProperty for FixedBytesProperty {
+    fn from_property(
+        p: &layout::Property,
+        _value_storage: &impl ValueStorageTrait,
+    ) -> Result<Option<Self>>
+    where
+        Self: Sized,
+    {
+        Ok(match &p.kind {
+            layout::PropertyKind::FixedBytes { size, default } => {
+                Some(FixedBytesProperty::new(p.offset, *size, default.clone()))
+            }
+            _ => None,
+        })
+    }
+}
+
+impl PropertyBuilderTrait for FixedBytesProperty {
+    type Output = FixedBytes;
+    fn create(&self, parser: &impl RandomParser) -> Result<Self::Output> {
+        Ok(match &self.default {
+            Some(v) => v.clone(),
+            None => FixedBytes::from(parser.read_slice(self.offset, self.size as usize)?.as_ref()),
+        })
+    }
+}
+
+/// A fixed bytes property read as a `[u8; N]`.
+///
+/// Converting from a layout property of another size is a invalid cast.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FixedBytesArrayProperty<const N: usize> {
+    offset: Offset,
+    default: Option<[u8; N]>,
+}
+
+impl<const N: usize> FromLayoutProperty for FixedBytesArrayProperty<N> {
+    fn from_property(
+        p: &layout::Property,
+        _value_storage: &impl ValueStorageTrait,
+    ) -> Result<Option<Self>>
+    where
+        Self: Sized,
+    {
+        Ok(match &p.kind {
+            layout::PropertyKind::FixedBytes { size, default } if *size as usize == N => {
+                Some(Self {
+                    offset: p.offset,
+                    default: default
+                        .as_ref()
+                        .map(|d| d.as_bytes().try_into().expect("default has the right size")),
+                })
+            }
+            _ => None,
+        })
+    }
+}
+
+impl<const N: usize> PropertyBuilderTrait for FixedBytesArrayProperty<N> {
+    type Output = [u8; N];
+    fn create(&self, parser: &impl RandomParser) -> Result<Self::Output> {
+        Ok(match self.default {
+            Some(v) => v,
+            None => {
+                let mut data = [0; N];
+                parser.read_data(self.offset, &mut data)?;
+                data
+            }
+        })
+    }
+}
+
 /// The definition of a property, as we need to parse it.
 /// In opposition to RawProperty, the property is the "final" property.
 /// It describe how to parse te value of a entry.
@@ -491,6 +733,10 @@ pub enum AnyProperty {
     UnsignedInt(IntProperty),
     SignedInt(SignedProperty),
     Array(ArrayProperty),
+    Range(RangeProperty),
+    Float(FloatProperty),
+    Bool(BoolProperty),
+    FixedBytes(FixedBytesProperty),
 }
 
 impl FromLayoutProperty for AnyProperty {
@@ -562,6 +808,26 @@ impl FromLayoutProperty for AnyProperty {
                     p.offset, int_size, store, id,
                 )?)
             }
+            &PropertyKind::Range {
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+            } => Self::Range(RangeProperty::new(
+                p.offset,
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+            )),
+            &PropertyKind::Float {
+                float_size,
+                default,
+            } => Self::Float(FloatProperty::new(p.offset, float_size, default)),
+            &PropertyKind::Bool { bit, default } => {
+                Self::Bool(BoolProperty::new(p.offset, bit, default))
+            }
+            PropertyKind::FixedBytes { size, default } => {
+                Self::FixedBytes(FixedBytesProperty::new(p.offset, *size, default.clone()))
+            }
             PropertyKind::Padding => unreachable!(),
             PropertyKind::VariantId => unreachable!(),
         }))
@@ -590,6 +856,10 @@ impl PropertyBuilderTrait for AnyProperty {
                 }
             },
             Self::Array(prop) => RawValue::Array(prop.create(parser)?),
+            Self::Range(prop) => RawValue::Range(prop.create(parser)?),
+            Self::Float(prop) => RawValue::Float(prop.create(parser)?),
+            Self::Bool(prop) => RawValue::Bool(prop.create(parser)?),
+            Self::FixedBytes(prop) => RawValue::FixedBytes(prop.create(parser)?),
         })
     }
 }
@@ -711,6 +981,87 @@ mod tests {
         assert_eq!(prop.create(&byte_slice).unwrap(), -500);
     }
 
+    #[rustest::test]
+    fn test_range() {
+        let reader = Reader::from([0x01, 0x02, 0x03, 0x04, 0x05]);
+        let byte_slice = reader.get_byte_slice(Offset::zero(), reader.size());
+        let prop = RangeProperty::new(Offset::new(0), ByteSize::U1, ByteSize::U1, None);
+        assert_eq!(
+            prop.create(&byte_slice).unwrap(),
+            EntryRange::new_from_size(EntryIdx::from(0x01), EntryCount::from(0x02))
+        );
+        let prop = RangeProperty::new(Offset::new(1), ByteSize::U2, ByteSize::U2, None);
+        assert_eq!(
+            prop.create(&byte_slice).unwrap(),
+            EntryRange::new_from_size(EntryIdx::from(0x0302), EntryCount::from(0x0504))
+        );
+        let prop = RangeProperty::new(
+            Offset::new(2),
+            ByteSize::U3,
+            ByteSize::U1,
+            Some(EntryCount::from(1)),
+        );
+        assert_eq!(
+            prop.create(&byte_slice).unwrap(),
+            EntryRange::new_from_size(EntryIdx::from(0x050403), EntryCount::from(1))
+        );
+    }
+
+    #[rustest::test]
+    fn test_float() {
+        let reader = Reader::from([
+            0x00, 0x00, 0xC0, 0x3F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0,
+        ]);
+        let byte_slice = reader.get_byte_slice(Offset::zero(), reader.size());
+        let prop = FloatProperty::new(Offset::new(0), ByteSize::U4, None);
+        assert_eq!(prop.create(&byte_slice).unwrap(), Float::from(1.5));
+        let prop = FloatProperty::new(Offset::new(4), ByteSize::U8, None);
+        assert_eq!(prop.create(&byte_slice).unwrap(), Float::from(-2.0));
+        let prop = FloatProperty::new(Offset::new(0), ByteSize::U8, Some(Float::from(0.1)));
+        assert_eq!(prop.create(&byte_slice).unwrap(), Float::from(0.1));
+    }
+
+    #[rustest::test]
+    fn test_bool() {
+        let reader = Reader::from([0b1010_0101]);
+        let byte_slice = reader.get_byte_slice(Offset::zero(), reader.size());
+        let values = (0..8)
+            .map(|bit| {
+                BoolProperty::new(Offset::new(0), bit, None)
+                    .create(&byte_slice)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, [true, false, true, false, false, true, false, true]);
+        let prop = BoolProperty::new(Offset::new(0), 0, Some(false));
+        assert!(!prop.create(&byte_slice).unwrap());
+    }
+
+    #[rustest::test]
+    fn test_fixed_bytes() {
+        let reader = Reader::from([0x01, 0x02, 0x03, 0x04, 0x05]);
+        let byte_slice = reader.get_byte_slice(Offset::zero(), reader.size());
+        let prop = FixedBytesProperty::new(Offset::new(1), 3, None);
+        assert_eq!(
+            prop.create(&byte_slice).unwrap().as_bytes(),
+            [0x02, 0x03, 0x04]
+        );
+        let prop =
+            FixedBytesProperty::new(Offset::new(0), 2, Some(FixedBytes::from(&[0xAA, 0xBB])));
+        assert_eq!(prop.create(&byte_slice).unwrap().as_bytes(), [0xAA, 0xBB]);
+
+        let prop = FixedBytesArrayProperty::<4> {
+            offset: Offset::new(1),
+            default: None,
+        };
+        assert_eq!(prop.create(&byte_slice).unwrap(), [0x02, 0x03, 0x04, 0x05]);
+        let prop = FixedBytesArrayProperty::<2> {
+            offset: Offset::new(0),
+            default: Some([0xAA, 0xBB]),
+        };
+        assert_eq!(prop.create(&byte_slice).unwrap(), [0xAA, 0xBB]);
+    }
+
     mod mock {
         use super::*;
         #[derive(Debug)]
@@ -28,13 +28,26 @@ pub enum EntryStore {
 }
 
 impl EntryStore {
-    pub fn get_entry_reader(&self, idx: EntryIdx) -> Option<ByteSlice<'_>> {
+    /// Get the data of the entry `idx`.
+    ///
+    /// If the store has per entry crc32, the crc32 of the entry is checked.
+    pub fn get_entry_reader(&self, idx: EntryIdx) -> Result<Option<ByteSlice<'_>>> {
         match self {
             EntryStore::Plain(store) => store.get_entry_reader(idx),
             /*  todo!() */
         }
     }
 
+    /// Check all entries of the store.
+    ///
+    /// If the store has no per entry crc32, the store has already been checked
+    /// as a whole at parsing and there is nothing to check.
+    pub(crate) fn check(&self) -> Result<()> {
+        match self {
+            EntryStore::Plain(store) => store.check(),
+        }
+    }
+
     pub fn layout(&self) -> &Layout {
         match self {
             EntryStore::Plain(store) => store.layout(),
@@ -130,15 +143,33 @@ pub struct PlainStore {
 }
 
 impl PlainStore {
-    fn get_entry_reader(&self, idx: EntryIdx) -> Option<ByteSlice<'_>> {
-        if idx.is_valid(*self.layout.entry_count) {
-            Some(self.entry_reader.get_byte_slice(
-                Offset::from(self.layout.entry_size.into_u64() * idx.into_u64()),
-                self.layout.entry_size.into(),
-            ))
+    fn get_entry_reader(&self, idx: EntryIdx) -> Result<Option<ByteSlice<'_>>> {
+        if !idx.is_valid(*self.layout.entry_count) {
+            return Ok(None);
+        }
+        let offset = if self.layout.is_entry_checked {
+            // Entries are checked lazily, when we access them.
+            let stride = self.layout.entry_size.into_u64() + BlockCheck::Crc32.size() as u64;
+            let offset = Offset::from(stride * idx.into_u64());
+            self.entry_reader
+                .check_block(offset, self.layout.entry_size)?;
+            offset
         } else {
-            None
+            Offset::from(self.layout.entry_size.into_u64() * idx.into_u64())
+        };
+        Ok(Some(
+            self.entry_reader
+                .get_byte_slice(offset, self.layout.entry_size.into()),
+        ))
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.layout.is_entry_checked {
+            for idx in self.layout.entry_count {
+                self.get_entry_reader(idx)?;
+            }
         }
+        Ok(())
     }
 
     pub(crate) fn layout(&self) -> &Layout {
@@ -181,6 +212,7 @@ impl graphex::Node for PlainStore {
             .map_err(|e| graphex::Error::key(&format!("{e}")))?;
         let entry_reader = self
             .get_entry_reader(EntryIdx::from(index))
+            .map_err(graphex::Error::from)?
             .ok_or_else(|| graphex::Error::key(&format!("Key {index} is not found in store")))?;
         let mut data = vec![];
         entry_reader
@@ -429,6 +461,48 @@ mod tests {
         assert_eq!(store.layout.common.inner(), &expected);
     }
 
+    #[rustest::test]
+    fn test_entry_checked() {
+        fn add_block(content: &mut Vec<u8>, data: &[u8]) {
+            content.extend_from_slice(data);
+            content.extend_from_slice(&CRC.checksum(data).to_be_bytes());
+        }
+        let mut content = vec![];
+        add_block(&mut content, &[0x01, 0x02]); // entry 0
+        add_block(&mut content, &[0x03, 0x04]); // entry 1
+        let tail_offset = content.len();
+        #[rustfmt::skip]
+        add_block(&mut content, &[
+            0x00, // kind
+            0x02, 0x00, 0x00, 0x00, // entry_count (2)
+            0x01, // flag (entry checked)
+            0x02, 0x00, // entry_size (2)
+            0x00, // variant count
+            0x01, // property count
+            0b0010_0001, 1, b'V', // u16
+        ]);
+        let sized_offset = SizedOffset::new(
+            ASize::from(content.len() - tail_offset - 4),
+            Offset::from(tail_offset),
+        );
+
+        let reader = Reader::from(content.clone());
+        let store = reader.parse_data_block::<EntryStore>(sized_offset).unwrap();
+        store.check().unwrap();
+        let entry = store.get_entry_reader(1.into()).unwrap().unwrap();
+        assert_eq!(&*entry.get_slice(Offset::zero(), 2).unwrap(), &[0x03, 0x04]);
+        assert!(store.get_entry_reader(2.into()).unwrap().is_none());
+
+        // Corrupt entry 1. Store can still be parsed and entry 0 read.
+        content[7] = 0xFF;
+        let reader = Reader::from(content);
+        let store = reader.parse_data_block::<EntryStore>(sized_offset).unwrap();
+        let entry = store.get_entry_reader(0.into()).unwrap().unwrap();
+        assert_eq!(&*entry.get_slice(Offset::zero(), 2).unwrap(), &[0x01, 0x02]);
+        assert!(store.get_entry_reader(1.into()).is_err());
+        assert!(store.check().is_err());
+    }
+
     #[rustest::test]
     fn test_2variants() {
         #[rustfmt::skip]
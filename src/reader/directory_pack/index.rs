@@ -1,7 +1,27 @@
+use super::builder::AnyBuilder;
+use super::range::CompareTrait;
 use super::{EntryRange, EntryStorage, EntryStore, RangeTrait};
 use crate::bases::*;
+use crate::common::{hash_key, slot_count, IndexSortKey, Value};
 use std::sync::Arc;
 
+/// How the entries of a index are stored.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
+pub(crate) enum IndexKind {
+    /// The entries are a contiguous range of the entry store.
+    Range { entry_offset: EntryIdx },
+    /// The entries are listed in a array of entry indexes of `idx_size` bytes each.
+    Permutation { idx_size: ByteSize },
+    /// The entries are a contiguous range of the entry store, with a hash table
+    /// on the property `key_property`, of slots of `slot_size` bytes each.
+    Hash {
+        entry_offset: EntryIdx,
+        key_property: PropertyIdx,
+        slot_size: ByteSize,
+    },
+}
+
 #[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "explorable_serde",
@@ -9,11 +29,11 @@ use std::sync::Arc;
     serde(rename = "Index")
 )]
 pub(crate) struct IndexHeader {
+    pub kind: IndexKind,
     pub store_id: EntryStoreIdx,
     pub entry_count: EntryCount,
-    pub entry_offset: EntryIdx,
     pub free_data: IndexFreeData,
-    pub index_property: u8,
+    pub sort_keys: Box<[IndexSortKey]>,
     pub name: SmallString,
 }
 
@@ -26,9 +46,31 @@ impl graphex::Display for IndexHeader {
         out.field("name", &self.name.as_str())?;
         out.field("store_id", &self.store_id.into_u64())?;
         out.field("entry_count", &self.entry_count.into_u64())?;
-        out.field("entry_offset", &self.entry_offset.into_u64())?;
+        match self.kind {
+            IndexKind::Range { entry_offset } => {
+                out.field("entry_offset", &entry_offset.into_u64())?
+            }
+            IndexKind::Permutation { idx_size } => {
+                out.field("permutation idx_size", &(idx_size as usize))?
+            }
+            IndexKind::Hash {
+                entry_offset,
+                key_property,
+                slot_size,
+            } => {
+                out.field("entry_offset", &entry_offset.into_u64())?;
+                out.field("hash key", &key_property.into_u8())?;
+                out.field("hash slot_size", &(slot_size as usize))?
+            }
+        }
         out.field("free_data", &graphex::AsBytes(&*self.free_data))?;
-        out.field("index_property", &self.index_property)
+        let sort_keys = self
+            .sort_keys
+            .iter()
+            .map(|k| format!("{} {:?}", k.property_idx.into_u8(), k.order))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.field("sort_keys", &sort_keys.as_str())
     }
 }
 
@@ -39,14 +81,29 @@ impl Parsable for IndexHeader {
         let entry_count = Count::<u32>::parse(parser)?.into();
         let entry_offset = Idx::<u32>::parse(parser)?.into();
         let free_data = IndexFreeData::parse(parser)?;
-        let index_property = parser.read_u8()?;
+        let kind_and_count = parser.read_u8()?;
+        let sort_keys = (0..kind_and_count & IndexSortKey::MAX_COUNT)
+            .map(|_| IndexSortKey::parse(parser))
+            .collect::<Result<_>>()?;
+        let kind = match kind_and_count >> 6 {
+            0 => IndexKind::Range { entry_offset },
+            1 => IndexKind::Permutation {
+                idx_size: ByteSize::parse(parser)?,
+            },
+            2 => IndexKind::Hash {
+                entry_offset,
+                key_property: Idx::<u8>::parse(parser)?.into(),
+                slot_size: ByteSize::parse(parser)?,
+            },
+            v => return Err(format_error!(&format!("Invalid index kind ({v})"), parser)),
+        };
         let name = PString::parse(parser)?;
         Ok(Self {
+            kind,
             store_id,
             entry_count,
-            entry_offset,
             free_data,
-            index_property,
+            sort_keys,
             name,
         })
     }
@@ -54,6 +111,117 @@ impl Parsable for IndexHeader {
 
 impl BlockParsable for IndexHeader {}
 
+/// The index header of the 0.2 directory packs.
+///
+/// The byte following the free data is the `indexKey`, which has never been used.
+/// Those indexes are always contiguous ranges, without known sort order.
+pub(crate) struct LegacyIndexHeader;
+
+impl Parsable for LegacyIndexHeader {
+    type Output = IndexHeader;
+    fn parse(parser: &mut impl Parser) -> Result<IndexHeader> {
+        let store_id = Idx::<u32>::parse(parser)?.into();
+        let entry_count = Count::<u32>::parse(parser)?.into();
+        let entry_offset = Idx::<u32>::parse(parser)?.into();
+        let free_data = IndexFreeData::parse(parser)?;
+        let _index_key = parser.read_u8()?;
+        let name = PString::parse(parser)?;
+        Ok(IndexHeader {
+            kind: IndexKind::Range { entry_offset },
+            store_id,
+            entry_count,
+            free_data,
+            sort_keys: [].into(),
+            name,
+        })
+    }
+}
+
+impl BlockParsable for LegacyIndexHeader {}
+
+/// The array of entry indexes of a permutation index.
+#[derive(Debug, Clone)]
+struct Permutation {
+    reader: CheckReader,
+    idx_size: ByteSize,
+}
+
+impl Permutation {
+    fn get(&self, idx: EntryIdx) -> Result<EntryIdx> {
+        let offset = Offset::from(idx.into_u64() * self.idx_size as u64);
+        let mut parser = self
+            .reader
+            .create_parser(offset, ASize::from(self.idx_size as usize))?;
+        Ok(EntryIdx::from(parser.read_usized(self.idx_size)? as u32))
+    }
+}
+
+/// The hash table of a hash index.
+#[derive(Debug)]
+struct HashTable {
+    reader: CheckReader,
+    slot_size: ByteSize,
+    slot_count: u64,
+    key_property: PropertyIdx,
+}
+
+impl HashTable {
+    /// Get the index (in the index) of the entry stored in `slot`.
+    fn get(&self, slot: u64) -> Result<Option<EntryIdx>> {
+        let offset = Offset::from(slot * self.slot_size as u64);
+        let mut parser = self
+            .reader
+            .create_parser(offset, ASize::from(self.slot_size as usize))?;
+        Ok(match parser.read_usized(self.slot_size)? {
+            0 => None,
+            v => Some(EntryIdx::from((v - 1) as u32)),
+        })
+    }
+}
+
+/// The entries of a index, or of a part of it.
+///
+/// Depending of the index kind, this is a range of the entry store
+/// or a range of the permutation of the index.
+#[derive(Debug, Clone)]
+pub struct IndexRange {
+    range: EntryRange,
+    permutation: Option<Permutation>,
+    sort_keys: Arc<[IndexSortKey]>,
+}
+
+impl RangeTrait for IndexRange {
+    type SubRange = Self;
+
+    fn offset(&self) -> EntryIdx {
+        self.range.begin()
+    }
+
+    fn count(&self) -> EntryCount {
+        self.range.size()
+    }
+
+    fn entry_idx(&self, idx: EntryIdx) -> Result<EntryIdx> {
+        let idx = self.range.begin() + idx;
+        match &self.permutation {
+            None => Ok(idx),
+            Some(permutation) => permutation.get(idx),
+        }
+    }
+
+    fn sub_range(&self, begin: EntryIdx, end: EntryIdx) -> Self {
+        Self {
+            range: EntryRange::new(self.range.begin() + begin, self.range.begin() + end),
+            permutation: self.permutation.clone(),
+            sort_keys: Arc::clone(&self.sort_keys),
+        }
+    }
+
+    fn sort_keys(&self) -> &[IndexSortKey] {
+        &self.sort_keys
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(
     feature = "explorable_serde",
@@ -62,13 +230,72 @@ impl BlockParsable for IndexHeader {}
 )]
 pub struct Index {
     header: IndexHeader,
+    #[cfg_attr(feature = "explorable_serde", serde(skip))]
+    range: IndexRange,
+    #[cfg_attr(feature = "explorable_serde", serde(skip))]
+    hash_table: Option<HashTable>,
 }
 
-impl Index {
-    pub(crate) fn new(header: IndexHeader) -> Self {
-        Self { header }
+impl DataBlockParsable for Index {
+    type TailParser = IndexHeader;
+    type Output = Self;
+
+    fn finalize(header: IndexHeader, header_offset: Offset, reader: &Reader) -> Result<Self> {
+        let mut hash_table = None;
+        let sort_keys: Arc<[IndexSortKey]> = header.sort_keys.clone().into();
+        let range = match header.kind {
+            IndexKind::Range { entry_offset } => IndexRange {
+                range: EntryRange::new_from_size(entry_offset, header.entry_count),
+                permutation: None,
+                sort_keys,
+            },
+            IndexKind::Hash {
+                entry_offset,
+                key_property,
+                slot_size,
+            } => {
+                let slot_count = slot_count(header.entry_count.into_u32());
+                let data_size = Size::from(slot_count * slot_size as u64);
+                let reader = reader.cut_check(
+                    header_offset - data_size - ASize::from(BlockCheck::Crc32.size()),
+                    data_size,
+                    BlockCheck::Crc32,
+                )?;
+                hash_table = Some(HashTable {
+                    reader,
+                    slot_size,
+                    slot_count,
+                    key_property,
+                });
+                IndexRange {
+                    range: EntryRange::new_from_size(entry_offset, header.entry_count),
+                    permutation: None,
+                    sort_keys,
+                }
+            }
+            IndexKind::Permutation { idx_size } => {
+                let data_size = Size::from(header.entry_count.into_u64() * idx_size as u64);
+                let reader = reader.cut_check(
+                    header_offset - data_size - ASize::from(BlockCheck::Crc32.size()),
+                    data_size,
+                    BlockCheck::Crc32,
+                )?;
+                IndexRange {
+                    range: EntryRange::new_from_size(EntryIdx::from(0), header.entry_count),
+                    permutation: Some(Permutation { reader, idx_size }),
+                    sort_keys,
+                }
+            }
+        };
+        Ok(Self {
+            header,
+            range,
+            hash_table,
+        })
     }
+}
 
+impl Index {
     pub fn get_store(&self, entry_storage: &EntryStorage) -> Result<Arc<EntryStore>> {
         Ok(Arc::clone(
             entry_storage.get_entry_store(self.header.store_id)?,
@@ -86,6 +313,86 @@ impl Index {
     pub fn is_empty(&self) -> bool {
         self.header.entry_count.into_u32() == 0
     }
+
+    /// The properties on which the entries of the index are sorted.
+    ///
+    /// Empty if the index is not sorted.
+    pub fn sort_keys(&self) -> &[IndexSortKey] {
+        &self.header.sort_keys
+    }
+
+    /// Is the index a permutation of its entry store (a secondary index).
+    ///
+    /// Entries of a permutation index are not contiguous in the entry store
+    /// and [`as_range`](Self::as_range) returns `None`.
+    pub fn is_permutation(&self) -> bool {
+        self.range.permutation.is_some()
+    }
+
+    /// Get the range of the entry store covered by the index.
+    ///
+    /// Return `None` if the index is a permutation, as its entries are not contiguous in the
+    /// entry store. Use [`RangeTrait::sub_range`] or [`RangeTrait::iter`] to access the entries
+    /// of any index.
+    pub fn as_range(&self) -> Option<EntryRange> {
+        if self.is_permutation() {
+            None
+        } else {
+            Some(EntryRange::from(self))
+        }
+    }
+
+    /// Is the index a hash index, allowing to [`lookup`](Self::lookup) entries by their key.
+    pub fn is_hash(&self) -> bool {
+        self.hash_table.is_some()
+    }
+
+    /// Find a entry from the value of its key, using the hash table of the index.
+    ///
+    /// `builder` must be a builder of the entry store of the index.
+    /// Return the index of the entry in the index, as [`RangeTrait::find`].
+    /// If several entries have the same key, the first one (in the index order) is returned.
+    /// Fails if the index is not a hash index.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is not of the type of the key property.
+    pub fn lookup(&self, builder: &AnyBuilder, key: &Value) -> Result<Option<EntryIdx>> {
+        let Some(table) = &self.hash_table else {
+            return Err(IndexKindError {
+                name: self.header.name.to_string(),
+                expected: "hash",
+            }
+            .into());
+        };
+        let Some(hash) = hash_key(key) else {
+            // Keys of a hash index are always hashable.
+            return Ok(None);
+        };
+        let Some(name) = builder.property_name(table.key_property) else {
+            return Err(format_error!(
+                "Index hash key is not a property of the entry store"
+            ));
+        };
+        let comparator = builder.new_property_compare(name.to_string(), key.clone());
+        let mut slot = hash % table.slot_count;
+        // A valid table always has empty slots, but do not loop forever on a corrupted one.
+        for _ in 0..table.slot_count {
+            let Some(idx) = table.get(slot)? else {
+                return Ok(None);
+            };
+            if !idx.is_valid(*self.count()) {
+                return Err(format_error!(&format!(
+                    "Hash table entry {idx} is out of the index"
+                )));
+            }
+            if comparator.compare_entry(self.entry_idx(idx)?)?.is_eq() {
+                return Ok(Some(idx));
+            }
+            slot = (slot + 1) % table.slot_count;
+        }
+        Ok(None)
+    }
 }
 
 impl From<&Index> for EntryRange {
@@ -95,12 +402,26 @@ impl From<&Index> for EntryRange {
 }
 
 impl RangeTrait for Index {
+    type SubRange = IndexRange;
+
     fn offset(&self) -> EntryIdx {
-        self.header.entry_offset
+        self.range.offset()
     }
 
     fn count(&self) -> EntryCount {
-        self.header.entry_count
+        self.range.count()
+    }
+
+    fn entry_idx(&self, idx: EntryIdx) -> Result<EntryIdx> {
+        self.range.entry_idx(idx)
+    }
+
+    fn sub_range(&self, begin: EntryIdx, end: EntryIdx) -> IndexRange {
+        self.range.sub_range(begin, end)
+    }
+
+    fn sort_keys(&self) -> &[IndexSortKey] {
+        self.range.sort_keys()
     }
 }
 
@@ -117,6 +438,7 @@ impl graphex::Display for Index {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::SortOrder;
 
     #[rustest::test]
     fn test_index() {
@@ -131,18 +453,134 @@ mod tests {
         ];
         let reader = Reader::from(content);
         let header = reader
-            .parse_block_in::<IndexHeader>(Offset::zero(), 23.into())
+            .parse_block_in::<LegacyIndexHeader>(Offset::zero(), 23.into())
             .unwrap();
         assert_eq!(
             header,
             IndexHeader {
+                kind: IndexKind::Range {
+                    entry_offset: EntryIdx::from(2)
+                },
                 store_id: EntryStoreIdx::from(1),
                 entry_count: EntryCount::from(0xff00),
-                entry_offset: EntryIdx::from(2),
                 free_data: [0x00; 4].into(),
-                index_property: 1,
+                sort_keys: [].into(),
                 name: "Hello".into()
             }
         );
     }
+
+    #[rustest::test]
+    fn test_index_sort_keys() {
+        let content = vec![
+            0x01, 0x00, 0x00, 0x00, // store_id
+            0x00, 0xff, 0x00, 0x00, // entry_count
+            0x02, 0x00, 0x00, 0x00, // entry_offset
+            0x00, 0x00, 0x00, 0x00, // free data
+            0x02, // kind (range) and sort key count
+            0x01, // sort key: property 1, ascending
+            0x83, // sort key: property 3, descending
+            0x05, 0x48, 0x65, 0x6C, 0x6C, 0x6F, // PString Hello
+            0x5D, 0x3C, 0x35, 0x78,
+        ];
+        let reader = Reader::from(content);
+        let header = reader
+            .parse_block_in::<IndexHeader>(Offset::zero(), 25.into())
+            .unwrap();
+        assert_eq!(
+            header,
+            IndexHeader {
+                kind: IndexKind::Range {
+                    entry_offset: EntryIdx::from(2)
+                },
+                store_id: EntryStoreIdx::from(1),
+                entry_count: EntryCount::from(0xff00),
+                free_data: [0x00; 4].into(),
+                sort_keys: [
+                    IndexSortKey::new(PropertyIdx::from(1), SortOrder::Ascending),
+                    IndexSortKey::new(PropertyIdx::from(3), SortOrder::Descending)
+                ]
+                .into(),
+                name: "Hello".into()
+            }
+        );
+    }
+
+    #[rustest::test]
+    fn test_permutation_index() -> rustest::Result {
+        let content = vec![
+            0x03, 0x02, 0x00, 0x01, // permutation
+            0x82, 0x99, 0xB0, 0xD0, // crc
+            0x01, 0x00, 0x00, 0x00, // store_id
+            0x04, 0x00, 0x00, 0x00, // entry_count
+            0x00, 0x00, 0x00, 0x00, // entry_offset (unused)
+            0x00, 0x00, 0x00, 0x00, // free data
+            0x41, // kind (permutation) and sort key count
+            0x81, // sort key: property 1, descending
+            0x01, // idx_size
+            0x00, // PString ""
+            0x73, 0x09, 0xFE, 0x91,
+        ];
+        let reader = Reader::from(content);
+        let index = reader.parse_data_block::<Index>(SizedOffset::new(
+            ASize::from(20_usize),
+            Offset::from(8_u64),
+        ))?;
+        assert!(index.is_permutation());
+        assert_eq!(index.count(), EntryCount::from(4));
+        assert_eq!(index.as_range(), None);
+        let entries = (0..4)
+            .map(|i| index.entry_idx(EntryIdx::from(i)))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(entries, [3, 2, 0, 1].map(EntryIdx::from));
+
+        let sub_range = index.sub_range(EntryIdx::from(1), EntryIdx::from(3));
+        assert_eq!(sub_range.count(), EntryCount::from(2));
+        assert_eq!(sub_range.entry_idx(EntryIdx::from(0))?, EntryIdx::from(2));
+        assert_eq!(sub_range.entry_idx(EntryIdx::from(1))?, EntryIdx::from(0));
+        Ok(())
+    }
+
+    #[rustest::test]
+    fn test_hash_index() -> rustest::Result {
+        let content = vec![
+            0x00, 0x02, 0x01, 0x00, // slots
+            0x18, 0x73, 0x7C, 0xD1, // crc
+            0x01, 0x00, 0x00, 0x00, // store_id
+            0x02, 0x00, 0x00, 0x00, // entry_count
+            0x03, 0x00, 0x00, 0x00, // entry_offset
+            0x00, 0x00, 0x00, 0x00, // free data
+            0x80, // kind (hash) and sort key count
+            0x01, // key property
+            0x01, // slot_size
+            0x00, // PString ""
+            0x07, 0x88, 0x81, 0xA3,
+        ];
+        let reader = Reader::from(content);
+        let index = reader.parse_data_block::<Index>(SizedOffset::new(
+            ASize::from(20_usize),
+            Offset::from(8_u64),
+        ))?;
+        assert!(index.is_hash());
+        assert!(!index.is_permutation());
+        assert_eq!(index.offset(), EntryIdx::from(3));
+        assert_eq!(index.count(), EntryCount::from(2));
+        assert_eq!(
+            index.as_range(),
+            Some(EntryRange::new_from_size(
+                EntryIdx::from(3),
+                EntryCount::from(2)
+            ))
+        );
+        assert_eq!(index.entry_idx(EntryIdx::from(1))?, EntryIdx::from(4));
+        let table = index.hash_table.as_ref().unwrap();
+        assert_eq!(table.key_property, PropertyIdx::from(1));
+        assert_eq!(table.slot_count, 4);
+        let slots = (0..4).map(|s| table.get(s)).collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            slots,
+            [None, Some(EntryIdx::from(1)), Some(EntryIdx::from(0)), None]
+        );
+        Ok(())
+    }
 }
@@ -1,13 +1,22 @@
 use super::super::raw_layout::{PropertyKind, RawProperty};
 use super::property::Property;
 use super::SmallString;
-use crate::PropertyName;
+use crate::{PropertyIdx, PropertyName};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Debug)]
-#[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
-pub struct Properties(HashMap<SmallString, Property>);
+#[cfg_attr(
+    feature = "explorable_serde",
+    derive(serde::Serialize),
+    serde(transparent)
+)]
+pub struct Properties {
+    properties: HashMap<SmallString, Property>,
+    // The names of the properties, in layout order.
+    #[cfg_attr(feature = "explorable_serde", serde(skip))]
+    names: Box<[SmallString]>,
+}
 
 pub(crate) type SharedProperties = Arc<Properties>;
 
@@ -15,27 +24,37 @@ impl Properties {
     pub(crate) fn new(initial_offset: usize, raw_properties: Vec<RawProperty>) -> Self {
         let mut offset = initial_offset;
         let mut properties = HashMap::new();
+        let mut names = Vec::with_capacity(raw_properties.len());
         for raw_property in raw_properties {
             let property = Property::new(offset, raw_property.kind);
             offset += raw_property.size;
+            names.push(raw_property.name.clone());
             if property.kind != PropertyKind::Padding && property.kind != PropertyKind::VariantId {
                 properties.insert(raw_property.name, property);
             }
         }
-        Properties(properties)
+        Properties {
+            properties,
+            names: names.into(),
+        }
     }
 
     pub fn get(&self, name: impl PropertyName) -> Option<&Property> {
-        self.0.get(name.as_str())
+        self.properties.get(name.as_str())
+    }
+
+    /// Get the name of the property at position `idx` in the layout.
+    pub(crate) fn name_at(&self, idx: PropertyIdx) -> Option<&SmallString> {
+        self.names.get(idx.into_usize())
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&SmallString, &Property)> {
-        self.0.iter()
+        self.properties.iter()
     }
 
     #[cfg(test)]
     pub fn inner(&self) -> &HashMap<SmallString, Property> {
-        &self.0
+        &self.properties
     }
 }
 
@@ -43,13 +62,13 @@ impl Properties {
 impl graphex::Display for Properties {
     fn print_content(&self, out: &mut graphex::Output) -> graphex::Result {
         let mut keys = self
-            .0
+            .properties
             .iter()
             .map(|(k, v)| (v.offset, k))
             .collect::<Vec<_>>();
         keys.sort_unstable();
         for (_, key) in keys.iter() {
-            out.field(key, &self.0[*key])?;
+            out.field(key, &self.properties[*key])?;
         }
         Ok(())
     }
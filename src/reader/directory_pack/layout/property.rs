@@ -42,6 +42,10 @@ impl graphex::Display for Property {
             PropertyKind::DeportedUnsignedInt { .. } => "Deported Unsigned Int",
             PropertyKind::DeportedSignedInt { .. } => "Deported Signed Int",
             PropertyKind::Array { .. } => "Array",
+            PropertyKind::Range { .. } => "Range",
+            PropertyKind::Float { .. } => "Float",
+            PropertyKind::Bool { .. } => "Bool",
+            PropertyKind::FixedBytes { .. } => "Fixed Bytes",
             PropertyKind::VariantId => "Variant Id",
         };
         Some((format!("{header}("), ")".to_string()))
@@ -141,6 +145,48 @@ impl graphex::Display for Property {
                     out.field("deported_id", &value_id)?;
                 }
             },
+            PropertyKind::Range {
+                entry_idx_size,
+                entry_count_size,
+                default_count,
+            } => {
+                out.field("offset", &self.offset.into_u64())?;
+                out.field("entry_idx_size", &(entry_idx_size as usize))?;
+                match default_count {
+                    None => out.field("entry_count_size", &(entry_count_size as usize))?,
+                    Some(count) => out.field("default_count", &count.into_u64())?,
+                }
+            }
+            PropertyKind::Float {
+                float_size,
+                default,
+            } => match default {
+                None => {
+                    out.field("offset", &self.offset.into_u64())?;
+                    out.field("float_size", &(float_size as usize))?;
+                }
+                Some(float) => {
+                    out.field("default", &float.get())?;
+                }
+            },
+            PropertyKind::Bool { bit, default } => match default {
+                None => {
+                    out.field("offset", &self.offset.into_u64())?;
+                    out.field("bit", &bit)?;
+                }
+                Some(value) => {
+                    out.field("default", &value)?;
+                }
+            },
+            PropertyKind::FixedBytes { size, ref default } => match default {
+                None => {
+                    out.field("offset", &self.offset.into_u64())?;
+                    out.field("size", &size)?;
+                }
+                Some(bytes) => {
+                    out.field("default", &graphex::AsBytes(bytes))?;
+                }
+            },
             PropertyKind::VariantId => {
                 out.field("offset", &self.offset.into_u64())?;
             }
@@ -3,16 +3,16 @@ use super::raw_value::RawValue;
 use super::EntryTrait;
 use crate::bases::*;
 use crate::reader::ByteRegion;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// A lazy entry
 pub struct LazyEntry {
-    properties: Rc<LazyEntryProperties>,
+    properties: Arc<LazyEntryProperties>,
     bytes: ByteRegion,
 }
 
 impl LazyEntry {
-    pub(crate) fn new(properties: Rc<LazyEntryProperties>, bytes: ByteRegion) -> Self {
+    pub(crate) fn new(properties: Arc<LazyEntryProperties>, bytes: ByteRegion) -> Self {
         Self { properties, bytes }
     }
 
@@ -9,15 +9,16 @@ mod raw_layout;
 mod raw_value;
 mod value_store;
 
-use self::index::IndexHeader;
+use self::index::{IndexHeader, LegacyIndexHeader};
+use super::{BlockFailure, BlockKind};
 use crate::bases::*;
 use crate::common::{CheckInfo, DirectoryPackHeader, Pack, PackHeader, PackKind};
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
 pub use self::entry_store::EntryStore;
-pub use self::index::Index;
-pub use self::range::{CompareTrait, RangeTrait};
+pub use self::index::{Index, IndexRange};
+pub use self::range::{CompareTrait, RangeIter, RangeTrait};
 pub(crate) use self::value_store::{ValueStore, ValueStoreTrait};
 pub use lazy_entry::LazyEntry;
 pub use raw_value::RawValue;
@@ -112,23 +113,33 @@ impl DirectoryPack {
         self.header.free_data.as_ref()
     }
 
+    /// Parse the index header at `sized_offset`, using the layout of the pack version.
+    fn parse_index_header(&self, sized_offset: SizedOffset) -> Result<IndexHeader> {
+        if self.pack_header.minor_version < 3 {
+            self.reader
+                .parse_block_in::<LegacyIndexHeader>(sized_offset.offset, sized_offset.size)
+        } else {
+            self.reader
+                .parse_block_in::<IndexHeader>(sized_offset.offset, sized_offset.size)
+        }
+    }
+
+    fn parse_index(&self, sized_offset: SizedOffset) -> Result<Index> {
+        let index_header = self.parse_index_header(sized_offset)?;
+        Index::finalize(index_header, sized_offset.offset, &self.reader)
+    }
+
     pub fn get_index(&self, index_id: IndexIdx) -> Result<Index> {
         let sized_offset = self.index_ptrs.index(*index_id)?;
-        let index_header = self
-            .reader
-            .parse_block_in::<IndexHeader>(sized_offset.offset, sized_offset.size)?;
-        let index = Index::new(index_header);
-        Ok(index)
+        self.parse_index(sized_offset)
     }
 
     pub fn get_index_from_name(&self, index_name: &str) -> Result<Option<Index>> {
         for index_id in self.header.index_count {
             let sized_offset = self.index_ptrs.index(*index_id)?;
-            let index_header = self
-                .reader
-                .parse_block_in::<IndexHeader>(sized_offset.offset, sized_offset.size)?;
+            let index_header = self.parse_index_header(sized_offset)?;
             if index_header.name.as_str() == index_name {
-                let index = Index::new(index_header);
+                let index = Index::finalize(index_header, sized_offset.offset, &self.reader)?;
                 return Ok(Some(index));
             }
         }
@@ -142,6 +153,43 @@ impl DirectoryPack {
     pub fn create_entry_storage(self: &Arc<Self>) -> Arc<EntryStorage> {
         Arc::new(EntryStorage::new(Arc::clone(self)))
     }
+
+    /// Check all indexes, entry stores and value stores of the pack and return
+    /// the ones which cannot be read.
+    pub(crate) fn block_failures(&self) -> Result<Vec<BlockFailure>> {
+        let mut failures = vec![];
+        for index_id in self.header.index_count {
+            let sized_offset = self.index_ptrs.index(*index_id)?;
+            if let Err(e) = self.parse_index(sized_offset) {
+                failures.push(BlockFailure::new(BlockKind::Index, index_id.into_u32(), e)?);
+            }
+        }
+        for store_id in self.header.entry_store_count {
+            let sized_offset = self.entry_stores_ptrs.index(*store_id)?;
+            if let Err(e) = self
+                .reader
+                .parse_data_block::<EntryStore>(sized_offset)
+                .and_then(|store| store.check())
+            {
+                failures.push(BlockFailure::new(
+                    BlockKind::EntryStore,
+                    store_id.into_u32(),
+                    e,
+                )?);
+            }
+        }
+        for store_id in self.header.value_store_count {
+            let sized_offset = self.value_stores_ptrs.index(*store_id)?;
+            if let Err(e) = self.reader.parse_data_block::<ValueStore>(sized_offset) {
+                failures.push(BlockFailure::new(
+                    BlockKind::ValueStore,
+                    store_id.into_u32(),
+                    e,
+                )?);
+            }
+        }
+        Ok(failures)
+    }
 }
 
 impl CachableSource<ValueStore> for DirectoryPack {
@@ -202,17 +250,12 @@ impl Pack for DirectoryPack {
             let mut s_check_info = self.check_info.write().unwrap();
             *s_check_info = Some(check_info);
         }
-        let mut check_stream = self.reader.create_stream(
-            Offset::zero(),
-            Size::from(self.pack_header.check_info_pos),
-            false,
-        )?;
-        Ok(self
-            .check_info
+        self.check_info
             .read()
             .unwrap()
+            .as_ref()
             .unwrap()
-            .check(&mut check_stream)?)
+            .check_reader(&self.reader, Size::from(self.pack_header.check_info_pos))
     }
 }
 
@@ -233,11 +276,7 @@ impl serde::Serialize for DirectoryPack {
                 .into_iter()
                 .map(|c| {
                     let sized_offset = self.index_ptrs.index(*c).unwrap();
-                    let index_header = self
-                        .reader
-                        .parse_block_in::<IndexHeader>(sized_offset.offset, sized_offset.size)
-                        .unwrap();
-                    Index::new(index_header)
+                    self.parse_index(sized_offset).unwrap()
                 })
                 .collect::<Vec<_>>(),
         )?;
@@ -306,11 +345,7 @@ impl graphex::Display for DirectoryPack {
                 .into_iter()
                 .map(|c| {
                     let sized_offset = self.index_ptrs.index(*c).unwrap();
-                    let index_header = self
-                        .reader
-                        .parse_block_in::<IndexHeader>(sized_offset.offset, sized_offset.size)
-                        .unwrap();
-                    Index::new(index_header)
+                    self.parse_index(sized_offset).unwrap()
                 })
                 .collect::<Vec<_>>(),
         )?;
@@ -391,7 +426,7 @@ impl graphex::Node for DirectoryPack {
 mod tests {
     use super::raw_value::*;
     use super::*;
-    use crate::common::ContentAddress;
+    use crate::common::{ContentAddress, IndexSortKey, SortOrder};
 
     #[derive(Debug)]
     struct FakeArray {
@@ -739,4 +774,156 @@ mod tests {
         }
         Ok(())
     }
+
+    #[rustest::test]
+    fn test_directorypack_sorted_index() -> rustest::Result {
+        // Same pack than `test_directorypack` but in version 0.3, with a index sorted on `C`.
+        // Pack header offset 0/0x00
+        let mut content = vec![
+            0x6a, 0x62, 0x6b, 0x64, // magic
+            0x00, 0x00, 0x00, 0x01, // app_vendor_id
+            0x00, // major_version
+            0x03, // minor_version
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f, // uuid
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // padding
+            0xAF, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // file_size
+            0x4A, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // check_info_pos
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // reserved
+            0x00, 0x00, 0x00, 0x00, // reserved
+        ];
+        content.extend_from_slice(&[0x28, 0xC7, 0x94, 0x5E]); // CRC
+
+        // Directory pack header offset 64/0x40
+        content.extend_from_slice(&[
+            0x3E, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // index_ptr_pos
+            0x13, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // entry_store_ptr_pos
+            0xA4, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // value_store_ptr_pos
+            0x01, 0x00, 0x00, 0x00, // index count
+            0x01, 0x00, 0x00, 0x00, // entry_store count
+            0x01, //value_store count
+        ]);
+        content.extend_from_slice(&[0xff; 27]); // free data
+        content.extend_from_slice(&[0x34, 0x01, 0x08, 0xD0]); // CRC
+
+        // Value Store data. Offset 128/0x80
+        content.extend_from_slice(&[
+            b'H', b'e', b'l', b'l', b'o', // value 0
+            b'F', b'o', b'o', // value 1
+            b'J', 0xc5, 0xab, b'b', b'a', b'k', b'o', // value 2
+        ]);
+        content.extend_from_slice(&[0x71, 0x51, 0xDF, 0x1D]); // CRC
+
+        // Value store header. Offset 128 + 15 + 4 = 147/0x93
+        content.extend_from_slice(&[
+            0x01, // kind
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // value count
+            0x01, // offset_size
+            0x0f, // data_size
+            0x05, // Offset of entry 1
+            0x08, // Offset of entry 2
+        ]);
+        content.extend_from_slice(&[0x1E, 0x6E, 0xE7, 0xB7]); // CRC
+
+        // Value store ptr. Offset 147 + 13 + 4 = 164/0xA4 (value_store_ptr_pos)
+        content.extend_from_slice(&[
+            13, 0x00, //size
+            0x93, 0x00, 0x00, 0x00, 0x00, 0x00, // Offset the tailler (147/0x93)
+        ]);
+        content.extend_from_slice(&[0xE0, 0x14, 0x59, 0xCA]); // CRC
+
+        // Entry store data. Offset 164+8+4=176/0xB0
+        #[rustfmt::skip]
+        content.extend_from_slice(&[
+            0x05, 0x00, 0x05, b'A', b'B', 0x01, 0x13, 0x12, 0x11, 0x00, 0x00, 0x00, 0x00, // Entry 0
+            0x07, 0x02, 0x07, b'a', b'B', 0x00, 0x23, 0x22, 0x21, 0x01, 0x00, 0x00, 0x00, // Entry 1
+            0x03, 0x01, 0x09, b'A', b'B', 0x02, 0x33, 0x32, 0x31, 0x00, 0x01, 0x00, 0x00, // Entry 2
+            0x07, 0x02, 0x05, b'A', b'B', 0x01, 0x43, 0x42, 0x41, 0x00, 0x02, 0x00, 0x00, // Entry 3
+            0x05, 0x00, 0x05, 0x00, 0x00, 0x01, 0x53, 0x52, 0x51, 0x00, 0xaa, 0xaa, 0xaa, // Entry 4
+        ]);
+        content.extend_from_slice(&[0x4C, 0x67, 0x87, 0x9B]); // CRC
+
+        // Entry store header Offset 176 + (13*5) + 4 = 245/0xF5
+        #[rustfmt::skip]
+        content.extend_from_slice(&[
+            0x00, // kind
+            0x05, 0x00, 0x00, 0x00, // entry_count (5)
+            0x00, // flag
+            0x0D, 0x00, // entry size (13)
+            0x00, // variant count
+            0x04, // value count
+            0b0101_0001, 0b001_00000, 0x00, 1, b'A', // Char1[0] + deported 1, idx 0x00
+            0b0101_0001, 0b001_00010, 0x00, 1, b'B', // Char1[2] + deported 1, idx 0x00
+            0b0010_0010, 1, b'C', // u24
+            0b0001_0010, 1, b'D', // content address
+        ]);
+        content.extend_from_slice(&[0x49, 0x82, 0x74, 0xD6]); // CRC
+
+        // Entry store array. Offset 245 + 26 + 4 = 275/0x113 (entry_store_ptr_pos)
+        content.extend_from_slice(&[
+            26, 0x00, // size
+            0xF5, 0x00, 0x00, 0x00, 0x00, 0x00, // offset of the tailler (245)
+        ]);
+        content.extend_from_slice(&[0x03, 0x73, 0x94, 0x0B]); // CRC
+
+        // Index. Offset 275+8+4=287/0x11F)
+        content.extend_from_slice(&[
+            0x00, 0x00, 0x00, 0x00, // store_id
+            0x04, 0x00, 0x00, 0x00, // entry_count (use only 4 from the 5 available)
+            0x01, 0x00, 0x00, 0x00, // entry offset (skip the first one)
+            0x00, 0x00, 0x00, 0x00, // free_data
+            0x01, // kind (range) and sort key count
+            0x02, // sort key: property 2 (C), ascending
+            0x08, b'm', b'y', b' ', b'i', b'n', b'd', b'e', b'x', // Pstring "my index"
+        ]);
+        content.extend_from_slice(&[0xEC, 0xB8, 0x3C, 0xA8]); // CRC
+
+        // Add a index_ptr (offset 287+27+4=318/0x13E) (index_ptr_pos)
+        content.extend_from_slice(&[
+            27, 0x00, //size (27)
+            0x1F, 0x01, 0x00, 0x00, 0x00, 0x00, // offset
+        ]);
+        content.extend_from_slice(&[0x01, 0x58, 0xD3, 0x43]); // CRC
+
+        // Check Info. Offset 318 + 8 + 4 = 330/0x14A (check_info_pos)
+        let hash = blake3::hash(&content);
+        content.push(0x01); // check info
+        content.extend(hash.as_bytes());
+        content.extend_from_slice(&[0x88, 0xFD, 0xF6, 0xCD]); // CRC
+
+        // Footer offset 330 + 33 + 4 = 367/0x16F
+        let mut footer = [0; 64];
+        footer.copy_from_slice(&content[..64]);
+        footer.reverse();
+        content.extend_from_slice(&footer);
+
+        // File size 367 + 64 = 431/0x1AF (file_size)
+
+        let directory_pack = Arc::new(DirectoryPack::new(content.into())?);
+        assert!(directory_pack.check()?);
+        let index = directory_pack.get_index(0.into())?;
+        assert_eq!(
+            index.sort_keys(),
+            &[IndexSortKey::new(
+                PropertyIdx::from(2),
+                SortOrder::Ascending
+            )]
+        );
+        let value_storage = directory_pack.create_value_storage();
+        let entry_storage = directory_pack.create_entry_storage();
+        let builder =
+            builder::AnyBuilder::new(index.get_store(&entry_storage)?, value_storage.as_ref())?;
+        assert_eq!(index.count(), 4.into());
+        let values = (0..4)
+            .map(|idx| {
+                let entry = index.get_entry(&builder, idx.into())?.unwrap();
+                entry.get_value("C")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            values,
+            [0x212223, 0x313233, 0x414243, 0x515253].map(|v| Some(RawValue::U32(v)))
+        );
+        Ok(())
+    }
 }
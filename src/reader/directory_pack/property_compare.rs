@@ -2,14 +2,17 @@ use super::builder::{AnyBuilder, BuilderTrait};
 use super::range::CompareTrait;
 use super::EntryTrait;
 use crate::bases::*;
-use crate::common::Value;
+use crate::common::{IndexSortKey, SortOrder, Value};
 use std::cmp::Ordering;
 
 pub struct PropertyCompare<'builder> {
     builder: &'builder AnyBuilder,
     property_names: Box<[String]>,
     values: Box<[Value]>,
-    ordered: bool,
+    // The sort orders of the properties if the entries are sorted on them.
+    orders: Option<Box<[SortOrder]>>,
+    // The last value is a prefix of the entry value.
+    prefix: bool,
 }
 
 impl<'builder> PropertyCompare<'builder> {
@@ -19,11 +22,26 @@ impl<'builder> PropertyCompare<'builder> {
         values: Vec<Value>,
     ) -> Self {
         assert_eq!(property_names.len(), values.len());
+        let orders = builder.sort_orders(&property_names);
         Self {
             builder,
             property_names: property_names.into(),
             values: values.into(),
-            ordered: false,
+            orders,
+            prefix: false,
+        }
+    }
+
+    /// Create a PropertyCompare where the last value is a prefix of the entry value.
+    pub(crate) fn new_prefix(
+        builder: &'builder AnyBuilder,
+        property_names: Vec<String>,
+        values: Vec<Value>,
+    ) -> Self {
+        assert!(matches!(values.last(), Some(Value::Array(_))));
+        Self {
+            prefix: true,
+            ..Self::new(builder, property_names, values)
         }
     }
 }
@@ -34,20 +52,41 @@ impl CompareTrait for PropertyCompare<'_> {
             .builder
             .create_entry(idx)?
             .expect("idx is valid as `compare_entry` is piloted by a range looping on its entries");
-        for (name, value) in std::iter::zip(self.property_names.iter(), self.values.iter()) {
-            let ordering = entry
-                .get_value(name)?
-                .expect("Name should be in the entry")
-                .partial_cmp(value)?
-                .expect("Value in the entry correspond to reference value");
+        for (idx, (name, value)) in
+            std::iter::zip(self.property_names.iter(), self.values.iter()).enumerate()
+        {
+            let entry_value = entry.get_value(name)?.expect("Name should be in the entry");
+            let ordering = match value {
+                Value::Array(prefix) if self.prefix && idx == self.values.len() - 1 => {
+                    entry_value.partial_cmp_prefix(prefix)?
+                }
+                _ => entry_value.partial_cmp(value)?,
+            }
+            .expect("Value in the entry correspond to reference value");
             if ordering.is_ne() {
-                return Ok(ordering);
+                return Ok(match &self.orders {
+                    Some(orders) => orders[idx].apply(ordering),
+                    None => ordering,
+                });
             }
         }
         Ok(Ordering::Equal)
     }
 
     fn ordered(&self) -> bool {
-        self.ordered
+        self.orders.is_some()
+    }
+
+    fn ordered_on(&self, sort_keys: &[IndexSortKey]) -> Option<Self> {
+        let orders = self
+            .builder
+            .sort_orders_on(&self.property_names, sort_keys)?;
+        Some(Self {
+            builder: self.builder,
+            property_names: self.property_names.clone(),
+            values: self.values.clone(),
+            orders: Some(orders),
+            prefix: self.prefix,
+        })
     }
 }
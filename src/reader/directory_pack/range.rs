@@ -1,73 +1,342 @@
 use super::builder::BuilderTrait;
 use crate::bases::*;
+use crate::common::IndexSortKey;
+use rayon::prelude::*;
 use std::cmp::Ordering;
+use std::iter::FusedIterator;
 
 pub trait CompareTrait {
     fn ordered(&self) -> bool;
     fn compare_entry(&self, idx: EntryIdx) -> Result<Ordering>;
+
+    /// Get a ordered comparator, comparing as `self` entries sorted on `sort_keys`.
+    ///
+    /// Sorted ranges (as indexes) use it to search with a binary search.
+    /// Return `None` if `self` cannot be ordered on `sort_keys` (default implementation).
+    fn ordered_on(&self, _sort_keys: &[IndexSortKey]) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 pub trait RangeTrait {
+    /// The type of the sub ranges of the range.
+    type SubRange: RangeTrait;
+
     fn count(&self) -> EntryCount;
     fn offset(&self) -> EntryIdx;
 
-    fn get_entry<Builder: BuilderTrait>(
+    /// Get the index in the entry store of the entry `idx` of the range.
+    ///
+    /// Default implementation is for ranges contiguous in the entry store.
+    fn entry_idx(&self, idx: EntryIdx) -> Result<EntryIdx> {
+        Ok(self.offset() + idx)
+    }
+
+    /// Get the sub range of the entries `begin..end` of the range.
+    fn sub_range(&self, begin: EntryIdx, end: EntryIdx) -> Self::SubRange;
+
+    /// The sort keys on which the entries of the range are sorted.
+    ///
+    /// Default implementation is for unsorted ranges.
+    fn sort_keys(&self) -> &[IndexSortKey] {
+        &[]
+    }
+
+    fn get_entry<Builder>(
         &self,
         builder: &Builder,
         id: EntryIdx,
-    ) -> std::result::Result<Option<Builder::Entry>, Builder::Error> {
+    ) -> std::result::Result<Option<Builder::Entry>, Builder::Error>
+    where
+        Builder: BuilderTrait,
+        Builder::Error: From<Error>,
+    {
         if id.is_valid(*self.count()) {
-            builder.create_entry(self.offset() + id)
+            builder.create_entry(self.entry_idx(id)?)
         } else {
             Ok(None)
         }
     }
 
+    /// Find a entry matching the searched value.
+    ///
+    /// Search is a binary search if the comparator is ordered (or can be ordered
+    /// on the sort keys of the range), a linear scan otherwise.
     fn find<Comparator: CompareTrait>(&self, comparator: &Comparator) -> Result<Option<EntryIdx>> {
-        if comparator.ordered() {
-            // INVARIANTS:
-            // - 0 <= left <= left + size = right <= self.count()
-            // - comparator returns Less for everything in self[..left]
-            // - comparator returns Greater for everything in self[right..]
-            let mut size = self.count();
-            let mut left = EntryIdx::from(0);
-            let mut right = left + size;
-            while left < right {
-                let mid = left + size / 2;
-
-                // SAFETY: the while condition means `size` is strictly positive, so
-                // `size/2 < size`. Thus `left + size/2 < left + size`, which
-                // coupled with the `left + size <= self.len()` invariant means
-                // we have `left + size/2 < self.len()`, and this is in-bounds.
-                let cmp = comparator.compare_entry(self.offset() + mid)?;
-
-                // The reason why we use if/else control flow rather than match
-                // is because match reorders comparison operations, which is perf sensitive.
-                // This is x86 asm for u8: https://rust.godbolt.org/z/8Y8Pra.
-                if cmp == Ordering::Less {
-                    left = mid + EntryCount::from(1);
-                } else if cmp == Ordering::Greater {
-                    right = mid;
-                } else {
-                    return Ok(Some(mid));
-                }
+        let ordered_comparator = comparator.ordered_on(self.sort_keys());
+        find(self, ordered_comparator.as_ref().unwrap_or(comparator))
+    }
+
+    /// Get the index of the first entry which is not lower than the searched value.
+    ///
+    /// Return `self.count()` if all entries are lower.
+    /// Comparator must be ordered, or orderable on the sort keys of the range.
+    fn lower_bound<Comparator: CompareTrait>(&self, comparator: &Comparator) -> Result<EntryIdx> {
+        let ordered_comparator = comparator.ordered_on(self.sort_keys());
+        let comparator = ordered_comparator.as_ref().unwrap_or(comparator);
+        partition_point(self, comparator, EntryIdx::from(0), Ordering::is_lt)
+    }
+
+    /// Get the index of the first entry which is greater than the searched value.
+    ///
+    /// Return `self.count()` if no entry is greater.
+    /// Comparator must be ordered, or orderable on the sort keys of the range.
+    fn upper_bound<Comparator: CompareTrait>(&self, comparator: &Comparator) -> Result<EntryIdx> {
+        let ordered_comparator = comparator.ordered_on(self.sort_keys());
+        let comparator = ordered_comparator.as_ref().unwrap_or(comparator);
+        partition_point(self, comparator, EntryIdx::from(0), Ordering::is_le)
+    }
+
+    /// Get the range of the entries equal to the searched value.
+    ///
+    /// The returned range is a sub range of `self` and can be used to access the found entries.
+    /// Comparator must be ordered, or orderable on the sort keys of the range.
+    fn equal_range<Comparator: CompareTrait>(
+        &self,
+        comparator: &Comparator,
+    ) -> Result<Self::SubRange> {
+        let ordered_comparator = comparator.ordered_on(self.sort_keys());
+        let comparator = ordered_comparator.as_ref().unwrap_or(comparator);
+        let begin = partition_point(self, comparator, EntryIdx::from(0), Ordering::is_lt)?;
+        let end = partition_point(self, comparator, begin, Ordering::is_le)?;
+        Ok(self.sub_range(begin, end))
+    }
+
+    /// Iterate on the entries of the range.
+    ///
+    /// `skip`, `step_by` and `nth` jump directly to the wanted index,
+    /// skipped entries are not built.
+    fn iter<'a, Builder>(&'a self, builder: &'a Builder) -> RangeIter<'a, Self, Builder>
+    where
+        Self: Sized,
+        Builder: BuilderTrait,
+        Builder::Error: From<Error>,
+    {
+        RangeIter::new(self, builder)
+    }
+
+    /// Iterate in parallel on the entries of the range.
+    fn par_iter<'a, Builder>(
+        &'a self,
+        builder: &'a Builder,
+    ) -> impl IndexedParallelIterator<Item = std::result::Result<Builder::Entry, Builder::Error>> + 'a
+    where
+        Self: Sync,
+        Builder: BuilderTrait + Sync,
+        Builder::Entry: Send,
+        Builder::Error: From<Error> + Send,
+    {
+        (0..self.count().into_u32())
+            .into_par_iter()
+            .map(move |idx| build_entry(self, builder, EntryIdx::from(idx)))
+    }
+}
+
+fn build_entry<Range, Builder>(
+    range: &Range,
+    builder: &Builder,
+    idx: EntryIdx,
+) -> std::result::Result<Builder::Entry, Builder::Error>
+where
+    Range: RangeTrait + ?Sized,
+    Builder: BuilderTrait,
+    Builder::Error: From<Error>,
+{
+    let idx = range.entry_idx(idx)?;
+    match builder.create_entry(idx)? {
+        Some(entry) => Ok(entry),
+        None => {
+            let error: Error = format_error!(format!("Entry {idx} is out of the entry store"));
+            Err(error.into())
+        }
+    }
+}
+
+/// An iterator on the entries of a range.
+///
+/// Created by [`RangeTrait::iter`].
+pub struct RangeIter<'a, Range: RangeTrait, Builder: BuilderTrait> {
+    range: &'a Range,
+    builder: &'a Builder,
+    // Remaining entries are `front..back`.
+    front: u32,
+    back: u32,
+}
 
-                size = right - left;
+impl<'a, Range: RangeTrait, Builder: BuilderTrait> RangeIter<'a, Range, Builder> {
+    fn new(range: &'a Range, builder: &'a Builder) -> Self {
+        Self {
+            range,
+            builder,
+            front: 0,
+            back: range.count().into_u32(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        (self.back - self.front) as usize
+    }
+}
+
+impl<Range, Builder> Iterator for RangeIter<'_, Range, Builder>
+where
+    Range: RangeTrait,
+    Builder: BuilderTrait,
+    Builder::Error: From<Error>,
+{
+    type Item = std::result::Result<Builder::Entry, Builder::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let idx = EntryIdx::from(self.front);
+        self.front += 1;
+        Some(build_entry(self.range, self.builder, idx))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.front = self.back;
+            return None;
+        }
+        self.front += n as u32;
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<Range, Builder> DoubleEndedIterator for RangeIter<'_, Range, Builder>
+where
+    Range: RangeTrait,
+    Builder: BuilderTrait,
+    Builder::Error: From<Error>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        let idx = EntryIdx::from(self.back);
+        Some(build_entry(self.range, self.builder, idx))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.back = self.front;
+            return None;
+        }
+        self.back -= n as u32;
+        self.next_back()
+    }
+}
+
+impl<Range, Builder> ExactSizeIterator for RangeIter<'_, Range, Builder>
+where
+    Range: RangeTrait,
+    Builder: BuilderTrait,
+    Builder::Error: From<Error>,
+{
+}
+
+impl<Range, Builder> FusedIterator for RangeIter<'_, Range, Builder>
+where
+    Range: RangeTrait,
+    Builder: BuilderTrait,
+    Builder::Error: From<Error>,
+{
+}
+
+/// Find a entry of `range` matching `comparator`.
+fn find<Range, Comparator>(range: &Range, comparator: &Comparator) -> Result<Option<EntryIdx>>
+where
+    Range: RangeTrait + ?Sized,
+    Comparator: CompareTrait,
+{
+    if comparator.ordered() {
+        // INVARIANTS:
+        // - 0 <= left <= left + size = right <= range.count()
+        // - comparator returns Less for everything in self[..left]
+        // - comparator returns Greater for everything in self[right..]
+        let mut size = range.count();
+        let mut left = EntryIdx::from(0);
+        let mut right = left + size;
+        while left < right {
+            let mid = left + size / 2;
+
+            // SAFETY: the while condition means `size` is strictly positive, so
+            // `size/2 < size`. Thus `left + size/2 < left + size`, which
+            // coupled with the `left + size <= range.len()` invariant means
+            // we have `left + size/2 < range.len()`, and this is in-bounds.
+            let cmp = comparator.compare_entry(range.entry_idx(mid)?)?;
+
+            // The reason why we use if/else control flow rather than match
+            // is because match reorders comparison operations, which is perf sensitive.
+            // This is x86 asm for u8: https://rust.godbolt.org/z/8Y8Pra.
+            if cmp == Ordering::Less {
+                left = mid + EntryCount::from(1);
+            } else if cmp == Ordering::Greater {
+                right = mid;
+            } else {
+                return Ok(Some(mid));
             }
-            Ok(None)
-        } else {
-            for idx in self.count() {
-                let cmp = comparator.compare_entry(self.offset() + idx)?;
-                if cmp.is_eq() {
-                    return Ok(Some(idx));
-                }
+
+            size = right - left;
+        }
+        Ok(None)
+    } else {
+        for idx in range.count() {
+            let cmp = comparator.compare_entry(range.entry_idx(idx)?)?;
+            if cmp.is_eq() {
+                return Ok(Some(idx));
             }
-            Ok(None)
+        }
+        Ok(None)
+    }
+}
+
+/// Binary search of the first entry of `range` (starting at `left`) for which `is_before` is false.
+///
+/// `is_before` must be true for all entries before this point and false for all entries after.
+fn partition_point<Range, Comparator>(
+    range: &Range,
+    comparator: &Comparator,
+    mut left: EntryIdx,
+    is_before: impl Fn(Ordering) -> bool,
+) -> Result<EntryIdx>
+where
+    Range: RangeTrait + ?Sized,
+    Comparator: CompareTrait,
+{
+    assert!(
+        comparator.ordered(),
+        "Bounds can be searched only with a ordered comparator"
+    );
+    let mut right = EntryIdx::from(0) + range.count();
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if is_before(comparator.compare_entry(range.entry_idx(mid)?)?) {
+            left = mid + EntryCount::from(1);
+        } else {
+            right = mid;
         }
     }
+    Ok(left)
 }
 
 impl RangeTrait for EntryRange {
+    type SubRange = Self;
+
     fn count(&self) -> EntryCount {
         self.size()
     }
@@ -75,6 +344,10 @@ impl RangeTrait for EntryRange {
     fn offset(&self) -> EntryIdx {
         self.begin()
     }
+
+    fn sub_range(&self, begin: EntryIdx, end: EntryIdx) -> Self {
+        Self::new(self.begin() + begin, self.begin() + end)
+    }
 }
 
 #[cfg(test)]
@@ -91,7 +364,7 @@ mod tests {
             v: RawValue,
         }
         impl Entry {
-            fn new(v: u16) -> Self {
+            pub fn new(v: u16) -> Self {
                 let v = RawValue::U16(v);
                 Self { v }
             }
@@ -130,6 +403,18 @@ mod tests {
             }
         }
 
+        // Each value is stored in two consecutive entries.
+        pub struct DuplicateCompare(pub u32);
+
+        impl CompareTrait for DuplicateCompare {
+            fn compare_entry(&self, index: EntryIdx) -> Result<Ordering> {
+                Ok((index.into_u32() / 2).cmp(&self.0))
+            }
+            fn ordered(&self) -> bool {
+                true
+            }
+        }
+
         pub struct Builder {}
         impl builder::BuilderTrait for Builder {
             type Entry = Entry;
@@ -190,4 +475,106 @@ mod tests {
         assert_eq!(result, None);
         Ok(())
     }
+
+    #[rustest::test]
+    fn test_bounds() -> rustest::Result {
+        let range = EntryRange::new_from_size(EntryIdx::from(10), EntryCount::from(10));
+        // Entries 10..20 have values 5,5,6,6,7,7,8,8,9,9
+        for value in 5..10 {
+            let comparator = mock::DuplicateCompare(value);
+            let lower = EntryIdx::from((value - 5) * 2);
+            assert_eq!(range.lower_bound(&comparator)?, lower);
+            assert_eq!(range.upper_bound(&comparator)?, lower + EntryCount::from(2));
+            assert_eq!(
+                range.equal_range(&comparator)?,
+                EntryRange::new_from_size(EntryIdx::from(value * 2), EntryCount::from(2))
+            );
+        }
+
+        let comparator = mock::DuplicateCompare(2);
+        assert_eq!(range.lower_bound(&comparator)?, EntryIdx::from(0));
+        assert_eq!(range.upper_bound(&comparator)?, EntryIdx::from(0));
+        assert_eq!(
+            range.equal_range(&comparator)?,
+            EntryRange::new_from_size(EntryIdx::from(10), EntryCount::from(0))
+        );
+
+        let comparator = mock::DuplicateCompare(12);
+        assert_eq!(range.lower_bound(&comparator)?, EntryIdx::from(10));
+        assert_eq!(range.upper_bound(&comparator)?, EntryIdx::from(10));
+        assert_eq!(range.equal_range(&comparator)?.size(), EntryCount::from(0));
+        Ok(())
+    }
+
+    fn values(entries: impl Iterator<Item = Result<mock::Entry>>) -> Vec<u64> {
+        entries
+            .map(|e| e.unwrap().get_value("foo").unwrap().unwrap().as_unsigned())
+            .collect()
+    }
+
+    #[rustest::test]
+    fn test_iter() {
+        let builder = mock::Builder {};
+        let range = EntryRange::new_from_size(EntryIdx::from(5), EntryCount::from(10));
+
+        let iter = range.iter(&builder);
+        assert_eq!(iter.len(), 10);
+        assert_eq!(values(iter), (5..15).collect::<Vec<_>>());
+        assert_eq!(
+            values(range.iter(&builder).rev()),
+            (5..15).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(values(range.iter(&builder).skip(7)), vec![12, 13, 14]);
+        assert_eq!(values(range.iter(&builder).step_by(4)), vec![5, 9, 13]);
+        assert_eq!(
+            values(range.iter(&builder).rev().step_by(4)),
+            vec![14, 10, 6]
+        );
+
+        let mut iter = range.iter(&builder);
+        assert_eq!(values(iter.nth(2).into_iter()), vec![7]);
+        assert_eq!(values(iter.nth_back(2).into_iter()), vec![12]);
+        assert_eq!(iter.len(), 4);
+        assert!(iter.nth(4).is_none());
+        assert!(iter.next().is_none());
+
+        let empty = EntryRange::new_from_size(EntryIdx::from(5), EntryCount::from(0));
+        assert!(empty.iter(&builder).next().is_none());
+    }
+
+    #[rustest::test]
+    fn test_par_iter() {
+        let builder = mock::Builder {};
+        let range = EntryRange::new_from_size(EntryIdx::from(5), EntryCount::from(100));
+        let values: Vec<_> = range
+            .par_iter(&builder)
+            .map(|e| e.unwrap().get_value("foo").unwrap().unwrap().as_unsigned())
+            .collect();
+        assert_eq!(values, (5..105).collect::<Vec<_>>());
+    }
+
+    #[rustest::test]
+    fn test_iter_missing_entry() {
+        struct Builder;
+        impl builder::BuilderTrait for Builder {
+            type Entry = mock::Entry;
+            type Error = Error;
+            fn create_entry(&self, idx: EntryIdx) -> Result<Option<Self::Entry>> {
+                Ok((idx.into_u32() < 3).then(|| mock::Entry::new(idx.into_u32() as u16)))
+            }
+        }
+        let range = EntryRange::new_from_size(EntryIdx::from(0), EntryCount::from(5));
+        let entries: Vec<_> = range.iter(&Builder).collect();
+        assert_eq!(entries.len(), 5);
+        assert!(entries[..3].iter().all(|e| e.is_ok()));
+        assert!(entries[3..].iter().all(|e| e.is_err()));
+    }
+
+    #[rustest::test]
+    #[xfail]
+    fn test_bounds_not_ordered() {
+        let range = EntryRange::new_from_size(EntryIdx::from(0), EntryCount::from(10));
+        let comparator = mock::EntryCompare::new(5, false);
+        let _ = range.lower_bound(&comparator);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::bases::*;
+use crate::common::Float;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "explorable_serde", derive(serde::Serialize))]
@@ -58,6 +59,23 @@ pub(crate) enum PropertyKind {
         deported_info: Option<DeportedInfo>,
         default: Option<(ASize, BaseArray, Option<u64>)>,
     },
+    Range {
+        entry_idx_size: ByteSize,
+        entry_count_size: ByteSize,
+        default_count: Option<EntryCount>,
+    },
+    Float {
+        float_size: ByteSize,
+        default: Option<Float>,
+    },
+    Bool {
+        bit: u8,
+        default: Option<bool>,
+    },
+    FixedBytes {
+        size: u16,
+        default: Option<FixedBytes>,
+    },
     VariantId,
 }
 
@@ -218,6 +236,101 @@ impl Parsable for RawProperty {
                     )
                 }
             }
+            PropType::Range => {
+                let default_count = (propdata & 0b1000) != 0;
+                let entry_idx_size = ByteSize::try_from((propdata & 0b0011) as usize + 1).unwrap();
+                let entry_count_size =
+                    ByteSize::try_from((parser.read_u8()? & 0b0011) as usize + 1).unwrap();
+                let default_count = if default_count {
+                    Some(EntryCount::from(
+                        parser.read_usized(entry_count_size)? as u32
+                    ))
+                } else {
+                    None
+                };
+                (
+                    entry_idx_size as u16
+                        + if default_count.is_some() {
+                            0
+                        } else {
+                            entry_count_size as u16
+                        },
+                    PropertyKind::Range {
+                        entry_idx_size,
+                        entry_count_size,
+                        default_count,
+                    },
+                    PString::parse(parser)?,
+                )
+            }
+            PropType::Float => {
+                let float_size = match propdata & 0x07 {
+                    0b011 => ByteSize::U4,
+                    0b111 => ByteSize::U8,
+                    s => {
+                        return Err(format_error!(
+                            format!("Invalid float size ({})", s + 1),
+                            parser
+                        ))
+                    }
+                };
+                let default = if (propdata & 0b1000) != 0 {
+                    Some(match float_size {
+                        ByteSize::U4 => Float::from(f32::from_bits(parser.read_u32()?)),
+                        _ => Float::from(f64::from_bits(parser.read_u64()?)),
+                    })
+                } else {
+                    None
+                };
+                (
+                    if default.is_some() {
+                        0
+                    } else {
+                        float_size as u16
+                    },
+                    PropertyKind::Float {
+                        float_size,
+                        default,
+                    },
+                    PString::parse(parser)?,
+                )
+            }
+            PropType::Bool => {
+                if (propdata & 0b1000) != 0 {
+                    (
+                        0,
+                        PropertyKind::Bool {
+                            bit: 0,
+                            default: Some((propdata & 0b0001) != 0),
+                        },
+                        PString::parse(parser)?,
+                    )
+                } else {
+                    // The last bool property stored in a byte "closes" it and owns the byte.
+                    let closing = parser.read_u8()? & 0b0000_0001;
+                    (
+                        closing as u16,
+                        PropertyKind::Bool {
+                            bit: propdata & 0b0111,
+                            default: None,
+                        },
+                        PString::parse(parser)?,
+                    )
+                }
+            }
+            PropType::FixedBytes => {
+                let size = parser.read_u8()? as u16 + 1;
+                let default = if (propdata & 0b1000) != 0 {
+                    Some(FixedBytes::parse(size as usize, parser)?)
+                } else {
+                    None
+                };
+                (
+                    if default.is_some() { 0 } else { size },
+                    PropertyKind::FixedBytes { size, default },
+                    PString::parse(parser)?,
+                )
+            }
             PropType::VariantId => (1, PropertyKind::VariantId, PString::parse(parser)?),
             PropType::DeportedUnsignedInt | PropType::DeportedSignedInt => {
                 let default_value = (propdata & 0b1000) != 0;
@@ -293,10 +406,22 @@ impl Parsable for RawLayout {
     fn parse(parser: &mut impl Parser) -> Result<Self> {
         let property_count: PropertyCount = Count::<u8>::parse(parser)?.into();
         let mut properties = Vec::with_capacity(property_count.into_usize());
+        // Bool properties sharing a byte must be followed by the bool property closing it.
+        let mut open_bool_byte = false;
         for _ in property_count {
             let property = RawProperty::parse(parser)?;
+            match property.kind {
+                PropertyKind::Bool { default: None, .. } => open_bool_byte = property.size == 0,
+                _ if property.size != 0 && open_bool_byte => {
+                    return Err(format_error!("Bool property byte is not closed", parser));
+                }
+                _ => {}
+            }
             properties.push(property);
         }
+        if open_bool_byte {
+            return Err(format_error!("Bool property byte is not closed", parser));
+        }
         Ok(Self(properties))
     }
 }
@@ -991,6 +1116,195 @@ mod tests {
                     "a",
                 ),
             ),
+            // Range
+            TestLayout(
+                "Range[1+1]",
+                &[0b0100_0000, 0b0000_0000, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Range {
+                        entry_idx_size: ByteSize::U1,
+                        entry_count_size: ByteSize::U1,
+                        default_count: None,
+                    },
+                    1 + 1,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "Range[4+2]",
+                &[0b0100_0011, 0b0000_0001, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Range {
+                        entry_idx_size: ByteSize::U4,
+                        entry_count_size: ByteSize::U2,
+                        default_count: None,
+                    },
+                    4 + 2,
+                    "a",
+                ),
+            ),
+            // Range with default count (redirection)
+            TestLayout(
+                "RangeDefault[3+1]",
+                &[0b0100_1010, 0b0000_0000, 0x01, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Range {
+                        entry_idx_size: ByteSize::U3,
+                        entry_count_size: ByteSize::U1,
+                        default_count: Some(1.into()),
+                    },
+                    3,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "RangeDefault[2+3]",
+                &[0b0100_1001, 0b0000_0010, 0x03, 0x02, 0x01, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Range {
+                        entry_idx_size: ByteSize::U2,
+                        entry_count_size: ByteSize::U3,
+                        default_count: Some(0x010203.into()),
+                    },
+                    2,
+                    "a",
+                ),
+            ),
+            // Float
+            TestLayout(
+                "Float[4]",
+                &[0b0110_0011, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Float {
+                        float_size: ByteSize::U4,
+                        default: None,
+                    },
+                    4,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "Float[8]",
+                &[0b0110_0111, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Float {
+                        float_size: ByteSize::U8,
+                        default: None,
+                    },
+                    8,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "FloatDefault[4]",
+                &[0b0110_1011, 0x00, 0x00, 0xC0, 0x3F, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Float {
+                        float_size: ByteSize::U4,
+                        default: Some(Float::from(1.5)),
+                    },
+                    0,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "FloatDefault[8]",
+                &[0b0110_1111, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Float {
+                        float_size: ByteSize::U8,
+                        default: Some(Float::from(-2.0)),
+                    },
+                    0,
+                    "a",
+                ),
+            ),
+            // Bool
+            TestLayout(
+                "Bool[0]",
+                &[0b0111_0000, 0b0000_0000, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Bool {
+                        bit: 0,
+                        default: None,
+                    },
+                    0,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "Bool[5]Closing",
+                &[0b0111_0101, 0b0000_0001, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Bool {
+                        bit: 5,
+                        default: None,
+                    },
+                    1,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "BoolDefault[false]",
+                &[0b0111_1000, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Bool {
+                        bit: 0,
+                        default: Some(false),
+                    },
+                    0,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "BoolDefault[true]",
+                &[0b0111_1001, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::Bool {
+                        bit: 0,
+                        default: Some(true),
+                    },
+                    0,
+                    "a",
+                ),
+            ),
+            // Fixed bytes
+            TestLayout(
+                "FixedBytes[1]",
+                &[0b1100_0000, 0x00, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::FixedBytes {
+                        size: 1,
+                        default: None,
+                    },
+                    1,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "FixedBytes[256]",
+                &[0b1100_0000, 0xFF, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::FixedBytes {
+                        size: 256,
+                        default: None,
+                    },
+                    256,
+                    "a",
+                ),
+            ),
+            TestLayout(
+                "FixedBytesDefault[4]",
+                &[0b1100_1000, 0x03, 0x01, 0x02, 0x03, 0x04, 1, b'a'],
+                RawProperty::new(
+                    PropertyKind::FixedBytes {
+                        size: 4,
+                        default: Some(FixedBytes::from(&[0x01, 0x02, 0x03, 0x04])),
+                    },
+                    0,
+                    "a",
+                ),
+            ),
         ];
         param.into()
     }
@@ -1007,4 +1321,45 @@ mod tests {
 
         assert_eq!(parsed, expected);
     }
+
+    #[rustest::test]
+    fn test_invalid_float_size() {
+        let reader = CheckReader::from(vec![0b0110_0001, 1, b'a']);
+        assert!(reader
+            .parse_in::<RawProperty>(Offset::zero(), 3.into())
+            .is_err());
+    }
+
+    #[rustest::test]
+    fn test_rawlayout_bool() {
+        // Two bools sharing a byte, followed by a u8.
+        let content = vec![
+            3,
+            0b0111_0000,
+            0b0000_0000,
+            1,
+            b'a',
+            0b0111_0001,
+            0b0000_0001,
+            1,
+            b'b',
+            0b0010_0000,
+            1,
+            b'c',
+        ];
+        let size = content.len();
+        let reader = CheckReader::from(content);
+        let layout = reader
+            .parse_in::<RawLayout>(Offset::zero(), size.into())
+            .unwrap();
+        assert_eq!(layout.iter().map(|p| p.size).collect::<Vec<_>>(), [0, 1, 1]);
+
+        // The bool byte is not closed before the u8.
+        let content = vec![2, 0b0111_0000, 0b0000_0000, 1, b'a', 0b0010_0000, 1, b'c'];
+        let size = content.len();
+        let reader = CheckReader::from(content);
+        assert!(reader
+            .parse_in::<RawLayout>(Offset::zero(), size.into())
+            .is_err());
+    }
 }
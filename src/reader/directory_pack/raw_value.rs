@@ -1,6 +1,6 @@
 use super::ValueStoreTrait;
 use crate::bases::*;
-use crate::common::{ContentAddress, Value};
+use crate::common::{ContentAddress, Float, Value};
 use std::cmp;
 use std::sync::Arc;
 
@@ -95,6 +95,25 @@ impl Array {
         })
     }
 
+    /// Compare the array with `prefix`, ignoring the bytes of the array after the prefix.
+    ///
+    /// Return `Equal` if the array starts with `prefix`.
+    pub fn cmp_prefix(&self, prefix: &[u8]) -> Result<cmp::Ordering> {
+        let mut our_iter = ArrayIter::new(self)?;
+        for prefix_value in prefix {
+            match our_iter.next() {
+                None => return Ok(cmp::Ordering::Less),
+                Some(our_value) => {
+                    let cmp = our_value?.cmp(prefix_value);
+                    if cmp != cmp::Ordering::Equal {
+                        return Ok(cmp);
+                    }
+                }
+            }
+        }
+        Ok(cmp::Ordering::Equal)
+    }
+
     pub fn size(&self) -> Option<usize> {
         self.size.map(|v| v.into_usize())
     }
@@ -200,6 +219,10 @@ pub enum RawValue {
     I32(i32),
     I64(i64),
     Array(Array),
+    Range(EntryRange),
+    Float(Float),
+    Bool(bool),
+    FixedBytes(FixedBytes),
 }
 
 impl RawValue {
@@ -219,6 +242,10 @@ impl RawValue {
                 a.resolve_to_vec(&mut vec)?;
                 Value::Array(vec)
             }
+            RawValue::Range(r) => Value::Range(*r),
+            RawValue::Float(v) => Value::Float(*v),
+            RawValue::Bool(v) => Value::Bool(*v),
+            RawValue::FixedBytes(v) => Value::Array(v.as_bytes().into()),
         })
     }
 
@@ -260,9 +287,49 @@ impl RawValue {
         }
     }
 
+    /// Get the range of entries stored in a range property.
+    ///
+    /// The returned range can be used as a [`crate::reader::Range`] to get or find
+    /// entries in the entry store the range refers to.
+    pub fn as_range(&self) -> EntryRange {
+        if let RawValue::Range(r) = self {
+            *r
+        } else {
+            panic!();
+        }
+    }
+
+    pub fn as_float(&self) -> f64 {
+        if let RawValue::Float(v) = self {
+            v.get()
+        } else {
+            panic!();
+        }
+    }
+
+    pub fn as_bool(&self) -> bool {
+        if let RawValue::Bool(v) = self {
+            *v
+        } else {
+            panic!();
+        }
+    }
+
+    /// Get the bytes of a fixed bytes property.
+    pub fn as_fixed_bytes(&self) -> &[u8] {
+        if let RawValue::FixedBytes(v) = self {
+            v.as_bytes()
+        } else {
+            panic!();
+        }
+    }
+
     pub(crate) fn partial_cmp(&self, other: &Value) -> Result<Option<cmp::Ordering>> {
         match other {
-            Value::Content(_) => Ok(None),
+            Value::Content(v) => Ok(match self {
+                RawValue::Content(c) => Some(c.cmp(v)),
+                _ => None,
+            }),
             Value::Unsigned(v) => Ok(match self {
                 RawValue::U8(r) => Some((*r as u64).cmp(v)),
                 RawValue::U16(r) => Some((*r as u64).cmp(v)),
@@ -279,8 +346,35 @@ impl RawValue {
             }),
             Value::Array(v) => match self {
                 RawValue::Array(a) => Ok(Some(a.cmp(v)?)),
+                RawValue::FixedBytes(b) => Ok(Some(b.as_bytes().cmp(v.as_bytes()))),
                 _ => Ok(None),
             },
+            Value::Range(v) => Ok(match self {
+                RawValue::Range(r) => r.partial_cmp(v),
+                _ => None,
+            }),
+            Value::Float(v) => Ok(match self {
+                RawValue::Float(r) => Some(r.cmp(v)),
+                _ => None,
+            }),
+            Value::Bool(v) => Ok(match self {
+                RawValue::Bool(r) => Some(r.cmp(v)),
+                _ => None,
+            }),
+        }
+    }
+
+    /// Compare the start of a array (or fixed bytes) value with `prefix`.
+    pub(crate) fn partial_cmp_prefix(&self, prefix: &[u8]) -> Result<Option<cmp::Ordering>> {
+        match self {
+            RawValue::Array(a) => Ok(Some(a.cmp_prefix(prefix)?)),
+            RawValue::FixedBytes(b) => {
+                let len = cmp::min(b.len(), prefix.len());
+                Ok(Some(
+                    b[..len].cmp(&prefix[..len]).then(len.cmp(&prefix.len())),
+                ))
+            }
+            _ => Ok(None),
         }
     }
 }
@@ -331,6 +425,11 @@ mod tests {
              Value::Array("Bye Jubako".into())),
             ResolveTestCase("ContentAddress", RawValue::Content(ContentAddress::new(PackId::from(0), ContentIdx::from(50))),
                Value::Content(ContentAddress::new(PackId::from(0), ContentIdx::from(50)))),
+            ResolveTestCase("Range", RawValue::Range(EntryRange::new(EntryIdx::from(5), EntryIdx::from(10))),
+               Value::Range(EntryRange::new(EntryIdx::from(5), EntryIdx::from(10)))),
+            ResolveTestCase("Float(1.5)", RawValue::Float(Float::from(1.5)), Value::Float(Float::from(1.5))),
+            ResolveTestCase("Bool(true)", RawValue::Bool(true), Value::Bool(true)),
+            ResolveTestCase("FixedBytes", RawValue::FixedBytes(FixedBytes::from(b"Jubako")), Value::Array("Jubako".into())),
         ])]
     fn test_resolver_resolve(Param(ResolveTestCase(_, value, expected)): Param) {
         assert_eq!(value.get().unwrap(), expected);
@@ -359,6 +458,169 @@ mod tests {
         assert_eq!(RawValue::I64(-300000000).as_signed(), -300000000);
     }
 
+    #[rustest::test]
+    fn test_resolver_range() {
+        let range = EntryRange::new_from_size(EntryIdx::from(5), EntryCount::from(10));
+        let raw_value = RawValue::Range(range);
+        assert_eq!(raw_value.as_range(), range);
+        assert_eq!(raw_value.as_range().begin(), EntryIdx::from(5));
+        assert_eq!(raw_value.as_range().size(), EntryCount::from(10));
+        assert_eq!(
+            raw_value.partial_cmp(&Value::Range(range)).unwrap(),
+            Some(cmp::Ordering::Equal)
+        );
+        assert_eq!(
+            raw_value
+                .partial_cmp(&Value::Range(EntryRange::new(
+                    EntryIdx::from(6),
+                    EntryIdx::from(7)
+                )))
+                .unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(raw_value.partial_cmp(&Value::Unsigned(5)).unwrap(), None);
+        assert_eq!(
+            RawValue::U8(5).partial_cmp(&Value::Range(range)).unwrap(),
+            None
+        );
+    }
+
+    #[rustest::test]
+    fn test_resolver_prefix() {
+        let raw_value = RawValue::Array(Array {
+            size: Some(12.into()),
+            base: BaseArray::new(b"Hello "),
+            base_len: 6,
+            extend: Some(Extend {
+                store: Arc::new(mock::ValueStore {}),
+                value_id: ValueIdx::from(10),
+            }),
+        });
+        for (prefix, expected) in [
+            ("", cmp::Ordering::Equal),
+            ("Hel", cmp::Ordering::Equal),
+            ("Hello ", cmp::Ordering::Equal),
+            ("Hello J", cmp::Ordering::Equal),
+            ("Hello Jubako", cmp::Ordering::Equal),
+            ("Hello Jubako!", cmp::Ordering::Less),
+            ("Hella", cmp::Ordering::Greater),
+            ("Hello K", cmp::Ordering::Less),
+        ] {
+            assert_eq!(
+                raw_value.partial_cmp_prefix(prefix.as_bytes()).unwrap(),
+                Some(expected),
+                "{prefix}"
+            );
+        }
+
+        let raw_value = RawValue::FixedBytes(b"abcd".into());
+        assert_eq!(
+            raw_value.partial_cmp_prefix(b"ab").unwrap(),
+            Some(cmp::Ordering::Equal)
+        );
+        assert_eq!(
+            raw_value.partial_cmp_prefix(b"abcde").unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(
+            raw_value.partial_cmp_prefix(b"b").unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(RawValue::U8(5).partial_cmp_prefix(b"ab").unwrap(), None);
+    }
+
+    #[rustest::test]
+    fn test_resolver_content() {
+        let content = |pack_id: u16, content_id: u32| {
+            ContentAddress::new(PackId::from(pack_id), ContentIdx::from(content_id))
+        };
+        let raw_value = RawValue::Content(content(1, 50));
+        assert_eq!(
+            raw_value
+                .partial_cmp(&Value::Content(content(1, 50)))
+                .unwrap(),
+            Some(cmp::Ordering::Equal)
+        );
+        assert_eq!(
+            raw_value
+                .partial_cmp(&Value::Content(content(1, 51)))
+                .unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(
+            raw_value
+                .partial_cmp(&Value::Content(content(0, 80)))
+                .unwrap(),
+            Some(cmp::Ordering::Greater)
+        );
+        assert_eq!(
+            raw_value
+                .partial_cmp(&Value::Content(content(2, 0)))
+                .unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(raw_value.partial_cmp(&Value::Unsigned(1)).unwrap(), None);
+    }
+
+    #[rustest::test]
+    fn test_resolver_float_bool() {
+        assert_eq!(RawValue::Float(Float::from(-2.5)).as_float(), -2.5);
+        assert!(RawValue::Bool(true).as_bool());
+        assert!(!RawValue::Bool(false).as_bool());
+
+        let nan = RawValue::Float(Float::from(f64::NAN));
+        assert_eq!(
+            nan.partial_cmp(&Value::Float(Float::from(f64::NAN)))
+                .unwrap(),
+            Some(cmp::Ordering::Equal)
+        );
+        assert_eq!(
+            nan.partial_cmp(&Value::Float(Float::from(f64::INFINITY)))
+                .unwrap(),
+            Some(cmp::Ordering::Greater)
+        );
+        assert_eq!(
+            RawValue::Float(Float::from(-0.0))
+                .partial_cmp(&Value::Float(Float::from(0.0)))
+                .unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(
+            RawValue::Bool(false)
+                .partial_cmp(&Value::Bool(true))
+                .unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(
+            RawValue::Bool(false)
+                .partial_cmp(&Value::Unsigned(0))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[rustest::test]
+    fn test_resolver_fixed_bytes() {
+        let raw_value = RawValue::FixedBytes(FixedBytes::from(b"Jubako"));
+        assert_eq!(raw_value.as_fixed_bytes(), b"Jubako");
+        assert_eq!(
+            raw_value
+                .partial_cmp(&Value::Array("Jubako".into()))
+                .unwrap(),
+            Some(cmp::Ordering::Equal)
+        );
+        assert_eq!(
+            raw_value.partial_cmp(&Value::Array("Juba".into())).unwrap(),
+            Some(cmp::Ordering::Greater)
+        );
+        assert_eq!(
+            raw_value
+                .partial_cmp(&Value::Array("Jubakz".into()))
+                .unwrap(),
+            Some(cmp::Ordering::Less)
+        );
+    }
+
     #[derive(Clone)]
     pub struct IndirectTestCase(&'static [u8], Option<Extend>, SmallBytes);
 
@@ -7,6 +7,7 @@ use crate::bases::*;
 enum ValueStoreKind {
     Plain = 0,
     Indexed = 1,
+    SizedIndexed = 2,
 }
 
 impl Parsable for ValueStoreKind {
@@ -15,6 +16,7 @@ impl Parsable for ValueStoreKind {
         match parser.read_u8()? {
             0 => Ok(ValueStoreKind::Plain),
             1 => Ok(ValueStoreKind::Indexed),
+            2 => Ok(ValueStoreKind::SizedIndexed),
             v => Err(format_error!(
                 &format!("Invalid ValueStoreKind ({v})"),
                 parser
@@ -23,6 +25,9 @@ impl Parsable for ValueStoreKind {
     }
 }
 
+// `ValueStoreTrait` is only reachable through the sealed `ValueStorageTrait`,
+// `ASize` doesn't leak out of the crate.
+#[allow(private_interfaces)]
 pub trait ValueStoreTrait: std::fmt::Debug + Send + Sync {
     fn get_data(&self, id: ValueIdx, size: Option<ASize>) -> Result<&[u8]>;
 }
@@ -32,13 +37,16 @@ pub trait ValueStoreTrait: std::fmt::Debug + Send + Sync {
 pub enum ValueStore {
     Plain(PlainValueStore),
     Indexed(IndexedValueStore),
+    SizedIndexed(SizedIndexedValueStore),
 }
 
+#[allow(private_interfaces)]
 impl ValueStoreTrait for ValueStore {
     fn get_data(&self, id: ValueIdx, size: Option<ASize>) -> Result<&[u8]> {
         match self {
             ValueStore::Plain(store) => store.get_data(id, size),
             ValueStore::Indexed(store) => store.get_data(id, size),
+            ValueStore::SizedIndexed(store) => store.get_data(id, size),
         }
     }
 }
@@ -49,12 +57,14 @@ impl graphex::Node for ValueStore {
         match self {
             ValueStore::Plain(store) => store.next(key),
             ValueStore::Indexed(store) => store.next(key),
+            ValueStore::SizedIndexed(store) => store.next(key),
         }
     }
     fn display(&self) -> &dyn graphex::Display {
         match self {
             ValueStore::Plain(store) => store.display(),
             ValueStore::Indexed(store) => store.display(),
+            ValueStore::SizedIndexed(store) => store.display(),
         }
     }
 
@@ -63,6 +73,7 @@ impl graphex::Node for ValueStore {
         match self {
             ValueStore::Plain(store) => store.serde(),
             ValueStore::Indexed(store) => store.serde(),
+            ValueStore::SizedIndexed(store) => store.serde(),
         }
     }
 }
@@ -73,12 +84,14 @@ impl graphex::Display for ValueStore {
         match self {
             ValueStore::Plain(store) => store.header_footer(),
             ValueStore::Indexed(store) => store.header_footer(),
+            ValueStore::SizedIndexed(store) => store.header_footer(),
         }
     }
     fn print_content(&self, out: &mut graphex::Output) -> graphex::Result {
         match self {
             ValueStore::Plain(store) => store.print_content(out),
             ValueStore::Indexed(store) => store.print_content(out),
+            ValueStore::SizedIndexed(store) => store.print_content(out),
         }
     }
 }
@@ -86,6 +99,7 @@ impl graphex::Display for ValueStore {
 pub(crate) enum ValueStoreBuilder {
     Plain,
     Indexed(Vec<Offset>),
+    SizedIndexed(Vec<(Offset, ASize)>),
 }
 
 impl Parsable for ValueStoreBuilder {
@@ -135,6 +149,36 @@ impl Parsable for ValueStoreBuilder {
                 value_offsets.push(data_size.into());
                 Ok((ValueStoreBuilder::Indexed(value_offsets), data_size))
             }
+            ValueStoreKind::SizedIndexed => {
+                let value_count: ValueCount = Count::<u64>::parse(parser)?.into();
+                let offset_size = ByteSize::parse(parser)?;
+                let data_size: Size = parser.read_usized(offset_size)?.into();
+
+                #[cfg(target_pointer_width = "32")]
+                if value_count.into_u64() > usize::MAX as u64 {
+                    unimplemented!()
+                }
+                let value_count = value_count.into_u64() as usize;
+
+                // Each value entry takes 2*offset_size bytes.
+                // Don't trust value_count to preallocate more than what we can parse.
+                let capacity =
+                    std::cmp::min(value_count, parser.remaining() / (2 * offset_size as usize));
+                let mut values = Vec::with_capacity(capacity);
+                for _ in 0..value_count {
+                    let offset: Offset = parser.read_usized(offset_size)?.into();
+                    let size = parser.read_usized(offset_size)?;
+                    let end = offset.into_u64().checked_add(size);
+                    if end.is_none_or(|end| end > data_size.into_u64()) {
+                        return Err(format_error!(
+                            &format!("Value ({offset}, {size}) is out of value store data"),
+                            parser
+                        ));
+                    }
+                    values.push((offset, ASize::from(size as usize)));
+                }
+                Ok((ValueStoreBuilder::SizedIndexed(values), data_size))
+            }
         }
     }
 }
@@ -163,6 +207,9 @@ impl DataBlockParsable for ValueStore {
                 value_offsets,
                 reader,
             }),
+            ValueStoreBuilder::SizedIndexed(values) => {
+                Self::SizedIndexed(SizedIndexedValueStore { values, reader })
+            }
         })
     }
 }
@@ -349,14 +396,106 @@ impl graphex::Node for IndexedValueStore {
     }
 }
 
+#[derive(Debug)]
+pub struct SizedIndexedValueStore {
+    values: Vec<(Offset, ASize)>,
+    reader: CheckReader,
+}
+
+impl SizedIndexedValueStore {
+    pub(self) fn get_data(&self, id: ValueIdx, size: Option<ASize>) -> Result<&[u8]> {
+        #[cfg(target_pointer_width = "32")]
+        if id.into_u64() > usize::MAX as u64 {
+            unimplemented!();
+        }
+        let id = id.into_u64() as usize;
+        let Some((offset, value_size)) = self.values.get(id) else {
+            return Err(format_error!(&format!("{id} is not a valid id")));
+        };
+        let size = size.unwrap_or(*value_size);
+        if let Cow::Borrowed(s) = self.reader.get_slice(*offset, size)? {
+            Ok(s)
+        } else {
+            unreachable!("Reader must be from memory")
+        }
+    }
+}
+
+#[cfg(feature = "explorable_serde")]
+impl serde::Serialize for SizedIndexedValueStore {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut ser = serializer.serialize_struct("SizedIndexedValueStore", 2)?;
+        ser.serialize_field("values", &self.values.len())?;
+        ser.serialize_field("size", &self.reader.size())?;
+        ser.end()
+    }
+}
+
+#[cfg(feature = "explorable")]
+impl graphex::Display for SizedIndexedValueStore {
+    fn header_footer(&self) -> Option<(String, String)> {
+        Some(("SizedIndexedValueStore(".to_string(), ")".to_string()))
+    }
+    fn print_content(&self, out: &mut graphex::Output) -> graphex::Result {
+        use yansi::Paint;
+        out.field(
+            &format!("values count ({})", "<N> or <N>-<len>".bold()),
+            &self.values.len(),
+        )?;
+        out.field("size", &self.reader.size())
+    }
+}
+
+#[cfg(feature = "explorable")]
+impl graphex::Node for SizedIndexedValueStore {
+    fn next(&self, key: &str) -> graphex::ExploreResult<'_> {
+        let (idx, size) = if let Some((first, second)) = key.split_once('-') {
+            let idx = first
+                .parse::<u64>()
+                .map_err(|e| graphex::Error::key(&format!("{e}")))?;
+            let size = Some(ASize::from(
+                second
+                    .parse::<usize>()
+                    .map_err(|e| graphex::Error::key(&format!("{e}")))?,
+            ));
+            (idx, size)
+        } else {
+            let idx = key
+                .parse::<u64>()
+                .map_err(|e| graphex::Error::key(&format!("{e}")))?;
+            (idx, None)
+        };
+        if idx >= self.values.len() as u64 {
+            return Err(graphex::Error::key(&format!("{idx} is not a valid index")));
+        }
+        Ok(Box::new(
+            String::from_utf8_lossy(self.get_data(ValueIdx::from(idx), size)?).into_owned(),
+        )
+        .into())
+    }
+
+    fn display(&self) -> &dyn graphex::Display {
+        self
+    }
+
+    #[cfg(feature = "explorable_serde")]
+    fn serde(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[rustest::test]
     fn test_valuestorekind() {
-        let reader = CheckReader::from([0x00, 0x01, 0x02]);
-        let mut parser = reader.create_parser(Offset::zero(), 3.into()).unwrap();
+        let reader = CheckReader::from([0x00, 0x01, 0x02, 0x03]);
+        let mut parser = reader.create_parser(Offset::zero(), 4.into()).unwrap();
         assert_eq!(
             ValueStoreKind::parse(&mut parser).unwrap(),
             ValueStoreKind::Plain
@@ -365,7 +504,11 @@ mod tests {
             ValueStoreKind::parse(&mut parser).unwrap(),
             ValueStoreKind::Indexed
         );
-        assert_eq!(parser.tell(), Offset::new(2));
+        assert_eq!(
+            ValueStoreKind::parse(&mut parser).unwrap(),
+            ValueStoreKind::SizedIndexed
+        );
+        assert_eq!(parser.tell(), Offset::new(3));
         assert!(ValueStoreKind::parse(&mut parser).is_err());
     }
 
@@ -497,4 +640,67 @@ mod tests {
             vec![0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37]
         );
     }
+
+    #[rustest::test]
+    fn test_sizedindexedvaluestore() {
+        #[rustfmt::skip]
+        let reader = Reader::from(
+            vec![
+                b'b', b'a', b'b', b'd', b'a', b'b', b'c', // Data
+                0x5C, 0x2F, 0xAB, 0xC5, // CRC
+                0x02, // kind
+                0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // value count
+                0x01, // offset_size
+                0x07, // data_size
+                0x04, 0x01, // Offset and size of entry 0
+                0x04, 0x02, // Offset and size of entry 1
+                0x04, 0x03, // Offset and size of entry 2
+                0x01, 0x03, // Offset and size of entry 3
+                0x00, 0x01, // Offset and size of entry 4
+                0x66, 0xB0, 0xA5, 0x7C, // CRC
+            ]
+        );
+        let value_store = reader
+            .parse_data_block::<ValueStore>(SizedOffset::new(ASize::new(21), Offset::new(11)))
+            .unwrap();
+        match &value_store {
+            ValueStore::SizedIndexed(store) => {
+                assert_eq!(store.values.len(), 5);
+                assert_eq!(store.reader.size(), Size::from(0x07_u64));
+            }
+            _ => panic!("Wrong type"),
+        }
+
+        assert_eq!(value_store.get_data(0.into(), None).unwrap(), b"a");
+        assert_eq!(value_store.get_data(1.into(), None).unwrap(), b"ab");
+        assert_eq!(value_store.get_data(2.into(), None).unwrap(), b"abc");
+        assert_eq!(value_store.get_data(3.into(), None).unwrap(), b"abd");
+        assert_eq!(value_store.get_data(4.into(), None).unwrap(), b"b");
+        assert_eq!(
+            value_store.get_data(3.into(), Some(2.into())).unwrap(),
+            b"ab"
+        );
+        assert!(value_store.get_data(5.into(), None).is_err());
+    }
+
+    #[rustest::test]
+    fn test_sizedindexedvaluestore_overflow() {
+        #[rustfmt::skip]
+        let reader = Reader::from(
+            vec![
+                0x02, // kind
+                0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // value count
+                0x08, // offset_size
+                0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // data_size
+                0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // Offset of entry 0
+                0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Size of entry 0
+                0x1F, 0xCD, 0x02, 0xBB, // CRC
+            ]
+        );
+        let Err(err) = reader.parse_block_in::<ValueStoreBuilder>(Offset::zero(), ASize::new(34))
+        else {
+            panic!("Value out of the value store data must be rejected");
+        };
+        assert!(matches!(*err, ErrorKind::Format(_)), "{err:?}");
+    }
 }
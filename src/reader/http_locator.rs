@@ -0,0 +1,246 @@
+use super::locator::PackLocatorTrait;
+use crate::bases::*;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use url::Url;
+use uuid::Uuid;
+
+/// The size of the blocks fetched by a [`HttpSource`].
+pub const HTTP_BLOCK_SIZE: u64 = 64 * 1024;
+
+/// The default number of blocks cached by a [`HttpSource`] (4MB).
+const DEFAULT_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(64).unwrap();
+
+fn http_error(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> std::io::Error {
+    std::io::Error::other(error)
+}
+
+/// Parse a `Content-Range` header value (`bytes <first>-<last>/<total>`).
+fn parse_content_range(value: &str) -> Option<(u64, u64, u64)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (first, last) = range.split_once('-')?;
+    Some((first.parse().ok()?, last.parse().ok()?, total.parse().ok()?))
+}
+
+/// A source reading a file served by a http server.
+///
+/// Bytes are fetched lazily, by blocks of [`HTTP_BLOCK_SIZE`] bytes, using http range requests.
+/// All the blocks missing for a read are fetched with one request.
+/// The last fetched blocks are kept in a cache.
+pub struct HttpSource {
+    url: Url,
+    session: attohttpc::Session,
+    size: Size,
+    blocks: Mutex<LruCache<u64, Arc<[u8]>>>,
+}
+
+impl HttpSource {
+    /// Open the file at `url`, returning `None` if the server doesn't find it.
+    pub fn open(url: Url) -> Result<Option<Self>> {
+        Self::with_cache_size(url, DEFAULT_CACHE_SIZE)
+    }
+
+    /// Open the file at `url`, caching at most `cache_size` blocks.
+    pub fn with_cache_size(url: Url, cache_size: NonZeroUsize) -> Result<Option<Self>> {
+        let session = attohttpc::Session::new();
+        // Fetch the first block. This gives us the size of the file.
+        let Some((size, first_block)) = Self::fetch(&session, &url, 0, HTTP_BLOCK_SIZE)? else {
+            return Ok(None);
+        };
+        let mut blocks = LruCache::new(cache_size);
+        blocks.put(0, first_block);
+        Ok(Some(Self {
+            url,
+            session,
+            size: size.into(),
+            blocks: Mutex::new(blocks),
+        }))
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Fetch at most `size` bytes at `offset`.
+    ///
+    /// Returns the total size of the file and the fetched bytes,
+    /// or `None` if the file is not found.
+    fn fetch(
+        session: &attohttpc::Session,
+        url: &Url,
+        offset: u64,
+        size: u64,
+    ) -> std::io::Result<Option<(u64, Arc<[u8]>)>> {
+        let response = session
+            .get(url.as_str())
+            .header(
+                attohttpc::header::RANGE,
+                format!("bytes={}-{}", offset, offset + size - 1),
+            )
+            .send()
+            .map_err(http_error)?;
+        match response.status() {
+            attohttpc::StatusCode::NOT_FOUND | attohttpc::StatusCode::GONE => Ok(None),
+            attohttpc::StatusCode::PARTIAL_CONTENT => {
+                let content_range = response
+                    .headers()
+                    .get(attohttpc::header::CONTENT_RANGE)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_content_range);
+                let Some((first, last, total)) = content_range else {
+                    return Err(http_error(format!("{url}: Invalid Content-Range")));
+                };
+                if first != offset || last < first {
+                    return Err(http_error(format!(
+                        "{url}: Server sent range {first}-{last} instead of {offset}-"
+                    )));
+                }
+                let bytes = response.bytes().map_err(http_error)?;
+                if bytes.len() as u64 != last - first + 1 {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                Ok(Some((total, bytes.into())))
+            }
+            attohttpc::StatusCode::OK => Err(http_error(format!(
+                "{url}: Server doesn't support range requests"
+            ))),
+            status => Err(http_error(format!("{url}: Http error {status}"))),
+        }
+    }
+
+    /// Fetch the blocks `first_block..=last_block` from the server, with one request.
+    ///
+    /// Fetched blocks are put in the cache.
+    fn fetch_blocks(&self, first_block: u64, last_block: u64) -> std::io::Result<Vec<Arc<[u8]>>> {
+        // Don't keep the lock while fetching, so other threads can read other blocks.
+        let Some((_, bytes)) = Self::fetch(
+            &self.session,
+            &self.url,
+            first_block * HTTP_BLOCK_SIZE,
+            (last_block - first_block + 1) * HTTP_BLOCK_SIZE,
+        )?
+        else {
+            return Err(http_error(format!("{}: File has been removed", self.url)));
+        };
+        let blocks = bytes
+            .chunks(HTTP_BLOCK_SIZE as usize)
+            .map(Arc::from)
+            .collect::<Vec<Arc<[u8]>>>();
+        if blocks.len() as u64 != last_block - first_block + 1 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        let mut cache = self.blocks.lock().unwrap();
+        for (block_idx, block) in (first_block..).zip(&blocks) {
+            cache.put(block_idx, Arc::clone(block));
+        }
+        Ok(blocks)
+    }
+}
+
+impl std::fmt::Debug for HttpSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Http {}", self.url)
+    }
+}
+
+impl SourceTrait for HttpSource {
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn read_at(&self, offset: Offset, buf: &mut [u8]) -> std::io::Result<usize> {
+        if offset.into_u64() >= self.size.into_u64() || buf.is_empty() {
+            return Ok(0);
+        }
+        let offset = offset.into_u64();
+        let end = std::cmp::min(offset + buf.len() as u64, self.size.into_u64());
+        let first_block = offset / HTTP_BLOCK_SIZE;
+        let last_block = (end - 1) / HTTP_BLOCK_SIZE;
+        let mut blocks = {
+            let mut cache = self.blocks.lock().unwrap();
+            (first_block..=last_block)
+                .map(|block_idx| cache.get(&block_idx).cloned())
+                .collect::<Vec<_>>()
+        };
+        // Fetch all the missing blocks at once, even if some blocks in between are cached.
+        if let (Some(first_missing), Some(last_missing)) = (
+            blocks.iter().position(Option::is_none),
+            blocks.iter().rposition(Option::is_none),
+        ) {
+            let fetched = self.fetch_blocks(
+                first_block + first_missing as u64,
+                first_block + last_missing as u64,
+            )?;
+            for (block, fetched) in blocks[first_missing..=last_missing].iter_mut().zip(fetched) {
+                *block = Some(fetched);
+            }
+        }
+        let mut read = 0;
+        for (block_idx, block) in (first_block..).zip(blocks) {
+            let block = block.expect("Missing blocks have been fetched");
+            let in_block = (offset + read as u64 - block_idx * HTTP_BLOCK_SIZE) as usize;
+            let available = block.get(in_block..).unwrap_or_default();
+            let size = std::cmp::min(buf.len() - read, available.len());
+            buf[read..read + size].copy_from_slice(&available[..size]);
+            read += size;
+            if read == buf.len() {
+                break;
+            }
+        }
+        Ok(read)
+    }
+}
+
+/// Locate packs on a http server.
+///
+/// Pack locations are resolved relatively to a base url (the url of the manifest pack).
+/// Located packs are read lazily with [`HttpSource`].
+pub struct HttpLocator {
+    base_url: Url,
+}
+
+impl HttpLocator {
+    pub fn new(base_url: Url) -> Self {
+        Self { base_url }
+    }
+}
+
+impl PackLocatorTrait for HttpLocator {
+    fn locate(&self, uuid: Uuid, helper: &str) -> Result<Option<Reader>> {
+        if helper.is_empty() {
+            return Ok(None);
+        }
+        let Ok(url) = self.base_url.join(helper) else {
+            return Ok(None);
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            return Ok(None);
+        }
+        match HttpSource::open(url)? {
+            None => Ok(None),
+            Some(source) => {
+                let container_pack =
+                    super::jubako::open_as_container_pack(Reader::from_source(source))?;
+                Ok(container_pack.get_pack_reader(&uuid))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustest::test]
+    fn test_parse_content_range() {
+        assert_eq!(parse_content_range("bytes 0-99/1000"), Some((0, 99, 1000)));
+        assert_eq!(
+            parse_content_range("bytes 65536-65599/65600"),
+            Some((65536, 65599, 65600))
+        );
+        assert_eq!(parse_content_range("bytes */1000"), None);
+        assert_eq!(parse_content_range("bytes 0-99/*"), None);
+        assert_eq!(parse_content_range("0-99/1000"), None);
+    }
+}
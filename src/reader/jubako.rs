@@ -5,9 +5,12 @@ use super::content_pack::ContentPack;
 use super::directory_pack::{DirectoryPack, EntryStorage};
 use super::locator::{ChainedLocator, FsLocator, PackLocatorTrait};
 use super::manifest_pack::ManifestPack;
-use super::{ByteRegion, Index, MayMissPack, ValueStorage};
+use super::{
+    ByteRegion, CheckProgress, CheckReport, Index, MayMissPack, PackCheckReport, ValueStorage,
+};
 use crate::bases::*;
-use crate::common::{ContentAddress, Pack, PackHeader, PackKind};
+use crate::common::{ContentAddress, Pack, PackHeader, PackInfo, PackKind};
+use rayon::prelude::*;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -85,7 +88,49 @@ impl Container {
         locator: Arc<dyn PackLocatorTrait>,
     ) -> Result<Self> {
         let path: PathBuf = path.as_ref().into();
-        let reader = Reader::from(FileSource::open(path)?);
+        Self::from_reader(Reader::from(FileSource::open(path)?), locator)
+    }
+
+    /// Open a container stored in memory.
+    ///
+    /// `bytes` is the content of a container pack (or of a manifest pack), as a `&'static [u8]`
+    /// (from `include_bytes!`), a `Vec<u8>`, a `Arc<[u8]>`, ...
+    /// As there is no location to search packs in, packs not stored in `bytes`
+    /// are missing packs.
+    pub fn from_bytes<T>(bytes: T) -> Result<Self>
+    where
+        T: AsRef<[u8]> + Sync + Send + std::fmt::Debug + 'static,
+    {
+        Self::from_reader(
+            Reader::from(bytes),
+            Arc::new(ChainedLocator::new(Vec::new())),
+        )
+    }
+
+    /// Open a container published on a http server.
+    ///
+    /// `url` is the url of the manifest pack (or a container pack with a manifest pack within).
+    /// Packs are located relatively to `url` with a [`HttpLocator`](super::HttpLocator)
+    /// and only the parts of the packs actually read are downloaded.
+    #[cfg(feature = "http")]
+    pub fn from_url(url: url::Url) -> Result<Self> {
+        let Some(source) = super::HttpSource::open(url.clone())? else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{url} not found"),
+            )
+            .into());
+        };
+        Self::from_reader(
+            Reader::from_source(source),
+            Arc::new(super::HttpLocator::new(url)),
+        )
+    }
+
+    /// Open a container from a reader, with a specific locator to found other packs.
+    ///
+    /// `reader` is a reader on the manifest pack (or a container pack with a manifest pack within).
+    pub fn from_reader(reader: Reader, locator: Arc<dyn PackLocatorTrait>) -> Result<Self> {
         let container_pack = Arc::new(open_as_container_pack(reader)?);
         let reader = container_pack.get_manifest_pack_reader()?;
 
@@ -102,11 +147,11 @@ impl Container {
         let locator = Arc::new(ChainedLocator::new(locators));
 
         let pack_info = manifest_pack.get_directory_pack_info();
-        let directory_pack = Arc::new(DirectoryPack::new(
-            locator
-                .locate(pack_info.uuid, &pack_info.pack_location)?
-                .unwrap(),
-        )?);
+        let Some(directory_reader) = locator.locate(pack_info.uuid, &pack_info.pack_location)?
+        else {
+            return Err(format_error!("Impossible to locate the directory pack"));
+        };
+        let directory_pack = Arc::new(DirectoryPack::new(directory_reader)?);
         let value_storage = directory_pack.create_value_storage();
         let entry_storage = directory_pack.create_entry_storage();
         let mut packs = Vec::new();
@@ -198,6 +243,11 @@ impl Container {
         }
     }
 
+    /// Get the manifest pack of the container
+    pub fn get_manifest_pack(&self) -> &ManifestPack {
+        &self.manifest_pack
+    }
+
     /// Get the directory pack of the container
     pub fn get_directory_pack(&self) -> &Arc<DirectoryPack> {
         &self.directory_pack
@@ -219,28 +269,142 @@ impl Container {
     }
 
     /// Check the container
+    ///
+    /// Each pack is checked against its own check info and against the check info
+    /// stored in the manifest pack.
+    /// Missing packs are not checked.
+    /// Use [`Self::check_report`] to know which pack is failing.
     pub fn check(&self) -> Result<bool> {
-        if !self.manifest_pack.check()? {
-            return Ok(false);
-        }
-        if !self.directory_pack.check()? {
-            return Ok(false);
+        Ok(self.check_report()?.is_ok())
+    }
+
+    /// Check the container and report the result of the check of each pack.
+    pub fn check_report(&self) -> Result<CheckReport> {
+        self.check_report_with_progress(&())
+    }
+
+    /// Check the container and report the result of the check of each pack.
+    ///
+    /// Packs are checked in parallel and `progress` is notified as packs are checked.
+    pub fn check_report_with_progress(&self, progress: &dyn CheckProgress) -> Result<CheckReport> {
+        let mut manifest_report =
+            PackCheckReport::new(self.manifest_pack.uuid(), Some(PackKind::Manifest), None);
+        manifest_report.found = true;
+        manifest_report.header_ok = Some(true);
+        progress.start_pack(self.manifest_pack.uuid(), self.manifest_pack.size());
+        match self.manifest_pack.check() {
+            Ok(ok) => manifest_report.tail_check_ok = Some(ok),
+            Err(e) => manifest_report.set_error(e)?,
         }
+        progress.hashed(self.manifest_pack.uuid(), self.manifest_pack.size());
+        progress.end_pack(&manifest_report);
 
-        for pack_info in self.manifest_pack.get_pack_infos().iter() {
-            let pack_reader = self
-                .locator
-                .locate(pack_info.uuid, &pack_info.pack_location)?;
-            if let Some(r) = pack_reader {
-                let pseudo_container_pack = open_as_container_pack(r)?;
-                // check the container in itself.
-                if !pseudo_container_pack.check()? {
-                    return Ok(false);
-                }
-                // [TODO] Check with the checkinfo contained in the manifest.
+        let pack_infos = std::iter::once(self.manifest_pack.get_directory_pack_info())
+            .chain(self.manifest_pack.get_pack_infos())
+            .collect::<Vec<_>>();
+        let pack_reports = pack_infos
+            .into_par_iter()
+            .map(|pack_info| self.check_pack_with_progress(pack_info, progress))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut packs = vec![manifest_report];
+        packs.extend(pack_reports);
+        Ok(CheckReport { packs })
+    }
+
+    /// Check the pack described by `pack_info`.
+    ///
+    /// The pack is checked against its own check info and against the check info
+    /// stored in the manifest pack. The later ensures that the pack is the one the
+    /// manifest was created with and not another (valid) pack with the same uuid.
+    pub fn check_pack(&self, pack_info: &PackInfo) -> Result<PackCheckReport> {
+        self.check_pack_with_progress(pack_info, &())
+    }
+
+    fn check_pack_with_progress(
+        &self,
+        pack_info: &PackInfo,
+        progress: &dyn CheckProgress,
+    ) -> Result<PackCheckReport> {
+        let report = self.check_pack_inner(pack_info, progress)?;
+        progress.end_pack(&report);
+        Ok(report)
+    }
+
+    fn check_pack_inner(
+        &self,
+        pack_info: &PackInfo,
+        progress: &dyn CheckProgress,
+    ) -> Result<PackCheckReport> {
+        let mut report = PackCheckReport::new(
+            pack_info.uuid,
+            Some(pack_info.pack_kind),
+            Some(pack_info.pack_location.to_string()),
+        );
+        let pack_reader = match self
+            .locator
+            .locate(pack_info.uuid, &pack_info.pack_location)?
+        {
+            None => return Ok(report),
+            Some(r) => r,
+        };
+        // The locator may give us a container containing the pack
+        let pack_reader = match open_as_container_pack(pack_reader) {
+            Ok(container_pack) => container_pack.get_pack_reader(&pack_info.uuid),
+            Err(e) => {
+                report.found = true;
+                report.set_error(e)?;
+                return Ok(report);
+            }
+        };
+        let pack_reader = match pack_reader {
+            None => {
+                report.found = true;
+                report.error = Some(format!(
+                    "Pack is not at location `{}`",
+                    pack_info.pack_location
+                ));
+                return Ok(report);
+            }
+            Some(r) => r,
+        };
+
+        // Check info contained in the manifest.
+        let check_info = match self.manifest_pack.get_pack_check_info(pack_info.uuid) {
+            Ok(Some(c)) => c,
+            Ok(None) => {
+                return Err(format_error!(format!(
+                    "Pack {} is not in the manifest",
+                    pack_info.uuid
+                )))
+            }
+            Err(e) => {
+                report.found = true;
+                report.set_error(e)?;
+                return Ok(report);
             }
+        };
+
+        let pack_header = match report.check_reader(&pack_reader, Some(&check_info), progress)? {
+            None => return Ok(report),
+            Some(h) => h,
+        };
+        if pack_header.file_size != pack_info.pack_size && report.error.is_none() {
+            report.error = Some(format!(
+                "Pack size ({}) doesn't match size in manifest ({})",
+                pack_header.file_size, pack_info.pack_size
+            ));
         }
-        Ok(true)
+        Ok(report)
+    }
+
+    /// Verify the container has been signed with the key of `public_key`.
+    ///
+    /// The signature authenticates the manifest pack only. Use [`Self::check`] to verify
+    /// that other packs are the ones referenced by the manifest pack.
+    #[cfg(feature = "signature")]
+    pub fn verify_signature(&self, public_key: &ed25519_dalek::VerifyingKey) -> Result<bool> {
+        self.manifest_pack.verify_signature(public_key)
     }
 
     /// Get the uuid of the container (manifest_pack)
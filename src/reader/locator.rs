@@ -1,13 +1,121 @@
 use crate::bases::*;
-use std::path::PathBuf;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use url::Url;
 use uuid::Uuid;
 
+/// Locate the packs of a container.
+///
+/// `locate` returns a reader on the pack `uuid`, using the location `helper` of the pack
+/// (as stored in the manifest pack), or `None` if the pack is not found.
+/// Readers can be created on any storage with [`Reader::from_source`] and
+/// [`open_as_container_pack`](super::open_as_container_pack) gives access to packs stored in
+/// a container pack.
 pub trait PackLocatorTrait: Sync + Send {
     fn locate(&self, uuid: Uuid, helper: &str) -> Result<Option<Reader>>;
 }
 
+/// The characters percent-encoded in the locations written by the creators.
+///
+/// `%` and `:` must be encoded for relative paths to be read back as paths.
+/// Other characters have a special meaning in urls.
+const LOCATION_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b':')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Convert a path to a pack location (as stored in the manifest pack).
+///
+/// This is the reverse of [`resolve_location`] for relative and absolute paths.
+pub(crate) fn encode_location(path: &str) -> String {
+    utf8_percent_encode(path, LOCATION_ENCODE_SET).to_string()
+}
+
+/// A resolved pack location.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PackLocation {
+    /// The pack is in the container pack.
+    Container,
+    /// The pack is a local file.
+    Path(PathBuf),
+    /// The pack is at a url with another scheme than `file:`.
+    Url(Url),
+}
+
+/// Resolve the location of a pack (as stored in the manifest pack).
+///
+/// `location` is a url, as described in the manifest pack specification:
+/// - An empty location: the pack is in the container pack.
+/// - A relative path, relative to `base_dir`.
+/// - An absolute path.
+/// - A `file:` url.
+/// - A url with another scheme, which is not resolved to a path.
+///
+/// Paths are percent-decoded.
+///
+/// Previous creators wrote raw paths. So if the decoded path does not exist,
+/// the location is used as a literal path (if it exists).
+pub(crate) fn resolve_location(base_dir: &Path, location: &str) -> Result<PackLocation> {
+    let invalid_location = |msg: String| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid pack location `{location}`: {msg}"),
+        )
+    };
+    if location.is_empty() {
+        return Ok(PackLocation::Container);
+    }
+    let literal_path = base_dir.join(location);
+    let url = match Url::parse(location) {
+        Ok(url) => {
+            // `C:\dir\pack.jbkc` is parsed as a url with a `c` scheme.
+            if cfg!(windows) && url.scheme().len() == 1 {
+                None
+            } else {
+                Some(url)
+            }
+        }
+        Err(url::ParseError::RelativeUrlWithoutBase) => None,
+        Err(e) => return Err(invalid_location(e.to_string()).into()),
+    };
+    match url {
+        None => match percent_decode_str(location).decode_utf8() {
+            Ok(path) => {
+                let path = base_dir.join(path.as_ref());
+                if !path.exists() && literal_path.exists() {
+                    Ok(PackLocation::Path(literal_path))
+                } else {
+                    Ok(PackLocation::Path(path))
+                }
+            }
+            Err(_) if literal_path.exists() => Ok(PackLocation::Path(literal_path)),
+            Err(e) => Err(invalid_location(e.to_string()).into()),
+        },
+        Some(url) if url.scheme() == "file" => match url.to_file_path() {
+            Ok(path) => Ok(PackLocation::Path(path)),
+            Err(_) => Err(invalid_location("Not a local file".into()).into()),
+        },
+        // A relative path containing a `:`, written by a previous creator.
+        Some(_) if literal_path.exists() => Ok(PackLocation::Path(literal_path)),
+        Some(url) => Ok(PackLocation::Url(url)),
+    }
+}
+
 /** Locate in a directory
+
+The located file may be the pack itself or a container pack containing the pack.
+Locations are resolved relatively to the directory. Absolute paths and `file:` urls are also
+supported. Locations with another scheme (as `http:`) are not located by `FsLocator`
+(`None` is returned), so another locator may locate them.
 */
 pub struct FsLocator {
     base_dir: PathBuf,
@@ -20,16 +128,24 @@ impl FsLocator {
 }
 
 impl PackLocatorTrait for FsLocator {
-    fn locate(&self, _uuid: Uuid, path: &str) -> Result<Option<Reader>> {
-        let path = self.base_dir.join(path);
+    fn locate(&self, uuid: Uuid, location: &str) -> Result<Option<Reader>> {
+        let PackLocation::Path(path) = resolve_location(&self.base_dir, location)? else {
+            return Ok(None);
+        };
         if path.is_file() {
-            Ok(Some(Reader::from(FileSource::open(path)?)))
+            let container_pack =
+                super::jubako::open_as_container_pack(Reader::from(FileSource::open(path)?))?;
+            Ok(container_pack.get_pack_reader(&uuid))
         } else {
             Ok(None)
         }
     }
 }
 
+/// Locate packs with several locators, in order.
+///
+/// The first located pack is returned. Locators must return `None` for the locations
+/// they don't handle (as an unsupported url scheme), so next locators can locate them.
 pub struct ChainedLocator(Vec<Arc<dyn PackLocatorTrait>>);
 
 impl ChainedLocator {
@@ -49,3 +165,64 @@ impl PackLocatorTrait for ChainedLocator {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustest::test]
+    fn test_resolve_location() {
+        let base_dir = Path::new("base");
+        assert_eq!(
+            resolve_location(base_dir, "").unwrap(),
+            PackLocation::Container
+        );
+        assert_eq!(
+            resolve_location(base_dir, "pack.jbkc").unwrap(),
+            PackLocation::Path(base_dir.join("pack.jbkc"))
+        );
+        assert_eq!(
+            resolve_location(base_dir, "sub%20dir/pack%25.jbkc").unwrap(),
+            PackLocation::Path(base_dir.join("sub dir/pack%.jbkc"))
+        );
+        assert_eq!(
+            resolve_location(base_dir, "../pack.jbkc").unwrap(),
+            PackLocation::Path(base_dir.join("../pack.jbkc"))
+        );
+        if cfg!(unix) {
+            assert_eq!(
+                resolve_location(base_dir, "/data/packs/pack.jbkc").unwrap(),
+                PackLocation::Path(PathBuf::from("/data/packs/pack.jbkc"))
+            );
+            assert_eq!(
+                resolve_location(base_dir, "file:///data/my%20packs/pack.jbkc").unwrap(),
+                PackLocation::Path(PathBuf::from("/data/my packs/pack.jbkc"))
+            );
+        }
+        assert_eq!(
+            resolve_location(base_dir, "http://example.com/pack.jbkc").unwrap(),
+            PackLocation::Url("http://example.com/pack.jbkc".parse().unwrap())
+        );
+        assert!(resolve_location(base_dir, "file://example.com/pack.jbkc").is_err());
+        assert!(resolve_location(base_dir, "%FF.jbkc").is_err());
+    }
+
+    #[rustest::test]
+    fn test_encode_location() {
+        let base_dir = Path::new("base");
+        for path in [
+            "pack.jbkc",
+            "sub dir/50%.jbkc",
+            "a:b.jbkc",
+            "../%FF#1?.jbkc",
+        ] {
+            let location = encode_location(path);
+            assert_eq!(
+                resolve_location(base_dir, &location).unwrap(),
+                PackLocation::Path(base_dir.join(path)),
+                "{location}"
+            );
+        }
+        assert_eq!(encode_location("a:b/50%.jbkc"), "a%3Ab/50%25.jbkc");
+    }
+}
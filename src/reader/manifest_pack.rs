@@ -4,6 +4,8 @@ use crate::bases::*;
 use crate::common::{
     CheckInfo, ManifestCheckStream, ManifestPackHeader, Pack, PackHeader, PackInfo, PackKind,
 };
+#[cfg(feature = "signature")]
+use crate::common::{CheckKind, Hasher};
 use crate::reader::directory_pack::{ValueStore, ValueStoreTrait};
 use std::cmp;
 use uuid::Uuid;
@@ -116,6 +118,28 @@ impl ManifestPack {
         )
     }
 
+    /// Verify the manifest pack has been signed with the key of `public_key`.
+    ///
+    /// Only the manifest pack is authenticated. As the manifest pack contains the check info
+    /// of all packs, other packs must be checked against it (see [`Container::check`][crate::reader::Container::check]).
+    #[cfg(feature = "signature")]
+    pub fn verify_signature(&self, public_key: &ed25519_dalek::VerifyingKey) -> Result<bool> {
+        let check_info = self.get_check_info()?;
+        if !check_info.has_signature(public_key) {
+            return Ok(false);
+        }
+        let mut check_stream = self.reader.create_stream(
+            Offset::zero(),
+            Size::from(self.pack_header.check_info_pos),
+            false,
+        )?;
+        let mut check_stream =
+            ManifestCheckStream::new_from_offset_iter(&mut check_stream, self.packs_offset());
+        let mut hasher = Hasher::new([CheckKind::Ed25519]);
+        hasher.update_reader(&mut check_stream)?;
+        Ok(check_info.check_signature(public_key, &hasher.finalize()))
+    }
+
     pub fn get_pack_check_info(&self, uuid: Uuid) -> Result<Option<CheckInfo>> {
         let pack_info = if self.directory_pack_info.uuid == uuid {
             &self.directory_pack_info
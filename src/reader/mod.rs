@@ -3,27 +3,35 @@ mod content_pack;
 mod byte_region;
 mod byte_slice;
 mod byte_stream;
+mod check_report;
 mod container_pack;
 mod directory_pack;
+#[cfg(feature = "http")]
+mod http_locator;
 mod jubako;
 mod locator;
 mod manifest_pack;
 mod missing;
 
-pub use self::jubako::Container;
+pub use self::jubako::{open_as_container_pack, Container};
 pub use container_pack::ContainerPack;
 pub use content_pack::ContentPack;
 pub use directory_pack::{builder, layout};
 pub use missing::MayMissPack;
 pub type EntryStore = std::sync::Arc<directory_pack::EntryStore>;
 pub(crate) use crate::common::ManifestPackHeader;
-pub use crate::common::{ContentAddress, PackInfo};
+pub use crate::common::{ContentAddress, IndexSortKey, PackInfo, PackKind};
 pub use byte_region::ByteRegion;
 pub use byte_slice::ByteSlice;
 pub use byte_stream::ByteStream;
+pub use check_report::{BlockFailure, BlockKind, CheckProgress, CheckReport, PackCheckReport};
 pub use directory_pack::{
-    CompareTrait, DirectoryPack, EntryTrait, Index, RangeTrait as Range, RawValue, ValueStorage,
+    CompareTrait, DirectoryPack, EntryTrait, Index, IndexRange, RangeIter, RangeTrait as Range,
+    RawValue, ValueStorage,
 };
+#[cfg(feature = "http")]
+pub use http_locator::{HttpLocator, HttpSource, HTTP_BLOCK_SIZE};
 pub use layout::VariantPart;
+pub(crate) use locator::{encode_location, resolve_location, PackLocation};
 pub use locator::{ChainedLocator, FsLocator, PackLocatorTrait};
 pub use manifest_pack::{ManifestPack, PackOffsetsIter};
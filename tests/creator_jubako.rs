@@ -10,6 +10,7 @@ use camino::{Utf8Path, Utf8PathBuf};
 use jubako::creator;
 use jubako::creator::{schema, Result};
 use jubako::reader::{EntryTrait, Range};
+use rayon::prelude::*;
 use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io::{Read, Seek};
@@ -20,6 +21,7 @@ use std::sync::Arc;
 pub enum ValueStoreKindParam {
     Plain,
     Indexed,
+    SizedIndexed,
 }
 
 impl rustest::ParamName for ValueStoreKindParam {
@@ -54,6 +56,7 @@ fn Compression(Param(c): Param) -> creator::Compression {
 #[fixture(params: ValueStoreKindParam=[
     ValueStoreKindParam::Plain,
     ValueStoreKindParam::Indexed,
+    ValueStoreKindParam::SizedIndexed,
 ])]
 fn ValueStoreKind(Param(k): Param) -> ValueStoreKindParam {
     k
@@ -115,6 +118,7 @@ fn create_directory_pack(
     let value_store = match value_store_kind {
         ValueStoreKindParam::Plain => creator::ValueStore::new_plain(None),
         ValueStoreKindParam::Indexed => creator::ValueStore::new_indexed(),
+        ValueStoreKindParam::SizedIndexed => creator::ValueStore::new_sized_indexed(None),
     };
     creator.add_value_store(value_store.clone());
     let entry_def = schema::Schema::<&str, ()>::new(
@@ -145,12 +149,11 @@ fn create_directory_pack(
         );
     }
 
-    let entry_store = jubako::creator::EntryStore::new(entry_def, entry_store.into_iter());
+    let entry_store = jubako::creator::EntryStore::new(entry_def, entry_store.into_iter())?;
     let entry_store_idx = creator.add_entry_store(entry_store);
-    creator.create_index(
+    creator.create_range_index(
         "Super index",
         Default::default(),
-        0.into(),
         entry_store_idx,
         (entries.len() as u32).into(),
         jubako::EntryIdx::from(0),
@@ -300,5 +303,1860 @@ fn test_content_pack(
     }
 }
 
+struct ContentEntryStore(Vec<jubako::ContentAddress>);
+
+impl creator::EntryStoreCreatorTrait for ContentEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        add_content_entry_store(self.0, false, directory_pack)
+    }
+}
+
+// Same as ContentEntryStore but with a crc32 per entry
+struct CheckedContentEntryStore(Vec<jubako::ContentAddress>);
+
+impl creator::EntryStoreCreatorTrait for CheckedContentEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        add_content_entry_store(self.0, true, directory_pack)
+    }
+}
+
+// The path of `temp_dir`, as a utf8 path.
+fn utf8_temp_dir(temp_dir: &rustest_fixtures::TempDir) -> Utf8PathBuf {
+    temp_dir
+        .path()
+        .to_path_buf()
+        .try_into()
+        .expect("We expect an utf8 path")
+}
+
+// A creator of a container at `path`, without compression.
+fn basic_creator(path: &Utf8Path, concat_mode: creator::ConcatMode) -> creator::BasicCreator {
+    creator::BasicCreator::new(
+        path,
+        concat_mode,
+        jubako::VendorId::from([1, 0, 0, 0]),
+        creator::Compression::None,
+        Arc::new(()),
+    )
+    .unwrap()
+}
+
+fn add_content_entry_store(
+    contents: Vec<jubako::ContentAddress>,
+    entry_checked: bool,
+    directory_pack: &mut creator::DirectoryPackCreator,
+) -> creator::Result<()> {
+    type SimpleEntry = creator::SimpleEntry<&'static str, ()>;
+    let entry_def = schema::Schema::<&str, ()>::new(
+        schema::CommonProperties::new(vec![schema::Property::new_content_address("C")]),
+        vec![],
+        None,
+    );
+    let count = contents.len();
+    let entries = contents.into_iter().map(|content_address| -> SimpleEntry {
+        HashMap::from([("C", jubako::Value::Content(content_address))]).into()
+    });
+    let mut entry_store = creator::EntryStore::new(entry_def, entries)?;
+    entry_store.set_entry_checked(entry_checked);
+    let entry_store_idx = directory_pack.add_entry_store(entry_store);
+    directory_pack.create_range_index(
+        "Contents",
+        Default::default(),
+        entry_store_idx,
+        (count as u32).into(),
+        jubako::EntryIdx::from(0),
+    );
+    Ok(())
+}
+
+fn read_contents(container: &jubako::reader::Container) -> Vec<String> {
+    let index = container
+        .get_index_for_name("Contents")
+        .unwrap()
+        .expect("Contents index exists");
+    let builder = jubako::reader::builder::AnyBuilder::new(
+        index.get_store(container.get_entry_storage()).unwrap(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    index
+        .count()
+        .into_iter()
+        .map(|i| {
+            let entry = index.get_entry(&builder, i).unwrap().unwrap();
+            let content_address = entry.get_value("C").unwrap().unwrap().as_content();
+            let mut stream = container
+                .get_bytes(content_address)
+                .unwrap()
+                .and_then(|m| m.transpose())
+                .expect("content address should be valid")
+                .unwrap()
+                .stream();
+            let mut content = String::new();
+            stream.read_to_string(&mut content).unwrap();
+            content
+        })
+        .collect()
+}
+
+#[test]
+fn test_incremental_creator(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let base_path = utf8_temp_dir.join("base.jbk");
+    let incr_path = utf8_temp_dir.join("incremental.jbk");
+    let vendor_id = jubako::VendorId::from([1, 0, 0, 0]);
+
+    let mut base_creator = basic_creator(&base_path, creator::ConcatMode::OneFile);
+    let base_addresses = ["foo", "foo bar"]
+        .into_iter()
+        .map(|c| {
+            base_creator
+                .add_content(Box::new(std::io::Cursor::new(c)), Default::default())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    base_creator
+        .finalize(Box::new(ContentEntryStore(base_addresses.clone())), vec![])
+        .unwrap();
+
+    let mut incr_creator = creator::IncrementalCreator::new(
+        &base_path,
+        &incr_path,
+        creator::ConcatMode::OneFile,
+        vendor_id,
+        creator::Compression::None,
+        Arc::new(()),
+    )
+    .unwrap();
+    let incr_addresses = ["foo bar", "baz", "baz"]
+        .into_iter()
+        .map(|c| {
+            incr_creator
+                .add_content(Box::new(std::io::Cursor::new(c)), Default::default())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    // Existing content is reused from base container
+    assert_eq!(incr_addresses[0], base_addresses[1]);
+    // New content is added once in a new content pack
+    assert_eq!(incr_addresses[1], incr_addresses[2]);
+    assert_eq!(incr_addresses[1].pack_id, jubako::PackId::from(2));
+    incr_creator
+        .finalize(Box::new(ContentEntryStore(incr_addresses)), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&incr_path).unwrap();
+    assert_eq!(container.pack_count(), 3.into());
+    assert!(container.check().unwrap());
+    assert_eq!(read_contents(&container), ["foo bar", "baz", "baz"]);
+
+    // Base container is untouched
+    let container = jubako::reader::Container::new(&base_path).unwrap();
+    assert!(container.check().unwrap());
+    assert_eq!(read_contents(&container), ["foo", "foo bar"]);
+}
+
+#[test]
+fn test_fs_locator_in_container(temp_dir: rustest_fixtures::TempDir) {
+    use jubako::reader::PackLocatorTrait;
+    use jubako::Pack;
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let base_path = utf8_temp_dir.join("base.jbk");
+
+    let mut base_creator = basic_creator(&base_path, creator::ConcatMode::OneFile);
+    let base_address = base_creator
+        .add_content(Box::new(std::io::Cursor::new("foo")), Default::default())
+        .unwrap();
+    base_creator
+        .finalize(Box::new(ContentEntryStore(vec![base_address])), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&base_path).unwrap();
+    let content_pack_uuid = container.get_manifest_pack().get_pack_infos()[0].uuid;
+    let locator = jubako::reader::FsLocator::new(utf8_temp_dir.clone().into_std_path_buf());
+    // The located file is the container, the pack reader is the content pack inside it.
+    let reader = locator
+        .locate(content_pack_uuid, "base.jbk")
+        .unwrap()
+        .expect("base.jbk should exist");
+    let content_pack = jubako::reader::ContentPack::new(reader).unwrap();
+    assert_eq!(content_pack.uuid(), content_pack_uuid);
+    assert!(locator
+        .locate(uuid::Uuid::new_v4(), "base.jbk")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_incremental_creator_extra_pack_id(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let base_path = utf8_temp_dir.join("base.jbk");
+    let vendor_id = jubako::VendorId::from([1, 0, 0, 0]);
+
+    let mut base_creator = basic_creator(&base_path, creator::ConcatMode::OneFile);
+    let base_address = base_creator
+        .add_content(Box::new(std::io::Cursor::new("foo")), Default::default())
+        .unwrap();
+    base_creator
+        .finalize(Box::new(ContentEntryStore(vec![base_address])), vec![])
+        .unwrap();
+
+    // Pack 1 is reused from the base container and pack 2 is the new content pack.
+    for pack_id in [1, 2] {
+        let incr_path = utf8_temp_dir.join(format!("incremental_{pack_id}.jbk"));
+        let incr_creator = creator::IncrementalCreator::new(
+            &base_path,
+            &incr_path,
+            creator::ConcatMode::OneFile,
+            vendor_id,
+            creator::Compression::None,
+            Arc::new(()),
+        )
+        .unwrap();
+        let extra_file: Box<dyn creator::PackRecipient> =
+            creator::AtomicOutFile::new(utf8_temp_dir.join(format!("extra_{pack_id}.jbkc")))
+                .unwrap();
+        let extra_creator = creator::ContentPackCreator::new_from_output(
+            extra_file,
+            jubako::PackId::from(pack_id),
+            vendor_id,
+            Default::default(),
+            creator::Compression::None,
+        )
+        .unwrap();
+        let err = incr_creator
+            .finalize(
+                Box::new(ContentEntryStore(vec![base_address])),
+                vec![extra_creator],
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, creator::Error::Io(e) if e.kind() == std::io::ErrorKind::InvalidInput),
+            "{err:?}"
+        );
+        assert!(!incr_path.exists());
+    }
+}
+
+#[test]
+fn test_container_from_bytes(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let create = |path: &Utf8Path, concat_mode| {
+        let mut creator = basic_creator(path, concat_mode);
+        let addresses = ["foo", "bar"]
+            .into_iter()
+            .map(|c| {
+                creator
+                    .add_content(Box::new(std::io::Cursor::new(c)), Default::default())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        creator
+            .finalize(Box::new(ContentEntryStore(addresses)), vec![])
+            .unwrap();
+    };
+    let path = utf8_temp_dir.join("one_file.jbk");
+    create(&path, creator::ConcatMode::OneFile);
+    let bytes = std::fs::read(&path).unwrap();
+
+    let container = jubako::reader::Container::from_bytes(bytes.clone()).unwrap();
+    assert_eq!(read_contents(&container), ["foo", "bar"]);
+    let container =
+        jubako::reader::Container::from_bytes(Arc::<[u8]>::from(bytes.clone())).unwrap();
+    assert_eq!(read_contents(&container), ["foo", "bar"]);
+    let static_bytes: &'static [u8] = bytes.leak();
+    let container = jubako::reader::Container::from_bytes(static_bytes).unwrap();
+    assert!(container.check().unwrap());
+    assert_eq!(read_contents(&container), ["foo", "bar"]);
+
+    // Other packs are found with the locator.
+    let path = utf8_temp_dir.join("two_files.jbk");
+    create(&path, creator::ConcatMode::TwoFiles);
+    let reader = jubako::Reader::from(std::fs::read(&path).unwrap());
+    let locator = Arc::new(jubako::reader::FsLocator::new(utf8_temp_dir.clone().into()));
+    let container = jubako::reader::Container::from_reader(reader, locator).unwrap();
+    assert_eq!(read_contents(&container), ["foo", "bar"]);
+
+    // Without locator, content pack is missing.
+    let container = jubako::reader::Container::from_bytes(std::fs::read(&path).unwrap()).unwrap();
+    let content_address = jubako::ContentAddress::new(1.into(), 0.into());
+    assert!(matches!(
+        container.get_bytes(content_address).unwrap(),
+        Some(jubako::reader::MayMissPack::MISSING(_))
+    ));
+
+    // Directory pack cannot be found.
+    let path = utf8_temp_dir.join("no_concat.jbk");
+    create(&path, creator::ConcatMode::NoConcat);
+    let result = jubako::reader::Container::from_bytes(std::fs::read(&path).unwrap());
+    assert!(result.is_err());
+}
+
+// A source keeping a file in memory and counting the reads.
+#[derive(Debug)]
+struct MemorySource {
+    data: Vec<u8>,
+    reads: std::sync::atomic::AtomicUsize,
+}
+
+impl jubako::SourceTrait for MemorySource {
+    fn size(&self) -> jubako::Size {
+        self.data.len().into()
+    }
+
+    fn read_at(&self, offset: jubako::Offset, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reads
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let mut data = &self.data[offset.into_u64() as usize..];
+        data.read(buf)
+    }
+}
+
+// Locate packs in memory sources, by their location.
+struct MemoryLocator(HashMap<String, Arc<MemorySource>>);
+
+impl jubako::reader::PackLocatorTrait for MemoryLocator {
+    fn locate(&self, uuid: uuid::Uuid, helper: &str) -> jubako::Result<Option<jubako::Reader>> {
+        match self.0.get(helper) {
+            None => Ok(None),
+            Some(source) => {
+                let reader = jubako::Reader::from_source(Arc::clone(source));
+                Ok(jubako::reader::open_as_container_pack(reader)?.get_pack_reader(&uuid))
+            }
+        }
+    }
+}
+
+#[test]
+fn test_custom_source(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("custom.jbk");
+    let mut creator = basic_creator(&path, creator::ConcatMode::TwoFiles);
+    let addresses = ["foo", "bar"]
+        .into_iter()
+        .map(|c| {
+            creator
+                .add_content(Box::new(std::io::Cursor::new(c)), Default::default())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    creator
+        .finalize(Box::new(ContentEntryStore(addresses)), vec![])
+        .unwrap();
+
+    let memory_source = |name: &str| {
+        Arc::new(MemorySource {
+            data: std::fs::read(utf8_temp_dir.join(name)).unwrap(),
+            reads: Default::default(),
+        })
+    };
+    let main_source = memory_source("custom.jbk");
+    let content_source = memory_source("custom.jbkc");
+    let locator = MemoryLocator(HashMap::from([(
+        "custom.jbkc".to_string(),
+        Arc::clone(&content_source),
+    )]));
+    let container = jubako::reader::Container::from_reader(
+        jubako::Reader::from_source(Arc::clone(&main_source)),
+        Arc::new(locator),
+    )
+    .unwrap();
+    assert_eq!(read_contents(&container), ["foo", "bar"]);
+    assert!(container.check().unwrap());
+    assert!(main_source.reads.load(std::sync::atomic::Ordering::Relaxed) > 0);
+    assert!(
+        content_source
+            .reads
+            .load(std::sync::atomic::Ordering::Relaxed)
+            > 0
+    );
+}
+
+#[test]
+fn test_set_location(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("set_location.jbk");
+    let mut creator = basic_creator(&path, creator::ConcatMode::TwoFiles);
+    let address = creator
+        .add_content(Box::new(std::io::Cursor::new("foo")), Default::default())
+        .unwrap();
+    creator
+        .finalize(Box::new(ContentEntryStore(vec![address])), vec![])
+        .unwrap();
+    let content_uuid = jubako::reader::Container::new(&path)
+        .unwrap()
+        .get_manifest_pack()
+        .get_content_pack_info(address.pack_id)
+        .unwrap()
+        .uuid;
+
+    let old = jubako::tools::set_location(&path, content_uuid, "moved.jbkc".into()).unwrap();
+    assert_eq!(
+        old,
+        Some((
+            jubako::reader::PackKind::Content,
+            "set_location.jbkc".into()
+        ))
+    );
+    std::fs::rename(
+        utf8_temp_dir.join("set_location.jbkc"),
+        utf8_temp_dir.join("moved.jbkc"),
+    )
+    .unwrap();
+    let container = jubako::reader::Container::new(&path).unwrap();
+    let pack_info = container
+        .get_manifest_pack()
+        .get_content_pack_info(address.pack_id)
+        .unwrap();
+    assert_eq!(pack_info.pack_location.as_str(), "moved.jbkc");
+    assert_eq!(read_contents(&container), ["foo"]);
+    assert!(container.check().unwrap());
+
+    // Unknown pack
+    assert_eq!(
+        jubako::tools::set_location(&path, uuid::Uuid::nil(), "other.jbkc".into()).unwrap(),
+        None
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_pack_location(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("location.jbk");
+    let mut creator = basic_creator(&path, creator::ConcatMode::TwoFiles);
+    let addresses = ["foo", "bar"]
+        .into_iter()
+        .map(|c| {
+            creator
+                .add_content(Box::new(std::io::Cursor::new(c)), Default::default())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    creator
+        .finalize(Box::new(ContentEntryStore(addresses.clone())), vec![])
+        .unwrap();
+    let content_uuid = jubako::reader::Container::new(&path)
+        .unwrap()
+        .get_manifest_pack()
+        .get_content_pack_info(addresses[0].pack_id)
+        .unwrap()
+        .uuid;
+
+    // Move the content pack in a directory with spaces in its name.
+    let pack_dir = utf8_temp_dir.join("my packs");
+    std::fs::create_dir(&pack_dir).unwrap();
+    let pack_path = pack_dir.join("content pack.jbkc");
+    std::fs::rename(utf8_temp_dir.join("location.jbkc"), &pack_path).unwrap();
+
+    let set_location = |location: &str| {
+        jubako::tools::set_location(&path, content_uuid, location.into())
+            .unwrap()
+            .unwrap();
+        jubako::reader::Container::new(&path).unwrap()
+    };
+
+    for location in [
+        "my%20packs/content%20pack.jbkc".to_string(),
+        pack_path.to_string(),
+        format!("file://{}", pack_path.as_str().replace(' ', "%20")),
+        format!("file://localhost{}", pack_path.as_str().replace(' ', "%20")),
+    ] {
+        let container = set_location(&location);
+        assert_eq!(read_contents(&container), ["foo", "bar"], "{location}");
+    }
+
+    // Not percent-encoded location is still found.
+    let container = set_location("my packs/content pack.jbkc");
+    assert_eq!(read_contents(&container), ["foo", "bar"]);
+
+    // Absent pack
+    let container = set_location("file:///nowhere/content.jbkc");
+    assert!(matches!(
+        container.get_bytes(addresses[0]).unwrap(),
+        Some(jubako::reader::MayMissPack::MISSING(_))
+    ));
+
+    // Not a local location
+    for location in [
+        "ftp://example.com/content.jbkc",
+        "http://example.com/content.jbkc",
+    ] {
+        let container = set_location(location);
+        assert!(
+            matches!(
+                container.get_bytes(addresses[0]).unwrap(),
+                Some(jubako::reader::MayMissPack::MISSING(_))
+            ),
+            "{location}"
+        );
+    }
+
+    // Invalid location
+    let container = set_location("file://example.com/content.jbkc");
+    let err = container.get_bytes(addresses[0]).err().unwrap();
+    assert!(matches!(*err, jubako::ErrorKind::Io(_)));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_location_round_trip(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    for (name, location) in [
+        ("50%25", "50%2525.jbkc"),
+        ("%FF", "%25FF.jbkc"),
+        ("a:b", "a%3Ab.jbkc"),
+        ("my pack#1", "my%20pack%231.jbkc"),
+    ] {
+        let base_path = utf8_temp_dir.join(format!("{name}.jbk"));
+        let mut creator = basic_creator(&base_path, creator::ConcatMode::TwoFiles);
+        let addresses = ["foo", "bar"]
+            .into_iter()
+            .map(|c| {
+                creator
+                    .add_content(Box::new(std::io::Cursor::new(c)), Default::default())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        creator
+            .finalize(Box::new(ContentEntryStore(addresses.clone())), vec![])
+            .unwrap();
+        let container = jubako::reader::Container::new(&base_path).unwrap();
+        assert_eq!(read_contents(&container), ["foo", "bar"], "{name}");
+        assert!(container.check().unwrap(), "{name}");
+
+        // Reused packs are located from the incremental container.
+        let incr_path = utf8_temp_dir.join(format!("{name}.incr.jbk"));
+        let mut incr_creator = creator::IncrementalCreator::new(
+            &base_path,
+            &incr_path,
+            creator::ConcatMode::OneFile,
+            jubako::VendorId::from([1, 0, 0, 0]),
+            creator::Compression::None,
+            Arc::new(()),
+        )
+        .unwrap();
+        let incr_addresses = ["bar", "baz"]
+            .into_iter()
+            .map(|c| {
+                incr_creator
+                    .add_content(Box::new(std::io::Cursor::new(c)), Default::default())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(incr_addresses[0], addresses[1]);
+        incr_creator
+            .finalize(Box::new(ContentEntryStore(incr_addresses)), vec![])
+            .unwrap();
+        let container = jubako::reader::Container::new(&incr_path).unwrap();
+        assert_eq!(read_contents(&container), ["bar", "baz"], "{name}");
+
+        // Containers written with raw (not percent-encoded) locations are still readable.
+        let content_pack_info = jubako::reader::Container::new(&base_path)
+            .unwrap()
+            .get_manifest_pack()
+            .get_content_pack_info(addresses[0].pack_id)
+            .unwrap()
+            .clone();
+        assert_eq!(content_pack_info.pack_location.as_str(), location);
+        jubako::tools::set_location(
+            &base_path,
+            content_pack_info.uuid,
+            format!("{name}.jbkc").into(),
+        )
+        .unwrap()
+        .unwrap();
+        let container = jubako::reader::Container::new(&base_path).unwrap();
+        assert_eq!(read_contents(&container), ["foo", "bar"], "{name}");
+    }
+}
+
+// The number of requests and bytes served for each file by `serve_http`.
+#[cfg(feature = "http")]
+type ServedFiles = Arc<std::sync::Mutex<HashMap<String, (usize, usize)>>>;
+
+// A minimal http server serving the files of `dir`, with support of range requests.
+// Returns the base url of the server and the files served.
+#[cfg(feature = "http")]
+fn serve_http(dir: PathBuf) -> (String, ServedFiles) {
+    use std::io::{BufRead, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/", listener.local_addr().unwrap());
+    let served = Arc::new(std::sync::Mutex::new(HashMap::new()));
+    let served_by_server = Arc::clone(&served);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let dir = dir.clone();
+            let served = Arc::clone(&served_by_server);
+            std::thread::spawn(move || {
+                let mut request = std::io::BufReader::new(&stream);
+                let mut line = String::new();
+                request.read_line(&mut line).unwrap();
+                let name = line.split(' ').nth(1).unwrap().trim_start_matches('/');
+                let name = name.to_string();
+                let mut range = None;
+                loop {
+                    line.clear();
+                    request.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((header, value)) = line.split_once(':') {
+                        if header.eq_ignore_ascii_case("range") {
+                            let (first, last) = value
+                                .trim()
+                                .strip_prefix("bytes=")
+                                .unwrap()
+                                .split_once('-')
+                                .unwrap();
+                            range = Some((
+                                first.parse::<usize>().unwrap(),
+                                last.parse::<usize>().unwrap(),
+                            ));
+                        }
+                    }
+                }
+                let Ok(data) = std::fs::read(dir.join(&name)) else {
+                    write!(
+                        stream,
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    )
+                    .unwrap();
+                    return;
+                };
+                let (first, last) = range.expect("Client should use range requests");
+                let last = std::cmp::min(last, data.len() - 1);
+                let body = &data[first..=last];
+                // Count before answering, so the client sees the count of its own requests.
+                {
+                    let mut served = served.lock().unwrap();
+                    let (requests, bytes) = served.entry(name).or_default();
+                    *requests += 1;
+                    *bytes += body.len();
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {first}-{last}/{}\r\nConnection: close\r\n\r\n",
+                    body.len(),
+                    data.len()
+                )
+                .unwrap();
+                stream.write_all(body).unwrap();
+            });
+        }
+    });
+    (base_url, served)
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_http_locator(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("http.jbk");
+    let mut creator = basic_creator(&path, creator::ConcatMode::TwoFiles);
+    // Contents of 64KB, so the content pack is far bigger than a http block.
+    let contents = (0..32)
+        .map(|i| format!("{i:04}").repeat(16 * 1024))
+        .collect::<Vec<_>>();
+    let addresses = contents
+        .iter()
+        .map(|c| {
+            creator
+                .add_content(
+                    Box::new(std::io::Cursor::new(c.clone())),
+                    Default::default(),
+                )
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    creator
+        .finalize(Box::new(ContentEntryStore(addresses.clone())), vec![])
+        .unwrap();
+
+    let (base_url, served) = serve_http(temp_dir.path().to_path_buf());
+    let container =
+        jubako::reader::Container::from_url(format!("{base_url}http.jbk").parse().unwrap())
+            .unwrap();
+
+    // Reading one content downloads only a small part of the content pack.
+    let mut content = String::new();
+    container
+        .get_bytes(addresses[5])
+        .unwrap()
+        .and_then(|m| m.transpose())
+        .expect("content address should be valid")
+        .unwrap()
+        .stream()
+        .read_to_string(&mut content)
+        .unwrap();
+    assert_eq!(content, contents[5]);
+    let content_pack_size = std::fs::metadata(utf8_temp_dir.join("http.jbkc"))
+        .unwrap()
+        .len() as usize;
+    let (_, content_pack_served) = served.lock().unwrap()["http.jbkc"];
+    assert!(content_pack_served < content_pack_size / 4);
+
+    assert_eq!(read_contents(&container), contents);
+    assert!(container.check().unwrap());
+
+    // A big read is done with one request, even if some of its blocks are cached.
+    use jubako::SourceTrait;
+    let source = jubako::reader::HttpSource::open(format!("{base_url}http.jbkc").parse().unwrap())
+        .unwrap()
+        .unwrap();
+    let block_size = jubako::reader::HTTP_BLOCK_SIZE;
+    let mut block = vec![0; 16];
+    source
+        .read_exact_at(jubako::Offset::from(2 * block_size + 5), &mut block)
+        .unwrap();
+    let (requests_before, _) = served.lock().unwrap()["http.jbkc"];
+    let mut buf = vec![0; 5 * block_size as usize];
+    source
+        .read_exact_at(jubako::Offset::from(block_size + 10), &mut buf)
+        .unwrap();
+    let (requests_after, _) = served.lock().unwrap()["http.jbkc"];
+    assert_eq!(requests_after, requests_before + 1);
+    let pack_data = std::fs::read(utf8_temp_dir.join("http.jbkc")).unwrap();
+    let begin = block_size as usize + 10;
+    assert_eq!(buf, pack_data[begin..begin + buf.len()]);
+    assert_eq!(block, pack_data[2 * block_size as usize + 5..][..16]);
+
+    // Local container referencing a pack on the http server.
+    let content_uuid = container
+        .get_manifest_pack()
+        .get_content_pack_info(addresses[0].pack_id)
+        .unwrap()
+        .uuid;
+    jubako::tools::set_location(&path, content_uuid, format!("{base_url}http.jbkc").into())
+        .unwrap()
+        .unwrap();
+    let locator = jubako::reader::ChainedLocator::new(vec![
+        Arc::new(jubako::reader::FsLocator::new(utf8_temp_dir.clone().into())),
+        Arc::new(jubako::reader::HttpLocator::new(base_url.parse().unwrap())),
+    ]);
+    let container = jubako::reader::Container::new_with_locator(&path, Arc::new(locator)).unwrap();
+    assert_eq!(read_contents(&container), contents);
+    // The local locator doesn't locate http packs.
+    let container = jubako::reader::Container::new(&path).unwrap();
+    assert!(matches!(
+        container.get_bytes(addresses[0]).unwrap(),
+        Some(jubako::reader::MayMissPack::MISSING(_))
+    ));
+
+    // Missing content pack
+    std::fs::rename(
+        utf8_temp_dir.join("http.jbkc"),
+        utf8_temp_dir.join("moved.jbkc"),
+    )
+    .unwrap();
+    let container =
+        jubako::reader::Container::from_url(format!("{base_url}http.jbk").parse().unwrap())
+            .unwrap();
+    assert!(matches!(
+        container.get_bytes(addresses[0]).unwrap(),
+        Some(jubako::reader::MayMissPack::MISSING(_))
+    ));
+
+    // Missing container
+    let err =
+        jubako::reader::Container::from_url(format!("{base_url}missing.jbk").parse().unwrap())
+            .err()
+            .unwrap();
+    assert!(matches!(*err, jubako::ErrorKind::Io(_)));
+}
+
+#[test]
+fn test_check_with_manifest(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let base_path = utf8_temp_dir.join("base.jbk");
+    let vendor_id = jubako::VendorId::from([1, 0, 0, 0]);
+
+    let mut base_creator = basic_creator(&base_path, creator::ConcatMode::TwoFiles);
+    let content_address = base_creator
+        .add_content(Box::new(std::io::Cursor::new("foo")), Default::default())
+        .unwrap();
+    base_creator
+        .finalize(Box::new(ContentEntryStore(vec![content_address])), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&base_path).unwrap();
+    assert!(container.check().unwrap());
+
+    // Create a new manifest where the content pack is associated to the check info
+    // of the directory pack.
+    let manifest_pack = container.get_manifest_pack();
+    let directory_pack_info = manifest_pack.get_directory_pack_info().clone();
+    let content_pack_info = manifest_pack.get_pack_infos()[0].clone();
+    let pack_data = |pack_info: &jubako::reader::PackInfo, check_uuid| creator::PackData {
+        uuid: pack_info.uuid,
+        pack_size: pack_info.pack_size,
+        pack_kind: pack_info.pack_kind,
+        pack_id: pack_info.pack_id,
+        free_data: vec![],
+        check_info: manifest_pack
+            .get_pack_check_info(check_uuid)
+            .unwrap()
+            .unwrap(),
+    };
+    let mut manifest_creator = creator::ManifestPackCreator::new(vendor_id, Default::default());
+    manifest_creator.add_pack(
+        pack_data(&directory_pack_info, directory_pack_info.uuid),
+        "base.jbk",
+    );
+    manifest_creator.add_pack(
+        pack_data(&content_pack_info, directory_pack_info.uuid),
+        "base.jbkc",
+    );
+    let tampered_path = utf8_temp_dir.join("tampered.jbkm");
+    let mut tampered_file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&tampered_path)
+        .unwrap();
+    manifest_creator.finalize(&mut tampered_file).unwrap();
+
+    let container = jubako::reader::Container::new(&tampered_path).unwrap();
+    let manifest_pack = container.get_manifest_pack();
+    let directory_pack_info = manifest_pack.get_directory_pack_info();
+    let content_pack_info = &manifest_pack.get_pack_infos()[0];
+    assert!(container.check_pack(directory_pack_info).unwrap().is_ok());
+    let report = container.check_pack(content_pack_info).unwrap();
+    assert!(!report.is_ok());
+    assert_eq!(report.uuid, content_pack_info.uuid);
+    assert_eq!(report.kind, Some(jubako::reader::PackKind::Content));
+    assert_eq!(report.location.as_deref(), Some("base.jbkc"));
+    assert!(report.found);
+    assert_eq!(report.header_ok, Some(true));
+    assert_eq!(report.tail_check_ok, Some(true));
+    assert_eq!(report.manifest_check_ok, Some(false));
+    assert!(report.block_failures.is_empty());
+    assert_eq!(report.error, None);
+    assert!(!container.check().unwrap());
+    let report = container.check_report().unwrap();
+    assert_eq!(report.packs.len(), 3);
+    assert_eq!(
+        report.failures().map(|p| p.uuid).collect::<Vec<_>>(),
+        [content_pack_info.uuid]
+    );
+}
+
+#[test]
+fn test_check_report_corrupted_content(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let base_path = utf8_temp_dir.join("base.jbk");
+    let content = b"A content which will be corrupted";
+
+    let mut base_creator = basic_creator(&base_path, creator::ConcatMode::TwoFiles);
+    let content_address = base_creator
+        .add_content(Box::new(std::io::Cursor::new(content)), Default::default())
+        .unwrap();
+    base_creator
+        .finalize(Box::new(ContentEntryStore(vec![content_address])), vec![])
+        .unwrap();
+
+    // Corrupt the content in the content pack
+    let content_pack_path = utf8_temp_dir.join("base.jbkc");
+    let mut data = std::fs::read(&content_pack_path).unwrap();
+    let content_offset = data
+        .windows(content.len())
+        .position(|w| w == content)
+        .expect("Content is stored uncompressed");
+    data[content_offset] ^= 0xFF;
+    std::fs::write(&content_pack_path, data).unwrap();
+
+    let container = jubako::reader::Container::new(&base_path).unwrap();
+    assert!(!container.check().unwrap());
+    let report = container.check_report().unwrap();
+    let failures = report.failures().collect::<Vec<_>>();
+    assert_eq!(failures.len(), 1);
+    let failure = failures[0];
+    assert_eq!(failure.kind, Some(jubako::reader::PackKind::Content));
+    assert_eq!(failure.location.as_deref(), Some("base.jbkc"));
+    assert!(failure.found);
+    assert_eq!(failure.header_ok, Some(true));
+    assert_eq!(failure.tail_check_ok, Some(false));
+    assert_eq!(failure.manifest_check_ok, Some(false));
+    assert!(failure.block_failures.is_empty());
+}
+
+#[test]
+fn test_check_kinds(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let base_path = utf8_temp_dir.join("base.jbk");
+    let content = b"A content which will be corrupted";
+
+    let mut base_creator = basic_creator(&base_path, creator::ConcatMode::TwoFiles);
+    base_creator.set_check_kinds(vec![
+        creator::CheckKind::Sha256,
+        creator::CheckKind::Xxh3,
+        creator::CheckKind::Size,
+    ]);
+    let content_address = base_creator
+        .add_content(Box::new(std::io::Cursor::new(content)), Default::default())
+        .unwrap();
+    base_creator
+        .finalize(Box::new(ContentEntryStore(vec![content_address])), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&base_path).unwrap();
+    assert!(container.check().unwrap());
+    assert_eq!(
+        read_contents(&container),
+        ["A content which will be corrupted"]
+    );
+    drop(container);
+
+    // Corrupt the content in the content pack
+    let content_pack_path = utf8_temp_dir.join("base.jbkc");
+    let mut data = std::fs::read(&content_pack_path).unwrap();
+    let content_offset = data
+        .windows(content.len())
+        .position(|w| w == content)
+        .expect("Content is stored uncompressed");
+    data[content_offset] ^= 0xFF;
+    std::fs::write(&content_pack_path, data).unwrap();
+
+    let container = jubako::reader::Container::new(&base_path).unwrap();
+    let report = container.check_report().unwrap();
+    let failures = report.failures().collect::<Vec<_>>();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].kind, Some(jubako::reader::PackKind::Content));
+    assert_eq!(failures[0].tail_check_ok, Some(false));
+    assert_eq!(failures[0].manifest_check_ok, Some(false));
+}
+
+#[cfg(feature = "signature")]
+#[test]
+fn test_signature(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let signing_key = jubako::SigningKey::from_bytes(&[0x42; 32]);
+    let other_key = jubako::SigningKey::from_bytes(&[0x24; 32]);
+
+    let create = |path: &Utf8Path, signer: Option<&jubako::SigningKey>| {
+        let mut creator = basic_creator(path, creator::ConcatMode::OneFile);
+        if let Some(signer) = signer {
+            creator.set_signer(Arc::new(signer.clone()));
+        }
+        let content_address = creator
+            .add_content(Box::new(std::io::Cursor::new("foo")), Default::default())
+            .unwrap();
+        creator
+            .finalize(Box::new(ContentEntryStore(vec![content_address])), vec![])
+            .unwrap();
+        jubako::reader::Container::new(path).unwrap()
+    };
+
+    let signed = create(&utf8_temp_dir.join("signed.jbk"), Some(&signing_key));
+    assert!(signed.check().unwrap());
+    assert_eq!(read_contents(&signed), ["foo"]);
+    assert!(signed
+        .verify_signature(&signing_key.verifying_key())
+        .unwrap());
+    assert!(!signed.verify_signature(&other_key.verifying_key()).unwrap());
+
+    let unsigned = create(&utf8_temp_dir.join("unsigned.jbk"), None);
+    assert!(unsigned.check().unwrap());
+    assert!(!unsigned
+        .verify_signature(&signing_key.verifying_key())
+        .unwrap());
+}
+
+#[test]
+fn test_entry_checked_store(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("checked.jbk");
+    let mut creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    let contents = ["foo", "bar", "baz"];
+    let addresses = contents
+        .iter()
+        .map(|c| {
+            creator
+                .add_content(Box::new(std::io::Cursor::new(*c)), Default::default())
+                .unwrap()
+        })
+        .collect();
+    creator
+        .finalize(Box::new(CheckedContentEntryStore(addresses)), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    assert!(container.check().unwrap());
+    assert_eq!(read_contents(&container), contents);
+}
+
+#[derive(Default)]
+struct CheckProgressCounter {
+    // uuid => (pack size, hashed size, hashed call count, ended)
+    packs: std::sync::Mutex<HashMap<uuid::Uuid, (u64, u64, usize, bool)>>,
+}
+
+impl jubako::reader::CheckProgress for CheckProgressCounter {
+    fn start_pack(&self, uuid: uuid::Uuid, size: jubako::Size) {
+        let old = self
+            .packs
+            .lock()
+            .unwrap()
+            .insert(uuid, (size.into_u64(), 0, 0, false));
+        assert!(old.is_none());
+    }
+    fn hashed(&self, uuid: uuid::Uuid, size: jubako::Size) {
+        let mut packs = self.packs.lock().unwrap();
+        let pack = packs.get_mut(&uuid).unwrap();
+        pack.1 += size.into_u64();
+        pack.2 += 1;
+    }
+    fn end_pack(&self, report: &jubako::reader::PackCheckReport) {
+        let mut packs = self.packs.lock().unwrap();
+        let pack = packs.get_mut(&report.uuid).unwrap();
+        assert!(!pack.3);
+        pack.3 = true;
+    }
+}
+
+#[test]
+fn test_check_progress(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let base_path = utf8_temp_dir.join("base.jbk");
+    // Big enough to be hashed in several chunks.
+    let content = vec![0x42_u8; 20 * 1024 * 1024];
+
+    let mut base_creator = basic_creator(&base_path, creator::ConcatMode::NoConcat);
+    let content_address = base_creator
+        .add_content(Box::new(std::io::Cursor::new(content)), Default::default())
+        .unwrap();
+    base_creator
+        .finalize(Box::new(ContentEntryStore(vec![content_address])), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&base_path).unwrap();
+    let progress = CheckProgressCounter::default();
+    let report = container.check_report_with_progress(&progress).unwrap();
+    assert!(report.is_ok());
+    assert_eq!(report.packs.len(), 3);
+
+    let packs = progress.packs.into_inner().unwrap();
+    assert_eq!(packs.len(), 3);
+    for pack_report in &report.packs {
+        let (size, hashed, hashed_count, ended) = packs[&pack_report.uuid];
+        assert!(ended);
+        assert!(hashed > 0 && hashed <= size);
+        if pack_report.kind == Some(jubako::reader::PackKind::Content) {
+            assert!(hashed > 20 * 1024 * 1024);
+            assert!(hashed_count > 1);
+        }
+    }
+}
+
+#[test]
+fn test_nested_container(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let inner_path = utf8_temp_dir.join("inner.jbk");
+
+    let mut creator = basic_creator(&inner_path, creator::ConcatMode::OneFile);
+    let content_address = creator
+        .add_content(Box::new(std::io::Cursor::new("foo")), Default::default())
+        .unwrap();
+    creator
+        .finalize(Box::new(ContentEntryStore(vec![content_address])), vec![])
+        .unwrap();
+
+    // Put the container (and so all its packs) in another container
+    let outer_path = utf8_temp_dir.join("outer.jbk");
+    let mut outer_container =
+        creator::ContainerPackCreator::new(&outer_path, Default::default()).unwrap();
+    outer_container
+        .add_pack(
+            uuid::Uuid::new_v4(),
+            &mut std::fs::File::open(&inner_path).unwrap(),
+        )
+        .unwrap();
+    outer_container.finalize().unwrap();
+
+    let container_pack = jubako::tools::open_pack(&outer_path).unwrap();
+    assert_eq!(container_pack.pack_count(), 3.into());
+    assert!(container_pack.check().unwrap());
+
+    let container = jubako::reader::Container::new(&outer_path).unwrap();
+    assert!(container.check().unwrap());
+    assert_eq!(read_contents(&container), ["foo"]);
+
+    // Concatenation flattens the nested container
+    let concat_path = utf8_temp_dir.join("concat.jbk");
+    jubako::tools::concat(&[&outer_path], &concat_path).unwrap();
+    let container_pack = jubako::tools::open_pack(&concat_path).unwrap();
+    assert_eq!(container_pack.pack_count(), 3.into());
+    let container = jubako::reader::Container::new(&concat_path).unwrap();
+    assert!(container.check().unwrap());
+    assert_eq!(read_contents(&container), ["foo"]);
+}
+
+// A small tree: each entry has a range of children and a redirection to its parent.
+struct TreeEntryStore(Vec<(jubako::EntryRange, jubako::EntryRange)>);
+
+impl creator::EntryStoreCreatorTrait for TreeEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        type SimpleEntry = creator::SimpleEntry<&'static str, ()>;
+        let entry_def = schema::Schema::<&str, ()>::new(
+            schema::CommonProperties::new(vec![
+                schema::Property::new_range("Children"),
+                schema::Property::new_range("Parent"),
+            ]),
+            vec![],
+            None,
+        );
+        let count = self.0.len();
+        let entries = self.0.into_iter().map(|(children, parent)| -> SimpleEntry {
+            HashMap::from([
+                ("Children", jubako::Value::Range(children)),
+                ("Parent", jubako::Value::Range(parent)),
+            ])
+            .into()
+        });
+        let entry_store = creator::EntryStore::new(entry_def, entries)?;
+        let entry_store_idx = directory_pack.add_entry_store(entry_store);
+        directory_pack.create_range_index(
+            "Tree",
+            Default::default(),
+            entry_store_idx,
+            (count as u32).into(),
+            jubako::EntryIdx::from(0),
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn test_range_property(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("tree.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    let range = |begin: u32, count: u32| {
+        jubako::EntryRange::new_from_size(begin.into(), jubako::EntryCount::from(count))
+    };
+    // Root (0) has three children (1, 2, 3). Entry 3 has 300 children (4..304).
+    let mut tree = vec![
+        (range(1, 3), range(0, 1)),
+        (range(304, 0), range(0, 1)),
+        (range(304, 0), range(0, 1)),
+        (range(4, 300), range(0, 1)),
+    ];
+    tree.extend(std::iter::repeat_n((range(304, 0), range(3, 1)), 300));
+    creator
+        .finalize(Box::new(TreeEntryStore(tree.clone())), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    assert!(container.check().unwrap());
+    let index = container
+        .get_index_for_name("Tree")
+        .unwrap()
+        .expect("Tree index exists");
+    let builder = jubako::reader::builder::AnyBuilder::new(
+        index.get_store(container.get_entry_storage()).unwrap(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    assert_eq!(index.count(), jubako::EntryCount::from(304));
+    for (i, (children, parent)) in tree.into_iter().enumerate() {
+        let entry = index
+            .get_entry(&builder, (i as u32).into())
+            .unwrap()
+            .unwrap();
+        let value = entry.get_value("Children").unwrap().unwrap();
+        assert_eq!(value.get().unwrap(), jubako::Value::Range(children));
+        assert_eq!(value.as_range(), children);
+        let value = entry.get_value("Parent").unwrap().unwrap();
+        assert_eq!(value.get().unwrap(), jubako::Value::Range(parent));
+        assert_eq!(value.as_range(), parent);
+    }
+
+    // Walk the tree using the ranges as `Range`.
+    let root = index.get_entry(&builder, 0.into()).unwrap().unwrap();
+    let children = root.get_value("Children").unwrap().unwrap().as_range();
+    for idx in children.count() {
+        let child = children.get_entry(&builder, idx).unwrap().unwrap();
+        let parent = child.get_value("Parent").unwrap().unwrap().as_range();
+        let parent = parent.get_entry(&builder, 0.into()).unwrap().unwrap();
+        assert_eq!(
+            parent.get_value("Children").unwrap().unwrap().as_range(),
+            children
+        );
+    }
+    let comparator =
+        builder.new_property_compare("Children".to_string(), jubako::Value::Range(range(4, 300)));
+    assert_eq!(children.find(&comparator).unwrap(), Some(2.into()));
+    assert_eq!(index.find(&comparator).unwrap(), Some(3.into()));
+    let comparator =
+        builder.new_property_compare("Parent".to_string(), jubako::Value::Range(range(3, 1)));
+    assert_eq!(children.find(&comparator).unwrap(), None);
+    assert_eq!(index.find(&comparator).unwrap(), Some(4.into()));
+}
+
+// Image metadata: a gps coordinate, a ratio and some flags.
+struct MetadataEntryStore(Vec<(f64, f32, bool, bool)>);
+
+impl creator::EntryStoreCreatorTrait for MetadataEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        type SimpleEntry = creator::SimpleEntry<&'static str, ()>;
+        let entry_def = schema::Schema::<&str, ()>::new(
+            schema::CommonProperties::new(vec![
+                schema::Property::new_float("Latitude"),
+                schema::Property::new_float("Ratio"),
+                schema::Property::new_bool("Visible"),
+                schema::Property::new_bool("Favorite"),
+                schema::Property::new_bool("Hidden"),
+            ]),
+            vec![],
+            Some(vec!["Latitude".into()]),
+        );
+        let count = self.0.len();
+        let entries =
+            self.0
+                .into_iter()
+                .map(|(latitude, ratio, visible, favorite)| -> SimpleEntry {
+                    HashMap::from([
+                        ("Latitude", jubako::Value::Float(latitude.into())),
+                        ("Ratio", jubako::Value::Float(ratio.into())),
+                        ("Visible", jubako::Value::Bool(visible)),
+                        ("Favorite", jubako::Value::Bool(favorite)),
+                        ("Hidden", jubako::Value::Bool(false)),
+                    ])
+                    .into()
+                });
+        let entry_store = creator::EntryStore::new(entry_def, entries)?;
+        let entry_store_idx = directory_pack.add_entry_store(entry_store);
+        directory_pack.create_range_index(
+            "Metadata",
+            Default::default(),
+            entry_store_idx,
+            (count as u32).into(),
+            jubako::EntryIdx::from(0),
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn test_float_bool_property(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("metadata.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    // Sorted on latitude (with total ordering)
+    let metadata = vec![
+        (f64::NEG_INFINITY, 1.5, true, false),
+        (-48.858_222, 0.75, false, true),
+        (-0.0, f32::NAN, true, true),
+        (0.0, 1.0, false, false),
+        (45.764_043, -2.5, true, false),
+        (f64::NAN, 1.0 / 3.0, false, true),
+    ];
+    creator
+        .finalize(Box::new(MetadataEntryStore(metadata.clone())), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    assert!(container.check().unwrap());
+    let index = container
+        .get_index_for_name("Metadata")
+        .unwrap()
+        .expect("Metadata index exists");
+    let builder = jubako::reader::builder::AnyBuilder::new(
+        index.get_store(container.get_entry_storage()).unwrap(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    for (i, (latitude, ratio, visible, favorite)) in metadata.into_iter().enumerate() {
+        let entry = index
+            .get_entry(&builder, (i as u32).into())
+            .unwrap()
+            .unwrap();
+        let value = entry.get_value("Latitude").unwrap().unwrap();
+        assert_eq!(value.get().unwrap(), jubako::Value::Float(latitude.into()));
+        let value = entry.get_value("Ratio").unwrap().unwrap();
+        assert_eq!(value.get().unwrap(), jubako::Value::Float(ratio.into()));
+        let value = entry.get_value("Visible").unwrap().unwrap();
+        assert_eq!(value.as_bool(), visible);
+        let value = entry.get_value("Favorite").unwrap().unwrap();
+        assert_eq!(value.as_bool(), favorite);
+        let value = entry.get_value("Hidden").unwrap().unwrap();
+        assert!(!value.as_bool());
+    }
+    let comparator =
+        builder.new_property_compare("Latitude".to_string(), jubako::Value::Float(0.0.into()));
+    assert_eq!(index.find(&comparator).unwrap(), Some(3.into()));
+    let comparator = builder.new_property_compare(
+        "Latitude".to_string(),
+        jubako::Value::Float(f64::NAN.into()),
+    );
+    assert_eq!(index.find(&comparator).unwrap(), Some(5.into()));
+}
+
+// Entries identified by a uuid and a hash.
+struct IdentifiedEntryStore(Vec<([u8; 16], [u8; 32])>);
+
+impl creator::EntryStoreCreatorTrait for IdentifiedEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        type SimpleEntry = creator::SimpleEntry<&'static str, ()>;
+        let entry_def = schema::Schema::<&str, ()>::new(
+            schema::CommonProperties::new(vec![
+                schema::Property::new_fixed_bytes(16, "Uuid"),
+                schema::Property::new_fixed_bytes(32, "Hash"),
+                schema::Property::new_fixed_bytes(4, "Magic"),
+            ]),
+            vec![],
+            None,
+        );
+        let count = self.0.len();
+        let entries = self.0.into_iter().map(|(uuid, hash)| -> SimpleEntry {
+            HashMap::from([
+                ("Uuid", jubako::Value::Array((&uuid).into())),
+                ("Hash", jubako::Value::Array((&hash).into())),
+                ("Magic", jubako::Value::Array("jbk!".into())),
+            ])
+            .into()
+        });
+        let entry_store = creator::EntryStore::new(entry_def, entries)?;
+        let entry_store_idx = directory_pack.add_entry_store(entry_store);
+        directory_pack.create_range_index(
+            "Identified",
+            Default::default(),
+            entry_store_idx,
+            (count as u32).into(),
+            jubako::EntryIdx::from(0),
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn test_fixed_bytes_property(temp_dir: rustest_fixtures::TempDir) {
+    use jubako::reader::builder::{FixedBytesArrayProperty, PropertyBuilderTrait};
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("identified.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    let entries = (0..5_u8)
+        .map(|i| ([i; 16], *blake3::hash(&[i]).as_bytes()))
+        .collect::<Vec<_>>();
+    creator
+        .finalize(Box::new(IdentifiedEntryStore(entries.clone())), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    assert!(container.check().unwrap());
+    let index = container
+        .get_index_for_name("Identified")
+        .unwrap()
+        .expect("Identified index exists");
+    let store = index.get_store(container.get_entry_storage()).unwrap();
+    let builder = jubako::reader::builder::AnyBuilder::new(
+        store.clone(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    let hash_property: FixedBytesArrayProperty<32> = store
+        .layout()
+        .common
+        .get("Hash")
+        .unwrap()
+        .as_builder(container.get_value_storage().as_ref())
+        .unwrap()
+        .expect("Hash is a 32 bytes property");
+    for (i, (uuid, hash)) in entries.iter().enumerate() {
+        let entry = index
+            .get_entry(&builder, (i as u32).into())
+            .unwrap()
+            .unwrap();
+        let value = entry.get_value("Uuid").unwrap().unwrap();
+        assert_eq!(value.as_fixed_bytes(), uuid);
+        let value = entry.get_value("Hash").unwrap().unwrap();
+        assert_eq!(value.as_fixed_bytes(), hash);
+        let value = entry.get_value("Magic").unwrap().unwrap();
+        assert_eq!(value.get().unwrap(), jubako::Value::Array("jbk!".into()));
+
+        let entry_reader = store.get_entry_reader((i as u32).into()).unwrap().unwrap();
+        assert_eq!(&hash_property.create(&entry_reader).unwrap(), hash);
+    }
+    let wrong_size: Option<FixedBytesArrayProperty<16>> = store
+        .layout()
+        .common
+        .get("Hash")
+        .unwrap()
+        .as_builder(container.get_value_storage().as_ref())
+        .unwrap();
+    assert!(wrong_size.is_none());
+
+    let comparator = builder.new_property_compare(
+        "Hash".to_string(),
+        jubako::Value::Array(entries[3].1.as_slice().into()),
+    );
+    assert_eq!(index.find(&comparator).unwrap(), Some(3.into()));
+    let comparator =
+        builder.new_property_compare("Uuid".to_string(), jubako::Value::Array((&[9; 16]).into()));
+    assert_eq!(index.find(&comparator).unwrap(), None);
+}
+
+// Events listed newest first, then by kind.
+// A secondary index lists them by kind.
+struct EventEntryStore(Vec<(u64, u64, u64)>);
+
+impl creator::EntryStoreCreatorTrait for EventEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        type SimpleEntry = creator::SimpleEntry<&'static str, ()>;
+        let mut entry_def = schema::Schema::<&str, ()>::new(
+            schema::CommonProperties::new(vec![
+                schema::Property::new_uint("Id"),
+                schema::Property::new_uint("Date"),
+                schema::Property::new_uint("Kind"),
+            ]),
+            vec![],
+            Some(vec![schema::SortKey::descending("Date"), "Kind".into()]),
+        );
+        entry_def.add_secondary_order("EventsByKind", vec!["Kind".into()]);
+        let count = self.0.len();
+        let entries = self.0.into_iter().map(|(id, date, kind)| -> SimpleEntry {
+            HashMap::from([
+                ("Id", jubako::Value::Unsigned(id)),
+                ("Date", jubako::Value::Unsigned(date)),
+                ("Kind", jubako::Value::Unsigned(kind)),
+            ])
+            .into()
+        });
+        let entry_store = creator::EntryStore::new(entry_def, entries)?;
+        let entry_store_idx = directory_pack.add_entry_store(entry_store);
+        directory_pack.create_range_index(
+            "Events",
+            Default::default(),
+            entry_store_idx,
+            (count as u32).into(),
+            jubako::EntryIdx::from(0),
+        );
+        directory_pack.create_secondary_index("EventsByKind", Default::default(), entry_store_idx);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_sorted_index(temp_dir: rustest_fixtures::TempDir) {
+    use jubako::reader::CompareTrait;
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("events.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    let events = vec![
+        (0, 50, 1),
+        (1, 50, 3),
+        (2, 40, 0),
+        (3, 30, 2),
+        (4, 30, 5),
+        (5, 10, 1),
+        (6, 10, 1),
+        (7, 5, 4),
+    ];
+    creator
+        .finalize(Box::new(EventEntryStore(events)), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    let index = container
+        .get_index_for_name("Events")
+        .unwrap()
+        .expect("Events index exists");
+    assert!(!index.is_permutation());
+    let sort_keys = index
+        .sort_keys()
+        .iter()
+        .map(|k| (k.property_idx.into_u8(), k.order))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sort_keys,
+        [
+            (1, jubako::SortOrder::Descending),
+            (2, jubako::SortOrder::Ascending)
+        ]
+    );
+
+    let builder = jubako::reader::builder::AnyBuilder::new_for_index(
+        &index,
+        container.get_entry_storage(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    let get_id = |idx: jubako::EntryIdx| {
+        let entry = index.get_entry(&builder, idx).unwrap().unwrap();
+        entry.get_value("Id").unwrap().unwrap().as_unsigned()
+    };
+    let comparator = builder.new_multiple_property_compare(
+        vec!["Date".to_string(), "Kind".to_string()],
+        vec![jubako::Value::Unsigned(30), jubako::Value::Unsigned(5)],
+    );
+    assert!(comparator.ordered());
+    assert_eq!(index.find(&comparator).unwrap(), Some(4.into()));
+    for (id, date, kind) in [(0, 50, 1), (2, 40, 0), (3, 30, 2), (7, 5, 4)] {
+        let comparator = builder.new_multiple_property_compare(
+            vec!["Date".to_string(), "Kind".to_string()],
+            vec![jubako::Value::Unsigned(date), jubako::Value::Unsigned(kind)],
+        );
+        assert_eq!(get_id(index.find(&comparator).unwrap().unwrap()), id);
+    }
+    let comparator = builder.new_property_compare("Date".to_string(), jubako::Value::Unsigned(10));
+    assert!(comparator.ordered());
+    let found = get_id(index.find(&comparator).unwrap().unwrap());
+    assert!(found == 5 || found == 6);
+    let comparator = builder.new_property_compare("Date".to_string(), jubako::Value::Unsigned(45));
+    assert_eq!(index.find(&comparator).unwrap(), None);
+
+    // Kind is not the first sort key: linear search
+    let comparator = builder.new_property_compare("Kind".to_string(), jubako::Value::Unsigned(4));
+    assert!(!comparator.ordered());
+    assert_eq!(index.find(&comparator).unwrap(), Some(7.into()));
+
+    // A builder not created for the index doesn't know the sort keys,
+    // but the index does: searches in it are still binary searches.
+    let builder = jubako::reader::builder::AnyBuilder::new(
+        index.get_store(container.get_entry_storage()).unwrap(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    let comparator = builder.new_property_compare("Date".to_string(), jubako::Value::Unsigned(40));
+    assert!(!comparator.ordered());
+    assert_eq!(index.find(&comparator).unwrap(), Some(2.into()));
+    let comparator = builder.new_property_compare("Date".to_string(), jubako::Value::Unsigned(30));
+    assert_eq!(index.lower_bound(&comparator).unwrap(), 3.into());
+    assert_eq!(index.upper_bound(&comparator).unwrap(), 5.into());
+    let range = index.equal_range(&comparator).unwrap();
+    let ids = range
+        .iter(&builder)
+        .map(|entry| {
+            entry
+                .unwrap()
+                .get_value("Id")
+                .unwrap()
+                .unwrap()
+                .as_unsigned()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [3, 4]);
+    let comparator = builder.new_multiple_property_compare(
+        vec!["Date".to_string(), "Kind".to_string()],
+        vec![jubako::Value::Unsigned(10), jubako::Value::Unsigned(1)],
+    );
+    assert_eq!(index.equal_range(&comparator).unwrap().count(), 2.into());
+}
+
+#[test]
+fn test_secondary_index(temp_dir: rustest_fixtures::TempDir) {
+    use jubako::reader::CompareTrait;
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("events.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    let events = vec![
+        (0, 50, 1),
+        (1, 50, 3),
+        (2, 40, 0),
+        (3, 30, 2),
+        (4, 30, 5),
+        (5, 10, 1),
+        (6, 10, 1),
+        (7, 5, 4),
+    ];
+    creator
+        .finalize(Box::new(EventEntryStore(events)), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    let index = container
+        .get_index_for_name("EventsByKind")
+        .unwrap()
+        .expect("EventsByKind index exists");
+    assert!(index.is_permutation());
+    assert_eq!(index.count(), 8.into());
+    let sort_keys = index
+        .sort_keys()
+        .iter()
+        .map(|k| (k.property_idx.into_u8(), k.order))
+        .collect::<Vec<_>>();
+    assert_eq!(sort_keys, [(2, jubako::SortOrder::Ascending)]);
+
+    let builder = jubako::reader::builder::AnyBuilder::new_for_index(
+        &index,
+        container.get_entry_storage(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    fn entry_id(entry: jubako::Result<impl EntryTrait>) -> u64 {
+        entry
+            .unwrap()
+            .get_value("Id")
+            .unwrap()
+            .unwrap()
+            .as_unsigned()
+    }
+    // Events with the same kind stay in the entry store order.
+    let by_kind = [2, 0, 5, 6, 3, 1, 7, 4];
+    assert_eq!(
+        index.iter(&builder).map(entry_id).collect::<Vec<_>>(),
+        by_kind
+    );
+    assert_eq!(
+        index.par_iter(&builder).map(entry_id).collect::<Vec<_>>(),
+        by_kind
+    );
+    assert_eq!(
+        entry_id(Ok(index.get_entry(&builder, 1.into()).unwrap().unwrap())),
+        0
+    );
+    assert!(index.get_entry(&builder, 8.into()).unwrap().is_none());
+
+    let comparator = builder.new_property_compare("Kind".to_string(), jubako::Value::Unsigned(2));
+    assert!(comparator.ordered());
+    assert_eq!(index.find(&comparator).unwrap(), Some(4.into()));
+    let comparator = builder.new_property_compare("Kind".to_string(), jubako::Value::Unsigned(1));
+    let range = index.equal_range(&comparator).unwrap();
+    assert_eq!(
+        range.iter(&builder).map(entry_id).collect::<Vec<_>>(),
+        [0, 5, 6]
+    );
+    let comparator = builder.new_property_compare("Kind".to_string(), jubako::Value::Unsigned(6));
+    assert_eq!(index.find(&comparator).unwrap(), None);
+
+    // Date is not a sort key of the secondary index: linear search
+    let comparator = builder.new_property_compare("Date".to_string(), jubako::Value::Unsigned(30));
+    assert!(!comparator.ordered());
+    assert_eq!(index.find(&comparator).unwrap(), Some(4.into()));
+}
+
+#[test]
+fn test_unsorted_entries(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("events.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    // Oldest first
+    let events = vec![(0, 10, 1), (1, 30, 0)];
+    let result = creator.finalize(Box::new(EventEntryStore(events)), vec![]);
+    assert!(matches!(result, Err(creator::Error::Sort(_))));
+}
+
+// Entries sorted on their path.
+struct PathEntryStore(Vec<&'static str>);
+
+impl creator::EntryStoreCreatorTrait for PathEntryStore {
+    fn finalize(
+        self: Box<Self>,
+        directory_pack: &mut creator::DirectoryPackCreator,
+    ) -> creator::Result<()> {
+        type SimpleEntry = creator::SimpleEntry<&'static str, ()>;
+        let value_store = creator::ValueStore::new_plain(None);
+        let mut entry_def = schema::Schema::<&str, ()>::new(
+            schema::CommonProperties::new(vec![schema::Property::new_array(
+                4,
+                value_store.clone(),
+                "Path",
+            )]),
+            vec![],
+            Some(vec!["Path".into()]),
+        );
+        entry_def.add_hash_key("PathsByHash", "Path");
+        let count = self.0.len();
+        let entries = self.0.into_iter().map(|path| -> SimpleEntry {
+            HashMap::from([("Path", jubako::Value::Array(path.into()))]).into()
+        });
+        let entry_store = creator::EntryStore::new(entry_def, entries)?;
+        directory_pack.add_value_store(value_store);
+        let entry_store_idx = directory_pack.add_entry_store(entry_store);
+        directory_pack.create_range_index(
+            "Paths",
+            Default::default(),
+            entry_store_idx,
+            (count as u32).into(),
+            jubako::EntryIdx::from(0),
+        );
+        directory_pack.create_hash_index("PathsByHash", Default::default(), entry_store_idx);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_range_queries(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("paths.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    let paths = vec![
+        "README",
+        "dir",
+        "dir/a",
+        "dir/b",
+        "dir/b",
+        "dir/sub/c",
+        "dir2/d",
+        "foo",
+        "foobar",
+        "fop",
+    ];
+    creator
+        .finalize(Box::new(PathEntryStore(paths.clone())), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    let index = container
+        .get_index_for_name("Paths")
+        .unwrap()
+        .expect("Paths index exists");
+    let builder = jubako::reader::builder::AnyBuilder::new_for_index(
+        &index,
+        container.get_entry_storage(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    fn entry_path(entry: jubako::Result<impl EntryTrait>) -> String {
+        let path = entry.unwrap().get_value("Path").unwrap().unwrap();
+        String::from_utf8(path.as_vec().unwrap().to_vec()).unwrap()
+    }
+    let get_paths = |range: jubako::reader::IndexRange| {
+        range.iter(&builder).map(entry_path).collect::<Vec<_>>()
+    };
+
+    let comparator =
+        builder.new_property_compare("Path".to_string(), jubako::Value::Array("dir/b".into()));
+    assert_eq!(index.lower_bound(&comparator).unwrap(), 3.into());
+    assert_eq!(index.upper_bound(&comparator).unwrap(), 5.into());
+    assert_eq!(
+        get_paths(index.equal_range(&comparator).unwrap()),
+        ["dir/b", "dir/b"]
+    );
+    let comparator =
+        builder.new_property_compare("Path".to_string(), jubako::Value::Array("dir/0".into()));
+    assert_eq!(index.lower_bound(&comparator).unwrap(), 2.into());
+    assert_eq!(index.upper_bound(&comparator).unwrap(), 2.into());
+    assert_eq!(index.equal_range(&comparator).unwrap().count(), 0.into());
+
+    let comparator = builder.new_prefix_compare("Path".to_string(), "dir/");
+    assert_eq!(
+        get_paths(index.equal_range(&comparator).unwrap()),
+        ["dir/a", "dir/b", "dir/b", "dir/sub/c"]
+    );
+    let comparator = builder.new_prefix_compare("Path".to_string(), "foo");
+    assert_eq!(
+        get_paths(index.equal_range(&comparator).unwrap()),
+        ["foo", "foobar"]
+    );
+    let comparator = builder.new_prefix_compare("Path".to_string(), "zzz");
+    let range = index.equal_range(&comparator).unwrap();
+    assert_eq!(range.offset(), 10.into());
+    assert_eq!(range.count(), 0.into());
+    let comparator = builder.new_prefix_compare("Path".to_string(), "");
+    assert_eq!(index.equal_range(&comparator).unwrap().count(), 10.into());
+
+    assert_eq!(
+        index
+            .iter(&builder)
+            .skip(1)
+            .step_by(3)
+            .map(entry_path)
+            .collect::<Vec<_>>(),
+        ["dir", "dir/b", "foo"]
+    );
+    assert_eq!(
+        index
+            .iter(&builder)
+            .rev()
+            .take(2)
+            .map(entry_path)
+            .collect::<Vec<_>>(),
+        ["fop", "foobar"]
+    );
+    let all_paths: Vec<_> = index.par_iter(&builder).map(entry_path).collect();
+    assert_eq!(all_paths, paths);
+}
+
+#[test]
+fn test_hash_index(temp_dir: rustest_fixtures::TempDir) {
+    let utf8_temp_dir = utf8_temp_dir(&temp_dir);
+    let path = utf8_temp_dir.join("paths.jbk");
+    let creator = basic_creator(&path, creator::ConcatMode::OneFile);
+    let paths = vec![
+        "README",
+        "dir",
+        "dir/a",
+        "dir/b",
+        "dir/b",
+        "dir/sub/c",
+        "dir2/d",
+        "foo",
+        "foobar",
+        "fop",
+    ];
+    creator
+        .finalize(Box::new(PathEntryStore(paths.clone())), vec![])
+        .unwrap();
+
+    let container = jubako::reader::Container::new(&path).unwrap();
+    let sorted_index = container
+        .get_index_for_name("Paths")
+        .unwrap()
+        .expect("Paths index exists");
+    assert!(!sorted_index.is_hash());
+    let index = container
+        .get_index_for_name("PathsByHash")
+        .unwrap()
+        .expect("PathsByHash index exists");
+    assert!(index.is_hash());
+    assert_eq!(index.count(), 10.into());
+    let builder = jubako::reader::builder::AnyBuilder::new_for_index(
+        &index,
+        container.get_entry_storage(),
+        container.get_value_storage().as_ref(),
+    )
+    .unwrap();
+    let lookup = |path: &str| {
+        index
+            .lookup(&builder, &jubako::Value::Array(path.into()))
+            .unwrap()
+    };
+    for (idx, path) in paths.iter().enumerate() {
+        if *path == "dir/b" {
+            continue;
+        }
+        assert_eq!(lookup(path), Some((idx as u32).into()));
+    }
+    // First entry of duplicated keys is found.
+    assert_eq!(lookup("dir/b"), Some(3.into()));
+    assert_eq!(lookup("dir/"), None);
+    assert_eq!(lookup("zzz"), None);
+    assert_eq!(lookup(""), None);
+
+    let entry = index
+        .get_entry(&builder, lookup("dir2/d").unwrap())
+        .unwrap()
+        .unwrap();
+    let value = entry.get_value("Path").unwrap().unwrap();
+    assert_eq!(value.as_vec().unwrap(), b"dir2/d");
+
+    // Only hash indexes can be looked up.
+    let err = sorted_index
+        .lookup(&builder, &jubako::Value::Array("foo".into()))
+        .unwrap_err();
+    assert!(matches!(*err, jubako::ErrorKind::IndexKind(_)), "{err:?}");
+}
+
 #[main]
 fn main() {}
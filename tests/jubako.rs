@@ -204,7 +204,7 @@ mod mock {
     pub struct Index {
         store_id: u32,
         entry_count: u32,
-        index_key: u8,
+        sort_key_count: u8,
         index_name: String,
         tail_size: Option<u16>,
     }
@@ -214,7 +214,7 @@ mod mock {
             Index {
                 store_id: 0,
                 entry_count: entries.len() as u32,
-                index_key: 0,
+                sort_key_count: 0,
                 index_name: "Super index".to_string(),
                 tail_size: None,
             }
@@ -226,7 +226,7 @@ mod mock {
             data.extend(self.entry_count.to_le_bytes()); // entry_count
             data.extend(0_u32.to_le_bytes()); // entry_offset
             data.extend([0; 4]); // free_data
-            data.extend(self.index_key.to_le_bytes()); // index_key
+            data.extend(self.sort_key_count.to_le_bytes()); // kind (range) and sort_key_count
             data.push(self.index_name.len() as u8);
             data.extend(self.index_name.bytes()); // The index name
             self.tail_size = Some(data.len() as u16);
@@ -274,7 +274,9 @@ mod mock {
             let mut data = vec![];
             data.extend(self.uuid.as_bytes());
             data.extend(self.pack_size.to_le_bytes());
-            data.extend(check_info_pos.to_le_bytes());
+            // SizedOffset: offset on 6 bytes, size (without crc) on 2 bytes
+            let check_info_size = self.check_info.size() - 4;
+            data.extend(((check_info_pos << 16) + check_info_size).to_le_bytes());
             data.extend(self.pack_id.to_le_bytes());
             data.push(self.pack_kind);
             data.push(0); // pack_group
@@ -530,9 +532,11 @@ fn create_directory_pack(entries: &Vec<TestEntry>) -> Result<PackInfo> {
     let mut hasher = blake3::Hasher::new();
     io::copy(&mut file, &mut hasher)?;
     let hash = hasher.finalize();
-    file.write_all(&[0x01])?;
-    file.write_all(hash.as_bytes())?;
-    file.write_all(&[0x25, 0x9F, 0x55, 0x12])?; // Crc32
+    let check_info = CheckInfo {
+        kind: 1,
+        data: hash.as_bytes().to_vec(),
+    };
+    file.write_all(&check_info.bytes())?;
 
     // Write footer
     file.seek(SeekFrom::Start(0))?;
@@ -612,9 +616,11 @@ fn create_main_pack(directory_pack: PackInfo, content_pack: PackInfo) -> Result<
     }
     io::copy(&mut file, &mut hasher)?; // finish
     let hash = hasher.finalize();
-    file.write_all(&[0x01])?;
-    file.write_all(hash.as_bytes())?;
-    file.write_all(&[0xD9, 0xA9, 0xF7, 0xDE])?; // Crc32
+    let check_info = CheckInfo {
+        kind: 1,
+        data: hash.as_bytes().to_vec(),
+    };
+    file.write_all(&check_info.bytes())?;
 
     // Write footer
     file.seek(SeekFrom::Start(0))?;